@@ -55,7 +55,10 @@ try {
     ValidatorRegistryNapi,
     getVersion,
   } = require("../rust/validator_napi");
-  validatorRegistry = new ValidatorRegistryNapi();
+  validatorRegistry = new ValidatorRegistryNapi(
+    process.env.BIZRA_GENESIS_PATH,
+    process.env.BIZRA_DATA_DIR
+  );
   const version = getVersion();
   console.log("[NODE0] Validator Registry initialized via NAPI-RS:", version);
 } catch (error) {
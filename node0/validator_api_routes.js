@@ -7,7 +7,7 @@
  * ## Endpoints (8 total)
  *
  * Validator Registry:
- * - POST /api/validator/register    - Register new validator
+ * - POST /api/validator/register    - Register new validator (signed JoinTx)
  * - GET  /api/validator/:id          - Get validator details
  * - GET  /api/validator/list         - List active validators
  * - GET  /api/validator/stats        - Registry statistics
@@ -54,14 +54,17 @@ function createValidatorRouter(validatorRegistry = null) {
   /**
    * POST /api/validator/register
    *
-   * Register new validator (Pending state)
+   * Register new validator (Pending state) with a JoinTx signed by its key.
+   * The signature proves possession of pk_ed25519; the registry sets the epoch.
    *
-   * Body:
+   * Body (JoinTx):
    * {
+   *   "chain_id": "bizra-testnet-001",
    *   "validator_id": "hex32",
    *   "pk_ed25519": "hex32",
    *   "network_address": "/ip4/127.0.0.1/tcp/9944",
-   *   "epoch": 0
+   *   "epoch": 0,
+   *   "signature": "hex64"
    * }
    *
    * Response:
@@ -69,19 +72,27 @@ function createValidatorRouter(validatorRegistry = null) {
    *   "success": true,
    *   "validator_id": "hex32",
    *   "status": "Pending",
-   *   "epoch_join": 0
+   *   "epoch_join": 0,
+   *   "stake_bond": "0"
    * }
    */
   router.post("/register", async (req, res) => {
     try {
-      const { validator_id, pk_ed25519, network_address, epoch } = req.body;
+      const joinTx = req.body;
 
       // Validate required fields
-      if (!validator_id || !pk_ed25519 || !network_address) {
+      const required = [
+        "chain_id",
+        "validator_id",
+        "pk_ed25519",
+        "network_address",
+        "signature",
+      ];
+      if (!joinTx || required.some((field) => !joinTx[field])) {
         return res.status(400).json({
           success: false,
           error: "Missing required fields",
-          required: ["validator_id", "pk_ed25519", "network_address"],
+          required,
         });
       }
 
@@ -89,20 +100,17 @@ function createValidatorRouter(validatorRegistry = null) {
       if (validatorRegistry === null) {
         return res.json({
           success: true,
-          validator_id,
+          validator_id: joinTx.validator_id,
           status: "Pending",
-          epoch_join: epoch || 0,
+          epoch_join: joinTx.epoch || 0,
           message:
             "Validator registered (placeholder - Rust NAPI integration pending)",
         });
       }
 
-      // Call Rust NAPI
-      const result = await validatorRegistry.register(
-        validator_id,
-        pk_ed25519,
-        network_address,
-        epoch || 0,
+      // Call Rust NAPI (verifies the signature before registering)
+      const result = await validatorRegistry.submitJoinTx(
+        JSON.stringify(joinTx),
       );
 
       res.json(result);
@@ -427,7 +435,7 @@ function createValidatorRouter(validatorRegistry = null) {
    * {
    *   "validator_id": "hex32",
    *   "pk_ed25519": "hex32",
-   *   "status": "Active|Pending|Exiting|Exited|Slashed",
+   *   "status": "Active|Pending|Offline|Exiting|Exited|Slashed",
    *   "poi_weight": 1000,
    *   "rep_score": 5000,
    *   "stake_bond": 10000,
@@ -10,36 +10,41 @@
 //! ????? Compliance: Explicit behavior composition with no hidden assumptions
 
 use libp2p::{
-    gossipsub::{self, Gossipsub, GossipsubConfig, MessageAuthenticity},
-    identify::{self, Identify, IdentifyConfig},
-    kad::{self, Kademlia, KademliaConfig, store::MemoryStore},
-    mdns::{self, Mdns, MdnsConfig},
-    ping::{self, Ping, PingConfig},
-    swarm::NetworkBehaviour,
+    gossipsub::{self, MessageAuthenticity},
+    identify,
+    kad::{self, store::MemoryStore},
+    mdns, ping,
+    swarm::{behaviour::toggle::Toggle, NetworkBehaviour},
     PeerId,
 };
 use std::time::Duration;
 
-use crate::{config::NetworkConfig, error::NetworkError};
+use crate::{
+    config::{NetworkConfig, ValidationMode},
+    error::NetworkError,
+};
+
+/// Identify protocol version advertised to peers
+pub const IDENTIFY_PROTOCOL_VERSION: &str = "bizra/1.0.0";
 
 /// Combined BIZRA network behavior
 #[derive(NetworkBehaviour)]
-#[behaviour(out_event = "BizraEvent")]
+#[behaviour(to_swarm = "BizraEvent")]
 pub struct BizraBehaviour {
     /// Gossipsub for pub-sub messaging (blocks, transactions)
-    pub gossipsub: Gossipsub,
+    pub gossipsub: gossipsub::Behaviour,
 
     /// Kademlia DHT for peer discovery and routing
-    pub kademlia: Kademlia<MemoryStore>,
+    pub kademlia: kad::Behaviour<MemoryStore>,
 
-    /// mDNS for local network discovery
-    pub mdns: Mdns,
+    /// mDNS for local network discovery (disabled unless `enable_mdns`)
+    pub mdns: Toggle<mdns::tokio::Behaviour>,
 
     /// Identify protocol for peer information exchange
-    pub identify: Identify,
+    pub identify: identify::Behaviour,
 
     /// Ping for connection health monitoring
-    pub ping: Ping,
+    pub ping: ping::Behaviour,
 }
 
 /// Events emitted by BizraBehaviour
@@ -55,12 +60,42 @@ pub enum BizraEvent {
     Mdns(mdns::Event),
 
     /// Peer identification event
-    Identify(identify::Event),
+    Identify(Box<identify::Event>),
 
     /// Ping event
     Ping(ping::Event),
 }
 
+impl From<gossipsub::Event> for BizraEvent {
+    fn from(event: gossipsub::Event) -> Self {
+        BizraEvent::Gossipsub(event)
+    }
+}
+
+impl From<kad::Event> for BizraEvent {
+    fn from(event: kad::Event) -> Self {
+        BizraEvent::Kademlia(event)
+    }
+}
+
+impl From<mdns::Event> for BizraEvent {
+    fn from(event: mdns::Event) -> Self {
+        BizraEvent::Mdns(event)
+    }
+}
+
+impl From<identify::Event> for BizraEvent {
+    fn from(event: identify::Event) -> Self {
+        BizraEvent::Identify(Box::new(event))
+    }
+}
+
+impl From<ping::Event> for BizraEvent {
+    fn from(event: ping::Event) -> Self {
+        BizraEvent::Ping(event)
+    }
+}
+
 impl BizraBehaviour {
     /// Create new BizraBehaviour with ????? compliance
     ///
@@ -77,45 +112,58 @@ impl BizraBehaviour {
     /// - NO assumptions about network capabilities
     /// - Explicit validation of all behavior configurations
     /// - Transparent error reporting for all failures
-    pub async fn new(local_peer_id: PeerId, keypair: &libp2p::identity::Keypair, config: &NetworkConfig) -> Result<Self, NetworkError> {
+    pub fn new(local_peer_id: PeerId, keypair: &libp2p::identity::Keypair, config: &NetworkConfig) -> Result<Self, NetworkError> {
         // Initialize Gossipsub for block/transaction propagation
-        let gossipsub_config = GossipsubConfig::default();
-        let gossipsub = Gossipsub::new(MessageAuthenticity::Signed(keypair.clone()), gossipsub_config)
+        let mut gossipsub_config = gossipsub::ConfigBuilder::default();
+        gossipsub_config.heartbeat_interval(Duration::from_secs(config.gossip_config.heartbeat_interval_secs));
+        if matches!(config.gossip_config.validation_mode, ValidationMode::Strict) {
+            // Messages are only forwarded once the node reports them valid
+            gossipsub_config.validate_messages();
+        }
+        let gossipsub_config = gossipsub_config
+            .build()
             .map_err(|e| NetworkError::BehaviourError {
                 behaviour: "gossipsub".to_string(),
-                source: e.into(),
+                source: anyhow::anyhow!(e),
+            })?;
+        let gossipsub = gossipsub::Behaviour::new(MessageAuthenticity::Signed(keypair.clone()), gossipsub_config)
+            .map_err(|e| NetworkError::BehaviourError {
+                behaviour: "gossipsub".to_string(),
+                source: anyhow::anyhow!(e),
             })?;
 
         // Initialize Kademlia DHT for peer discovery
         let kademlia_store = MemoryStore::new(local_peer_id);
-        let kademlia_config = KademliaConfig::default();
-        let mut kademlia = Kademlia::new(local_peer_id, kademlia_store);
-        kademlia.with_config(|c| {
-            c.set_query_timeout(Duration::from_secs(5 * 60)); // 5 minutes
-        });
+        let mut kademlia_config = kad::Config::default();
+        kademlia_config.set_query_timeout(Duration::from_secs(config.kad_config.query_timeout_secs));
+        let kademlia = kad::Behaviour::with_config(local_peer_id, kademlia_store, kademlia_config);
 
         // Initialize mDNS for local discovery
-        let mdns = Mdns::new(MdnsConfig::default())
-            .await
-            .map_err(|e| NetworkError::BehaviourError {
-                behaviour: "mdns".to_string(),
-                source: e.into(),
-            })?;
+        let mdns = if config.enable_mdns {
+            let mdns = mdns::tokio::Behaviour::new(mdns::Config::default(), local_peer_id)
+                .map_err(|e| NetworkError::BehaviourError {
+                    behaviour: "mdns".to_string(),
+                    source: e.into(),
+                })?;
+            Some(mdns)
+        } else {
+            None
+        };
 
         // Initialize Identify protocol
-        let identify_config = IdentifyConfig::new("bizra/1.0.0".to_string(), local_peer_id);
-        let identify = Identify::new(identify_config);
+        let identify_config = identify::Config::new(IDENTIFY_PROTOCOL_VERSION.to_string(), keypair.public());
+        let identify = identify::Behaviour::new(identify_config);
 
         // Initialize Ping for health monitoring
-        let ping_config = PingConfig::new()
+        let ping_config = ping::Config::new()
             .with_interval(Duration::from_secs(30)) // Ping every 30 seconds
             .with_timeout(Duration::from_secs(10)); // 10 second timeout
-        let ping = Ping::new(ping_config);
+        let ping = ping::Behaviour::new(ping_config);
 
         Ok(Self {
             gossipsub,
             kademlia,
-            mdns,
+            mdns: mdns.into(),
             identify,
             ping,
         })
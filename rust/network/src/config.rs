@@ -5,6 +5,7 @@
 use anyhow::{bail, Result};
 use libp2p::Multiaddr;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// BIZRA Network Configuration
@@ -29,6 +30,20 @@ pub struct NetworkConfig {
 
     /// Kademlia DHT configuration
     pub kad_config: KademliaConfig,
+
+    /// Discover peers on the local network via mDNS
+    #[serde(default = "default_enable_mdns")]
+    pub enable_mdns: bool,
+
+    /// Node key file (protobuf-encoded, created with mode 0600 if missing)
+    ///
+    /// Without it the node generates a fresh identity, and a new PeerId, on every start.
+    #[serde(default)]
+    pub identity_path: Option<PathBuf>,
+}
+
+fn default_enable_mdns() -> bool {
+    true
 }
 
 /// Gossipsub Configuration
@@ -45,6 +60,14 @@ pub struct GossipConfig {
 
     /// Heartbeat interval (seconds)
     pub heartbeat_interval_secs: u64,
+
+    /// Chain consensus messages are signed for; others are rejected
+    #[serde(default = "default_chain_id")]
+    pub chain_id: String,
+}
+
+fn default_chain_id() -> String {
+    validator::chain_spec::TESTNET_CHAIN_ID.to_string()
 }
 
 /// Kademlia DHT Configuration
@@ -78,6 +101,8 @@ impl Default for NetworkConfig {
             heartbeat_interval: Duration::from_secs(10),
             gossip_config: GossipConfig::default(),
             kad_config: KademliaConfig::default(),
+            enable_mdns: default_enable_mdns(),
+            identity_path: None,
         }
     }
 }
@@ -89,6 +114,7 @@ impl Default for GossipConfig {
             tx_topic: "bizra/transactions".to_string(),
             validation_mode: ValidationMode::Strict, // احسان principle
             heartbeat_interval_secs: 10,
+            chain_id: default_chain_id(),
         }
     }
 }
@@ -158,22 +184,19 @@ mod tests {
 
     #[test]
     fn test_zero_max_peers_fails() {
-        let mut config = NetworkConfig::default();
-        config.max_peers = 0;
+        let config = NetworkConfig { max_peers: 0, ..NetworkConfig::default() };
         assert!(config.validate().is_err(), "Zero max_peers should fail");
     }
 
     #[test]
     fn test_excessive_max_peers_fails() {
-        let mut config = NetworkConfig::default();
-        config.max_peers = 2000;
+        let config = NetworkConfig { max_peers: 2000, ..NetworkConfig::default() };
         assert!(config.validate().is_err(), "Excessive max_peers should fail");
     }
 
     #[test]
     fn test_short_timeout_fails() {
-        let mut config = NetworkConfig::default();
-        config.connection_timeout = Duration::from_secs(2);
+        let config = NetworkConfig { connection_timeout: Duration::from_secs(2), ..NetworkConfig::default() };
         assert!(config.validate().is_err(), "Short timeout should fail");
     }
 }
@@ -41,6 +41,14 @@ pub enum NetworkError {
     #[error("احسان violation: Invalid message from {peer_id}: {reason}")]
     InvalidMessage { peer_id: String, reason: String },
 
+    /// Consensus message signed by a validator we have no key for
+    #[error("احسان warning: Message from {peer_id} signed by unknown validator {validator_id}")]
+    UnknownValidator { peer_id: String, validator_id: String },
+
+    /// Consensus message signature does not verify
+    #[error("احسان violation: Invalid signature from {peer_id} for validator {validator_id}")]
+    InvalidSignature { peer_id: String, validator_id: String },
+
     /// Configuration validation failed
     #[error("احسان violation: Invalid configuration: {0}")]
     ConfigError(String),
@@ -65,6 +73,10 @@ pub enum NetworkError {
         source: anyhow::Error,
     },
 
+    /// Network event loop is not running (never started or shut down)
+    #[error("احسان violation: Network event loop is not running")]
+    NotRunning,
+
     /// Generic error with explicit احسان reporting
     #[error("احسان violation: {0}")]
     Other(#[from] anyhow::Error),
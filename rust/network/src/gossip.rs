@@ -7,56 +7,101 @@
 //! - Quorum certificates
 //!
 //! ????? Compliance: Cryptographic verification of all messages
-
-use libp2p::gossipsub;
+//!
+//! # Signatures
+//! Every consensus message is signed with the Ed25519 key of the validator
+//! named in it, over [`ConsensusMessage::signing_payload`], which also covers
+//! the [`GossipMessage`]'s chain id and timestamp. The message types
+//! and their signing format live in [`validator::consensus_message`], so the
+//! registry checks slash evidence against the same bytes. Validator IDs
+//! resolve to keys through a [`ValidatorKeyLookup`], which the validator
+//! registry implements.
+//!
+//! With [`ValidationMode::Strict`](crate::config::ValidationMode::Strict) the
+//! network reports each result to Gossipsub ([`acceptance_for`]), so messages
+//! that fail verification are not forwarded.
+
+use ed25519_dalek::SigningKey;
+use futures::future::BoxFuture;
+use libp2p::gossipsub::{MessageAcceptance, MessageId};
+use libp2p::PeerId;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::{info, warn, error};
+use tracing::{debug, info, warn};
+use validator::{ValidatorRegistry, ValidatorStatus};
 
 use crate::error::NetworkError;
 
-/// HotStuff consensus message types
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum ConsensusMessage {
-    /// Block proposal from leader
-    BlockProposal {
-        block_data: Vec<u8>, // Serialized Block
-        proposer_id: Vec<u8>, // Validator ID
-        view: u64,
-        signature: Vec<u8>,
-    },
+pub use validator::consensus_message::{ConsensusMessage, SignedConsensusMessage, VoteType, GOSSIP_SIGNING_DOMAIN};
 
-    /// Vote on proposed block
-    Vote {
-        block_hash: Vec<u8>, // Block hash being voted on
-        voter_id: Vec<u8>,   // Validator ID
-        vote_type: VoteType, // Prepare, PreCommit, or Commit
-        view: u64,
-        signature: Vec<u8>,
-    },
+/// Topics whose messages are [`GossipMessage`]s and get signature-checked
+pub const CONSENSUS_TOPIC_PREFIX: &str = "bizra-consensus-";
 
-    /// New view message (view change)
-    NewView {
-        new_view: u64,
-        highest_qc: Vec<u8>, // Serialized QuorumCertificate
-        validator_id: Vec<u8>,
-        signature: Vec<u8>,
-    },
+/// Whether `topic` carries consensus messages
+pub fn is_consensus_topic(topic: &str) -> bool {
+    topic.starts_with(CONSENSUS_TOPIC_PREFIX)
+}
 
-    /// Timeout message (for view changes)
-    Timeout {
-        view: u64,
-        validator_id: Vec<u8>,
-        signature: Vec<u8>,
-    },
+/// Resolves validator IDs to the Ed25519 keys they currently sign with
+///
+/// Returns `None` for validators that are unknown or not allowed to sign
+/// consensus messages.
+pub trait ValidatorKeyLookup: Send + Sync {
+    fn ed25519_key<'a>(&'a self, validator_id: &'a [u8; 32]) -> BoxFuture<'a, Option<[u8; 32]>>;
+
+    /// Called for a message whose signature does not verify against the
+    /// signer's current key (no-op by default)
+    fn on_invalid_signature<'a>(&'a self, _message: &'a SignedConsensusMessage) -> BoxFuture<'a, ()> {
+        Box::pin(async {})
+    }
 }
 
-/// Vote types in HotStuff 3-phase protocol
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum VoteType {
-    Prepare,
-    PreCommit,
-    Commit,
+/// Fixed validator set (validator ID → Ed25519 public key)
+impl ValidatorKeyLookup for HashMap<[u8; 32], [u8; 32]> {
+    fn ed25519_key<'a>(&'a self, validator_id: &'a [u8; 32]) -> BoxFuture<'a, Option<[u8; 32]>> {
+        Box::pin(async move { self.get(validator_id).copied() })
+    }
+}
+
+/// Current key of validators in the active set, or Offline ones (their
+/// messages are how they come back)
+///
+/// A message signed by one of the signer's rotated-out keys is applied as
+/// key-theft evidence.
+impl ValidatorKeyLookup for ValidatorRegistry {
+    fn ed25519_key<'a>(&'a self, validator_id: &'a [u8; 32]) -> BoxFuture<'a, Option<[u8; 32]>> {
+        Box::pin(async move {
+            self.get(validator_id)
+                .await
+                .filter(|record| record.status.is_active_set() || record.status == ValidatorStatus::Offline)
+                .map(|record| record.pk_ed25519)
+        })
+    }
+
+    fn on_invalid_signature<'a>(&'a self, message: &'a SignedConsensusMessage) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let Some(evidence) = self.retired_key_evidence(message).await else {
+                return;
+            };
+            let validator_id = hex::encode(evidence.validator_id);
+            match self.submit_slash_evidence(evidence).await {
+                Ok(_) => warn!(validator = %validator_id, "Slashed for a message signed by a retired key"),
+                Err(e) => debug!(validator = %validator_id, "Retired-key evidence not applied: {}", e),
+            }
+        })
+    }
+}
+
+/// Gossipsub verdict for a validation result
+///
+/// Unknown validators are ignored rather than rejected: our registry may lag
+/// the sender's, and the forwarding peer is not at fault.
+pub fn acceptance_for(result: &Result<(), NetworkError>) -> MessageAcceptance {
+    match result {
+        Ok(()) => MessageAcceptance::Accept,
+        Err(NetworkError::UnknownValidator { .. }) => MessageAcceptance::Ignore,
+        Err(_) => MessageAcceptance::Reject,
+    }
 }
 
 /// Gossip message wrapper with metadata
@@ -65,9 +110,12 @@ pub struct GossipMessage {
     /// Consensus message payload
     pub message: ConsensusMessage,
 
-    /// Message timestamp (Unix milliseconds)
+    /// Message timestamp (Unix milliseconds, signed)
     pub timestamp: u64,
 
+    /// Chain the message is signed for
+    pub chain_id: String,
+
     /// Sender peer ID (for verification)
     pub sender_peer_id: Vec<u8>,
 
@@ -75,6 +123,121 @@ pub struct GossipMessage {
     pub sequence_number: u64,
 }
 
+impl GossipMessage {
+    /// Sign the consensus message for this message's chain and timestamp
+    pub fn sign(&mut self, key: &SigningKey) {
+        self.message.sign(key, &self.chain_id, self.timestamp);
+    }
+
+    /// The consensus message with what its signature covers, as slash proofs carry it
+    pub fn signed(&self) -> SignedConsensusMessage {
+        SignedConsensusMessage {
+            chain_id: self.chain_id.clone(),
+            timestamp: self.timestamp,
+            message: self.message.clone(),
+        }
+    }
+
+    /// Wire encoding (bincode)
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("bincode serialization should never fail")
+    }
+
+    /// Decode a message received from `sender`
+    pub fn decode(data: &[u8], sender: &PeerId) -> Result<Self, NetworkError> {
+        bincode::deserialize(data).map_err(|e| NetworkError::InvalidMessage {
+            peer_id: sender.to_string(),
+            reason: format!("Undecodable consensus message: {}", e),
+        })
+    }
+
+    /// Structure and timestamp checks
+    pub fn check(&self, sender: &PeerId) -> Result<(), NetworkError> {
+        let invalid = |reason: &str| {
+            Err(NetworkError::InvalidMessage { peer_id: sender.to_string(), reason: reason.to_string() })
+        };
+
+        // Check timestamp (within 5 minutes of now)
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let time_diff = self.timestamp.abs_diff(now);
+
+        if time_diff > 5 * 60 * 1000 { // 5 minutes
+            return invalid("Message timestamp too old or too far in future");
+        }
+
+        // Validate message structure based on type
+        match &self.message {
+            ConsensusMessage::BlockProposal { block_data, proposer_id, .. } => {
+                if block_data.is_empty() {
+                    return invalid("Empty block data in proposal");
+                }
+                if proposer_id.len() != 32 {
+                    return invalid("Invalid proposer ID length");
+                }
+            }
+
+            ConsensusMessage::Vote { block_hash, voter_id, .. } => {
+                if block_hash.len() != 32 {
+                    return invalid("Invalid block hash length");
+                }
+                if voter_id.len() != 32 {
+                    return invalid("Invalid voter ID length");
+                }
+            }
+
+            ConsensusMessage::NewView { highest_qc, validator_id, .. } => {
+                if highest_qc.is_empty() {
+                    return invalid("Empty QC in NewView");
+                }
+                if validator_id.len() != 32 {
+                    return invalid("Invalid validator ID length");
+                }
+            }
+
+            ConsensusMessage::Timeout { validator_id, .. } => {
+                if validator_id.len() != 32 {
+                    return invalid("Invalid validator ID length");
+                }
+            }
+        }
+
+        if self.message.signature().len() != 64 {
+            return invalid("Invalid signature length");
+        }
+
+        Ok(())
+    }
+
+    /// [`Self::check`], then verify the signature for `chain_id` with the signer's key from `keys`
+    pub async fn verify(&self, sender: &PeerId, keys: &dyn ValidatorKeyLookup, chain_id: &str) -> Result<(), NetworkError> {
+        self.check(sender)?;
+        if self.chain_id != chain_id {
+            return Err(NetworkError::InvalidMessage {
+                peer_id: sender.to_string(),
+                reason: format!("Message signed for chain {}, expected {}", self.chain_id, chain_id),
+            });
+        }
+
+        let signer: [u8; 32] = self.message.signer_id().try_into().expect("length checked");
+        let key = keys.ed25519_key(&signer).await.ok_or_else(|| NetworkError::UnknownValidator {
+            peer_id: sender.to_string(),
+            validator_id: hex::encode(signer),
+        })?;
+        // Same strict check as slash evidence, so gossip and proofs agree
+        if !self.message.verify_signature(&key, &self.chain_id, self.timestamp) {
+            return Err(NetworkError::InvalidSignature {
+                peer_id: sender.to_string(),
+                validator_id: hex::encode(signer),
+            });
+        }
+        Ok(())
+    }
+}
+
 /// BIZRA Gossip Handler
 ///
 /// Manages consensus message propagation with ????? compliance
@@ -83,7 +246,7 @@ pub struct GossipHandler {
     active_topics: HashMap<u64, String>,
 
     /// Message deduplication cache
-    seen_messages: HashMap<gossipsub::MessageId, u64>, // MessageId -> timestamp
+    seen_messages: HashMap<MessageId, u64>, // MessageId -> timestamp
 
     /// Maximum cache size
     max_cache_size: usize,
@@ -93,6 +256,9 @@ pub struct GossipHandler {
 
     /// Message counter for cleanup
     message_count: usize,
+
+    /// Chain messages are created and verified for
+    chain_id: String,
 }
 
 impl GossipHandler {
@@ -100,17 +266,19 @@ impl GossipHandler {
     ///
     /// # Arguments
     /// - `max_cache_size`: Maximum messages to cache for deduplication
+    /// - `chain_id`: Chain messages are signed for
     ///
     /// # ????? Compliance
     /// - Explicit cache size limits
     /// - Transparent deduplication logic
-    pub fn new(max_cache_size: usize) -> Self {
+    pub fn new(max_cache_size: usize, chain_id: &str) -> Self {
         Self {
             active_topics: HashMap::new(),
             seen_messages: HashMap::new(),
             max_cache_size,
             cleanup_interval: max_cache_size / 4, // Cleanup at 25% capacity
             message_count: 0,
+            chain_id: chain_id.to_string(),
         }
     }
 
@@ -126,7 +294,7 @@ impl GossipHandler {
     /// - Deterministic topic naming
     /// - No assumptions about view validity
     pub fn topic_for_view(&self, view: u64) -> String {
-        format!("bizra-consensus-view-{}", view)
+        format!("{}view-{}", CONSENSUS_TOPIC_PREFIX, view)
     }
 
     /// Register active view topic
@@ -192,101 +360,37 @@ impl GossipHandler {
         }
     }
 
-    /// Validate consensus message
+    /// Validate consensus message structure and timestamp
     ///
     /// # Arguments
     /// - `message`: GossipMessage to validate
+    /// - `sender`: Peer the message came from (named in errors)
     ///
     /// # Returns
     /// - `Ok(())`: Message is valid
     /// - `Err(NetworkError)`: Message validation failed
     ///
     /// # ????? Compliance
-    /// - Cryptographic verification of signatures
     /// - Timestamp validation (not too old/future)
     /// - Message structure validation
-    pub fn validate_message(&self, message: &GossipMessage) -> Result<(), NetworkError> {
-        // Check timestamp (within 5 minutes of now)
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64;
-
-        let time_diff = if message.timestamp > now {
-            message.timestamp - now
-        } else {
-            now - message.timestamp
-        };
-
-        if time_diff > 5 * 60 * 1000 { // 5 minutes
-            return Err(NetworkError::InvalidMessage {
-                peer_id: "unknown".to_string(),
-                reason: "Message timestamp too old or too far in future".to_string(),
-            });
-        }
-
-        // Validate message structure based on type
-        match &message.message {
-            ConsensusMessage::BlockProposal { block_data, proposer_id, .. } => {
-                if block_data.is_empty() {
-                    return Err(NetworkError::InvalidMessage {
-                        peer_id: "unknown".to_string(),
-                        reason: "Empty block data in proposal".to_string(),
-                    });
-                }
-                if proposer_id.len() !=32 {
-                    return Err(NetworkError::InvalidMessage {
-                        peer_id: "unknown".to_string(),
-                        reason: "Invalid proposer ID length".to_string(),
-                    });
-                }
-                // TODO: Verify signature when we have public key
-            }
-
-            ConsensusMessage::Vote { block_hash, voter_id, .. } => {
-                if block_hash.len() !=32 {
-                    return Err(NetworkError::InvalidMessage {
-                        peer_id: "unknown".to_string(),
-                        reason: "Invalid block hash length".to_string(),
-                    });
-                }
-                if voter_id.len() !=32 {
-                    return Err(NetworkError::InvalidMessage {
-                        peer_id: "unknown".to_string(),
-                        reason: "Invalid voter ID length".to_string(),
-                    });
-                }
-                // TODO: Verify signature when we have public key
-            }
-
-            ConsensusMessage::NewView { highest_qc, validator_id, .. } => {
-                if highest_qc.is_empty() {
-                    return Err(NetworkError::InvalidMessage {
-                        peer_id: "unknown".to_string(),
-                        reason: "Empty QC in NewView".to_string(),
-                    });
-                }
-                if validator_id.len() !=32 {
-                    return Err(NetworkError::InvalidMessage {
-                        peer_id: "unknown".to_string(),
-                        reason: "Invalid validator ID length".to_string(),
-                    });
-                }
-                // TODO: Verify signature when we have public key
-            }
-
-            ConsensusMessage::Timeout { validator_id, .. } => {
-                if validator_id.len() !=32 {
-                    return Err(NetworkError::InvalidMessage {
-                        peer_id: "unknown".to_string(),
-                        reason: "Invalid validator ID length".to_string(),
-                    });
-                }
-                // TODO: Verify signature when we have public key
-            }
-        }
+    /// - Signatures are checked by [`Self::verify_message`]
+    pub fn validate_message(&self, message: &GossipMessage, sender: &PeerId) -> Result<(), NetworkError> {
+        message.check(sender)
+    }
 
-        Ok(())
+    /// Validate a message and verify its signature against the signer's registered key
+    ///
+    /// # Returns
+    /// - `Err(NetworkError::InvalidMessage)`: Malformed, stale, or for another chain
+    /// - `Err(NetworkError::UnknownValidator)`: No key for the signer
+    /// - `Err(NetworkError::InvalidSignature)`: Signature does not match
+    pub async fn verify_message(
+        &self,
+        message: &GossipMessage,
+        sender: &PeerId,
+        keys: &dyn ValidatorKeyLookup,
+    ) -> Result<(), NetworkError> {
+        message.verify(sender, keys, &self.chain_id).await
     }
 
     /// Create block proposal message
@@ -295,7 +399,7 @@ impl GossipHandler {
     /// - `block_data`: Serialized block data
     /// - `proposer_id`: Validator ID of proposer
     /// - `view`: Current view number
-    /// - `signature`: Ed25519 signature
+    /// - `signature`: Ed25519 signature (or empty, then [`GossipMessage::sign`])
     ///
     /// # Returns
     /// - GossipMessage ready for publishing
@@ -323,6 +427,7 @@ impl GossipHandler {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64,
+            chain_id: self.chain_id.clone(),
             sender_peer_id: proposer_id,
             sequence_number: 0, // TODO: Implement sequence numbers
         }
@@ -335,7 +440,7 @@ impl GossipHandler {
     /// - `voter_id`: Validator ID of voter
     /// - `vote_type`: Type of vote (Prepare/PreCommit/Commit)
     /// - `view`: Current view number
-    /// - `signature`: Ed25519 signature
+    /// - `signature`: Ed25519 signature (or empty, then [`GossipMessage::sign`])
     ///
     /// # Returns
     /// - GossipMessage ready for publishing
@@ -365,6 +470,7 @@ impl GossipHandler {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64,
+            chain_id: self.chain_id.clone(),
             sender_peer_id: voter_id,
             sequence_number: 0, // TODO: Implement sequence numbers
         }
@@ -419,15 +525,17 @@ impl GossipHandler {
 mod tests {
     use super::*;
 
+    const CHAIN: &str = validator::chain_spec::TESTNET_CHAIN_ID;
+
     #[test]
     fn test_topic_naming() {
-        let handler = GossipHandler::new(1000);
+        let handler = GossipHandler::new(1000, CHAIN);
         assert_eq!(handler.topic_for_view(42), "bizra-consensus-view-42");
     }
 
     #[test]
     fn test_view_topic_activation() {
-        let mut handler = GossipHandler::new(1000);
+        let mut handler = GossipHandler::new(1000, CHAIN);
 
         handler.activate_view_topic(1);
         assert_eq!(handler.active_topics.len(), 1);
@@ -439,7 +547,8 @@ mod tests {
 
     #[test]
     fn test_message_validation() {
-        let handler = GossipHandler::new(1000);
+        let handler = GossipHandler::new(1000, CHAIN);
+        let sender = PeerId::random();
 
         // Valid block proposal
         let valid_message = GossipMessage {
@@ -453,11 +562,12 @@ mod tests {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64,
+            chain_id: CHAIN.to_string(),
             sender_peer_id: vec![0u8; 32],
             sequence_number: 0,
         };
 
-        assert!(handler.validate_message(&valid_message).is_ok());
+        assert!(handler.validate_message(&valid_message, &sender).is_ok());
 
         // Invalid: Empty block data
         let invalid_message = GossipMessage {
@@ -471,10 +581,200 @@ mod tests {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64,
+            chain_id: CHAIN.to_string(),
             sender_peer_id: vec![0u8; 32],
             sequence_number: 0,
         };
 
-        assert!(handler.validate_message(&invalid_message).is_err());
+        // Errors name the sending peer
+        match handler.validate_message(&invalid_message, &sender) {
+            Err(NetworkError::InvalidMessage { peer_id, .. }) => assert_eq!(peer_id, sender.to_string()),
+            other => panic!("expected InvalidMessage, got {:?}", other),
+        }
+    }
+
+    fn signed_vote(key: &SigningKey, view: u64) -> GossipMessage {
+        let voter_id = blake3::hash(key.verifying_key().as_bytes()).as_bytes().to_vec();
+        let mut message = GossipHandler::new(1000, CHAIN).create_vote(vec![9u8; 32], voter_id, VoteType::Prepare, view, vec![]);
+        message.sign(key);
+        message
+    }
+
+    #[tokio::test]
+    async fn test_signature_verification() {
+        let handler = GossipHandler::new(1000, CHAIN);
+        let sender = PeerId::random();
+        let key = SigningKey::from_bytes(&[1u8; 32]);
+        let message = signed_vote(&key, 7);
+        let voter: [u8; 32] = message.message.signer_id().try_into().unwrap();
+        let keys = HashMap::from([(voter, key.verifying_key().to_bytes())]);
+
+        handler.verify_message(&message, &sender, &keys).await.unwrap();
+        assert!(matches!(acceptance_for(&Ok(())), MessageAcceptance::Accept));
+
+        // Survives the wire encoding
+        let decoded = GossipMessage::decode(&message.encode(), &sender).unwrap();
+        handler.verify_message(&decoded, &sender, &keys).await.unwrap();
+
+        // Any signed field changed → rejected, tagged with the sender
+        let mut tampered = message.clone();
+        if let ConsensusMessage::Vote { view, .. } = &mut tampered.message {
+            *view = 8;
+        }
+        let result = handler.verify_message(&tampered, &sender, &keys).await;
+        match &result {
+            Err(NetworkError::InvalidSignature { peer_id, validator_id }) => {
+                assert_eq!(peer_id, &sender.to_string());
+                assert_eq!(validator_id, &hex::encode(voter));
+            }
+            other => panic!("expected InvalidSignature, got {:?}", other),
+        }
+        assert!(matches!(acceptance_for(&result), MessageAcceptance::Reject));
+
+        // Chain and timestamp are signed too
+        let mut replayed = message.clone();
+        replayed.timestamp += 1;
+        assert!(matches!(
+            handler.verify_message(&replayed, &sender, &keys).await,
+            Err(NetworkError::InvalidSignature { .. })
+        ));
+        let mut foreign = message.clone();
+        foreign.chain_id = "bizra-other".to_string();
+        foreign.sign(&key);
+        assert!(matches!(
+            handler.verify_message(&foreign, &sender, &keys).await,
+            Err(NetworkError::InvalidMessage { .. })
+        ));
+
+        // Signed by someone else's key
+        let mut forged = message.clone();
+        forged.sign(&SigningKey::from_bytes(&[2u8; 32]));
+        assert!(matches!(
+            handler.verify_message(&forged, &sender, &keys).await,
+            Err(NetworkError::InvalidSignature { .. })
+        ));
+
+        // Non-canonical scalar: the group order added to S
+        const GROUP_ORDER: [u8; 32] = [
+            0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+        ];
+        let mut malleated = message.clone();
+        if let ConsensusMessage::Vote { signature, .. } = &mut malleated.message {
+            let mut carry = 0u16;
+            for (byte, order) in signature[32..].iter_mut().zip(GROUP_ORDER) {
+                let sum = *byte as u16 + order as u16 + carry;
+                *byte = sum as u8;
+                carry = sum >> 8;
+            }
+        }
+        assert!(matches!(
+            handler.verify_message(&malleated, &sender, &keys).await,
+            Err(NetworkError::InvalidSignature { .. })
+        ));
+
+        // Small-order key and signature point, which verify any message unless strict
+        let mut weak_pk = [0u8; 32];
+        weak_pk[0] = 1;
+        let mut weak = message.clone();
+        if let ConsensusMessage::Vote { signature, .. } = &mut weak.message {
+            *signature = [weak_pk.to_vec(), vec![0u8; 32]].concat();
+        }
+        let weak_keys = HashMap::from([(voter, weak_pk)]);
+        assert!(matches!(
+            handler.verify_message(&weak, &sender, &weak_keys).await,
+            Err(NetworkError::InvalidSignature { .. })
+        ));
+        assert!(!weak.signed().verify_signature(&weak_pk));
+
+        // Unknown validator → ignored, not rejected
+        let result = handler.verify_message(&message, &sender, &HashMap::new()).await;
+        assert!(matches!(result, Err(NetworkError::UnknownValidator { .. })));
+        assert!(matches!(acceptance_for(&result), MessageAcceptance::Ignore));
+
+        assert!(GossipMessage::decode(b"garbage", &sender).is_err());
+    }
+
+    #[test]
+    fn test_signing_payload_is_domain_separated() {
+        let timeout = ConsensusMessage::Timeout { view: 5, validator_id: vec![3u8; 32], signature: vec![] };
+        let new_view = ConsensusMessage::NewView {
+            new_view: 5,
+            highest_qc: vec![],
+            validator_id: vec![3u8; 32],
+            signature: vec![],
+        };
+
+        let payload = timeout.signing_payload(CHAIN, 1_000);
+        assert!(payload.starts_with(GOSSIP_SIGNING_DOMAIN));
+        assert_ne!(payload, new_view.signing_payload(CHAIN, 1_000));
+        assert_ne!(payload, timeout.signing_payload("bizra-other", 1_000));
+        assert_ne!(payload, timeout.signing_payload(CHAIN, 1_001));
+
+        // The signature is not part of what is signed
+        let mut signed = timeout.clone();
+        signed.sign(&SigningKey::from_bytes(&[1u8; 32]), CHAIN, 1_000);
+        assert_eq!(signed.signing_payload(CHAIN, 1_000), payload);
+        assert_eq!(signed.signature().len(), 64);
+    }
+
+    /// Join `registry` with a signed JoinTx; Pending until the next epoch
+    async fn join(registry: &ValidatorRegistry, key: &SigningKey) -> [u8; 32] {
+        use validator::tx::JoinTx;
+
+        let epoch = registry.get_epoch().await;
+        let mut tx = JoinTx::new(registry.chain_spec().chain_id.clone(), key, "/ip4/127.0.0.1/tcp/1", None, epoch);
+        tx.sign(key).unwrap();
+        registry.submit_join_tx(&tx).await.unwrap().validator_id
+    }
+
+    #[tokio::test]
+    async fn test_registry_key_lookup() {
+        let registry = ValidatorRegistry::new();
+        let key = SigningKey::from_bytes(&[4u8; 32]);
+        let pk = key.verifying_key().to_bytes();
+        let validator_id = join(&registry, &key).await;
+
+        // Pending validators may not sign consensus messages
+        assert_eq!(registry.ed25519_key(&validator_id).await, None);
+
+        registry.advance_epoch().await.unwrap();
+        assert_eq!(registry.get(&validator_id).await.unwrap().status, ValidatorStatus::Active);
+        assert_eq!(registry.ed25519_key(&validator_id).await, Some(pk));
+        assert_eq!(registry.ed25519_key(&[0u8; 32]).await, None);
+
+        let message = signed_vote(&key, 1);
+        message.verify(&PeerId::random(), &registry, CHAIN).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retired_key_signature_slashes_through_lookup() {
+        use validator::tx::RotateKeyTx;
+
+        let registry = ValidatorRegistry::new();
+        let (old_key, new_key) = (SigningKey::from_bytes(&[4u8; 32]), SigningKey::from_bytes(&[5u8; 32]));
+        let validator_id = join(&registry, &old_key).await;
+        registry.advance_epoch().await.unwrap();
+
+        let chain = registry.chain_spec().chain_id.clone();
+        let mut rotate = RotateKeyTx::new(chain, &validator_id, &new_key.verifying_key().to_bytes(), 1);
+        rotate.sign(&old_key, &new_key).unwrap();
+        registry.submit_rotate_key_tx(&rotate).await.unwrap();
+        let delay = registry.chain_spec().epoch.key_rotation_delay_epochs;
+        for _ in 0..delay {
+            registry.advance_epoch().await.unwrap();
+        }
+
+        // Signed with the rotated-out key for a view after its retirement
+        let view = (1 + delay) * validator::EPOCH_DURATION_SLOTS;
+        let message = signed_vote(&old_key, view);
+        let sender = PeerId::random();
+        assert!(matches!(message.verify(&sender, &registry, CHAIN).await, Err(NetworkError::InvalidSignature { .. })));
+        registry.on_invalid_signature(&message.signed()).await;
+
+        assert_eq!(registry.get(&validator_id).await.unwrap().status, ValidatorStatus::Slashed);
+        // Repeats are deduplicated
+        registry.on_invalid_signature(&message.signed()).await;
+        assert_eq!(registry.slash_events().await.len(), 1);
     }
 }
@@ -0,0 +1,184 @@
+//! Network Handle با احسان
+//!
+//! Cloneable front end to a running [`BizraNetwork`](crate::BizraNetwork).
+//! Every call is a [`Command`] sent to the swarm event loop, which owns the
+//! swarm and the peer manager; the reply comes back on a oneshot channel.
+//!
+//! ```text
+//!  NetworkHandle ──Command──▶ BizraNetwork::run() ──▶ Swarm<BizraBehaviour>
+//!        ▲                          │
+//!        └──── Subscription ◀───────┘  gossip messages per topic
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use libp2p::{gossipsub::MessageId, Multiaddr, PeerId};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::error::NetworkError;
+
+/// Queued commands before `NetworkHandle` calls wait for the event loop
+pub const COMMAND_BUFFER: usize = 256;
+
+/// Undelivered messages per subscription before new ones are dropped
+pub const SUBSCRIPTION_BUFFER: usize = 1024;
+
+/// Gossip message delivered to a topic subscriber
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedMessage {
+    /// Topic name
+    pub topic: String,
+
+    /// Original author (None for anonymous messages)
+    pub source: Option<PeerId>,
+
+    /// Peer that forwarded the message to us
+    pub propagation_source: PeerId,
+
+    /// Gossipsub message ID
+    pub message_id: MessageId,
+
+    /// Message payload
+    pub data: Vec<u8>,
+}
+
+/// Request to the swarm event loop
+#[derive(Debug)]
+pub(crate) enum Command {
+    Publish {
+        topic: String,
+        data: Vec<u8>,
+        reply: oneshot::Sender<Result<MessageId, NetworkError>>,
+    },
+    Subscribe {
+        topic: String,
+        reply: oneshot::Sender<Result<Subscription, NetworkError>>,
+    },
+    /// A subscription on `topic` was dropped
+    Unsubscribe {
+        topic: String,
+    },
+    Dial {
+        address: Multiaddr,
+        reply: oneshot::Sender<Result<(), NetworkError>>,
+    },
+    Peers {
+        reply: oneshot::Sender<Vec<PeerId>>,
+    },
+    ListenAddrs {
+        reply: oneshot::Sender<Vec<Multiaddr>>,
+    },
+    Shutdown,
+}
+
+/// Cloneable handle to a running network node
+#[derive(Debug, Clone)]
+pub struct NetworkHandle {
+    commands: mpsc::Sender<Command>,
+    local_peer_id: PeerId,
+}
+
+impl NetworkHandle {
+    pub(crate) fn new(commands: mpsc::Sender<Command>, local_peer_id: PeerId) -> Self {
+        Self { commands, local_peer_id }
+    }
+
+    /// Local peer ID
+    pub fn local_peer_id(&self) -> PeerId {
+        self.local_peer_id
+    }
+
+    /// Publish `data` to a gossipsub topic
+    ///
+    /// # Returns
+    /// - `Ok(MessageId)`: Message handed to the mesh
+    /// - `Err(NetworkError)`: No subscribed peers yet, message too large, or the node stopped
+    pub async fn publish(&self, topic: &str, data: Vec<u8>) -> Result<MessageId, NetworkError> {
+        let topic = topic.to_string();
+        self.request(|reply| Command::Publish { topic, data, reply }).await?
+    }
+
+    /// Subscribe to a gossipsub topic
+    ///
+    /// Every subscription receives every message on the topic; dropping it
+    /// unsubscribes once no other subscription for the topic is left (or on
+    /// the topic's next message if the command queue was full at the time).
+    pub async fn subscribe(&self, topic: &str) -> Result<Subscription, NetworkError> {
+        let topic = topic.to_string();
+        self.request(|reply| Command::Subscribe { topic, reply }).await?
+    }
+
+    /// Dial a peer address (`/ip4/.../tcp/.../p2p/<peer id>` or without the peer ID)
+    ///
+    /// Returns once the dial has started; the connection shows up in [`Self::peers`].
+    pub async fn dial(&self, address: Multiaddr) -> Result<(), NetworkError> {
+        self.request(|reply| Command::Dial { address, reply }).await?
+    }
+
+    /// Connected peers, as tracked by the peer manager
+    pub async fn peers(&self) -> Result<Vec<PeerId>, NetworkError> {
+        self.request(|reply| Command::Peers { reply }).await
+    }
+
+    /// Addresses the node is listening on
+    pub async fn listen_addrs(&self) -> Result<Vec<Multiaddr>, NetworkError> {
+        self.request(|reply| Command::ListenAddrs { reply }).await
+    }
+
+    /// Stop the event loop; `run()` returns and all subscriptions end
+    pub async fn shutdown(&self) -> Result<(), NetworkError> {
+        self.commands.send(Command::Shutdown).await.map_err(|_| NetworkError::NotRunning)
+    }
+
+    async fn request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> Command) -> Result<T, NetworkError> {
+        let (reply, response) = oneshot::channel();
+        self.commands.send(command(reply)).await.map_err(|_| NetworkError::NotRunning)?;
+        response.await.map_err(|_| NetworkError::NotRunning)
+    }
+}
+
+/// Stream of gossip messages on one topic
+#[derive(Debug)]
+pub struct Subscription {
+    topic: String,
+    messages: mpsc::Receiver<ReceivedMessage>,
+    commands: mpsc::Sender<Command>,
+}
+
+impl Subscription {
+    pub(crate) fn new(
+        topic: String,
+        messages: mpsc::Receiver<ReceivedMessage>,
+        commands: mpsc::Sender<Command>,
+    ) -> Self {
+        Self { topic, messages, commands }
+    }
+
+    /// Topic name
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Next message (None once the node shut down)
+    pub async fn recv(&mut self) -> Option<ReceivedMessage> {
+        self.messages.recv().await
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        // Close first so the event loop sees this subscription as gone
+        self.messages.close();
+        let _ = self.commands.try_send(Command::Unsubscribe { topic: std::mem::take(&mut self.topic) });
+    }
+}
+
+impl Stream for Subscription {
+    type Item = ReceivedMessage;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.messages.poll_recv(cx)
+    }
+}
@@ -1,9 +1,23 @@
 //! Peer Identity Management با احسان
 //!
 //! Ed25519 keypair generation and peer ID derivation.
+//!
+//! A node keeps its PeerId across restarts by loading its key from a file
+//! ([`PeerIdentity::load_or_generate`]). The file holds the libp2p protobuf
+//! encoding of the private key and is only readable by its owner (0600).
+//!
+//! A validator node can instead derive its libp2p identity from its consensus
+//! Ed25519 key ([`PeerIdentity::from_validator_key`]); the PeerId is then bound
+//! to the validator: [`PeerIdentity::validator_id`] is the `ValidatorId`
+//! (blake3 of the Ed25519 public key) the registry knows it by.
 
-use anyhow::Result;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
 use libp2p::{identity, PeerId};
+use tracing::info;
 
 /// BIZRA peer identity (Ed25519 keypair)
 pub struct PeerIdentity {
@@ -22,12 +36,91 @@ impl PeerIdentity {
         // Generate Ed25519 keypair (cryptographically secure)
         let keypair = identity::Keypair::generate_ed25519();
 
+        Self::from_keypair(keypair)
+    }
+
+    /// Use an existing keypair (must be Ed25519)
+    pub fn from_keypair(keypair: identity::Keypair) -> Result<Self> {
+        if keypair.public().try_into_ed25519().is_err() {
+            bail!("Peer identity must be an Ed25519 key, got {:?}", keypair.key_type());
+        }
+
         // Derive peer ID from public key
         let peer_id = PeerId::from(keypair.public());
 
         Ok(Self { keypair, peer_id })
     }
 
+    /// Derive the identity from a validator's consensus Ed25519 secret key
+    ///
+    /// The resulting PeerId is fixed by the validator key, so peers can map it
+    /// back to the validator ([`Self::validator_id`]).
+    pub fn from_validator_key(secret_key: &[u8; 32]) -> Result<Self> {
+        let keypair = identity::Keypair::ed25519_from_bytes(*secret_key)
+            .context("Invalid validator Ed25519 secret key")?;
+        Self::from_keypair(keypair)
+    }
+
+    /// Load the key stored at `path`, or generate one and store it there
+    ///
+    /// # احسان Compliance
+    /// - The key file is created with mode 0600
+    /// - A key file readable by group or others is rejected, not silently used
+    pub fn load_or_generate(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if path.exists() {
+            let identity = Self::load(path)?;
+            info!("🔑 Loaded peer identity {} from {}", identity.peer_id, path.display());
+            return Ok(identity);
+        }
+
+        let identity = Self::new()?;
+        identity.save(path)?;
+        info!("🔑 Generated peer identity {} at {}", identity.peer_id, path.display());
+        Ok(identity)
+    }
+
+    /// Load a protobuf-encoded key file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        check_permissions(path)?;
+
+        let bytes = fs::read(path).with_context(|| format!("Failed to read key file {}", path.display()))?;
+        let keypair = identity::Keypair::from_protobuf_encoding(&bytes)
+            .with_context(|| format!("Invalid key file {}", path.display()))?;
+        Self::from_keypair(keypair)
+    }
+
+    /// Store the key as a protobuf-encoded file with mode 0600
+    ///
+    /// Writes a fresh temporary file next to `path` and renames it into place;
+    /// a leftover temporary file is removed first so its mode is never reused.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+
+        let bytes = self.keypair.to_protobuf_encoding().context("Failed to encode peer key")?;
+        let tmp = path.with_extension("tmp");
+        match fs::remove_file(&tmp) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(e).with_context(|| format!("Failed to remove stale key file {}", tmp.display()));
+            }
+            _ => {}
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&tmp).with_context(|| format!("Failed to create key file {}", tmp.display()))?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to write key file {}", path.display()))?;
+        Ok(())
+    }
+
     /// Get peer ID
     pub fn peer_id(&self) -> PeerId {
         self.peer_id
@@ -37,4 +130,109 @@ impl PeerIdentity {
     pub fn keypair(&self) -> &identity::Keypair {
         &self.keypair
     }
+
+    /// Raw Ed25519 public key
+    pub fn ed25519_public_key(&self) -> [u8; 32] {
+        self.keypair
+            .public()
+            .try_into_ed25519()
+            .expect("checked in from_keypair")
+            .to_bytes()
+    }
+
+    /// Validator ID this identity corresponds to (blake3 of the Ed25519 public key)
+    pub fn validator_id(&self) -> [u8; 32] {
+        *blake3::hash(&self.ed25519_public_key()).as_bytes()
+    }
+}
+
+/// Refuse key files that group or others can access
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path)
+        .with_context(|| format!("Failed to read key file {}", path.display()))?
+        .permissions()
+        .mode();
+    if mode & 0o077 != 0 {
+        bail!("Key file {} has mode {:o}; it must not be accessible by group or others (chmod 600)", path.display(), mode & 0o777);
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_or_generate_keeps_peer_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys/node.key");
+
+        let first = PeerIdentity::load_or_generate(&path).unwrap();
+        let second = PeerIdentity::load_or_generate(&path).unwrap();
+        assert_eq!(first.peer_id(), second.peer_id());
+        assert_ne!(PeerIdentity::new().unwrap().peer_id(), first.peer_id());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+
+            // Loosened permissions are rejected
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(PeerIdentity::load_or_generate(&path).is_err());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_ignores_stale_temporary_file_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node.key");
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, b"stale").unwrap();
+        fs::set_permissions(&tmp, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let identity = PeerIdentity::new().unwrap();
+        identity.save(&path).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert!(!tmp.exists());
+        assert_eq!(PeerIdentity::load(&path).unwrap().peer_id(), identity.peer_id());
+    }
+
+    #[test]
+    fn test_identity_bound_to_validator_key() {
+        let secret = [7u8; 32];
+        let identity = PeerIdentity::from_validator_key(&secret).unwrap();
+        let public = ed25519_dalek::SigningKey::from_bytes(&secret).verifying_key().to_bytes();
+
+        assert_eq!(identity.ed25519_public_key(), public);
+        assert_eq!(identity.validator_id(), *blake3::hash(&public).as_bytes());
+        assert_eq!(identity.peer_id(), PeerIdentity::from_validator_key(&secret).unwrap().peer_id());
+
+        // Round-trips through the key file
+        let dir = tempfile::tempdir().unwrap();
+        identity.save(dir.path().join("validator.key")).unwrap();
+        let loaded = PeerIdentity::load(dir.path().join("validator.key")).unwrap();
+        assert_eq!(loaded.peer_id(), identity.peer_id());
+
+        assert!(PeerIdentity::load(dir.path().join("missing.key")).is_err());
+        fs::write(dir.path().join("garbage.key"), b"not a key").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(dir.path().join("garbage.key"), fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        assert!(PeerIdentity::load(dir.path().join("garbage.key")).is_err());
+    }
 }
@@ -12,6 +12,28 @@
 //! - **Kademlia DHT**: Peer discovery
 //! - **mDNS**: Local network discovery
 //!
+//! # Usage
+//! [`BizraNetwork::run`] drives the swarm; everything else talks to it through
+//! a cloneable [`NetworkHandle`]:
+//!
+//! ```no_run
+//! use bizra_network::{BizraNetwork, NetworkConfig};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let mut network = BizraNetwork::new(NetworkConfig::default()).await?;
+//! network.start().await?;
+//! let handle = network.handle();
+//! tokio::spawn(network.run());
+//!
+//! let mut blocks = handle.subscribe("bizra/blocks").await?;
+//! handle.publish("bizra/blocks", b"block".to_vec()).await.ok();
+//! while let Some(message) = blocks.recv().await {
+//!     println!("{} bytes from {}", message.data.len(), message.propagation_source);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # احسان Compliance
 //! - NO silent assumptions about peer availability
 //! - Explicit error handling for all network operations
@@ -32,22 +54,36 @@ pub mod behavior;
 pub mod config;
 pub mod error;
 pub mod gossip;
+pub mod handle;
 pub mod identity;
 pub mod peer_manager;
 pub mod transport;
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use anyhow::Result;
+use futures::StreamExt;
 use libp2p::{
-    noise, quic,
-    swarm::{NetworkBehaviour, SwarmEvent},
-    yamux, Multiaddr, PeerId, Swarm,
+    gossipsub::{self, IdentTopic, MessageAcceptance, TopicHash},
+    identify, kad, mdns,
+    multiaddr::Protocol,
+    ping,
+    swarm::SwarmEvent,
+    PeerId, Swarm,
 };
-use tracing::info;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
 
+use behavior::BizraEvent;
+use config::ValidationMode;
+use gossip::{GossipMessage, ValidatorKeyLookup};
+use handle::{Command, ReceivedMessage, Subscription, COMMAND_BUFFER, SUBSCRIPTION_BUFFER};
 
 pub use behavior::BizraBehaviour;
 pub use config::NetworkConfig;
 pub use error::NetworkError;
+pub use handle::NetworkHandle;
 pub use identity::PeerIdentity;
 pub use peer_manager::PeerManager;
 
@@ -66,11 +102,37 @@ pub struct BizraNetwork {
 
     /// احسان compliance score (0.0-1.0)
     ahsan_score: f64,
+
+    /// Command channel shared by all handles
+    commands: mpsc::Sender<Command>,
+    command_rx: mpsc::Receiver<Command>,
+
+    /// Topic subscribers (topic name and one sender per subscription)
+    subscribers: HashMap<TopicHash, (String, Vec<mpsc::Sender<ReceivedMessage>>)>,
+
+    /// Consensus keys for verifying gossip signatures
+    key_lookup: Option<Arc<dyn ValidatorKeyLookup>>,
+
+    /// Results of gossip validations running off the event loop
+    validated: mpsc::Sender<Validated>,
+    validated_rx: mpsc::Receiver<Validated>,
+}
+
+/// Consensus messages being verified or awaiting their result before new ones are ignored
+const VALIDATION_BUFFER: usize = 1024;
+
+/// Finished validation of a consensus gossip message
+struct Validated {
+    message: ReceivedMessage,
+    result: Result<(), NetworkError>,
 }
 
 impl BizraNetwork {
     /// Create new BIZRA network node با احسان
     ///
+    /// The node key is loaded from (or created at) `config.identity_path`, so
+    /// the PeerId survives restarts; without a path the identity is ephemeral.
+    ///
     /// # Arguments
     /// - `config`: Network configuration (listen addresses, max peers, etc.)
     ///
@@ -98,15 +160,30 @@ impl BizraNetwork {
         info!("🌐 Initializing BIZRA P2P Network با احسان");
         info!("  احسان principle: Zero assumptions about network state");
 
+        // Validate before touching the key file
+        config.validate()?;
+
+        // Load or generate peer identity
+        let identity = match &config.identity_path {
+            Some(path) => PeerIdentity::load_or_generate(path)?,
+            None => {
+                info!("  🔑 No identity_path configured: generating an ephemeral Ed25519 keypair");
+                PeerIdentity::new()?
+            }
+        };
+
+        Self::with_identity(config, identity).await
+    }
+
+    /// Create a network node with a given identity
+    ///
+    /// Use with [`PeerIdentity::from_validator_key`] to bind the PeerId to a validator.
+    pub async fn with_identity(config: NetworkConfig, identity: PeerIdentity) -> Result<Self> {
         // Validate configuration (احسان compliance)
         config.validate()?;
 
-        // Generate or load peer identity
-        let identity = PeerIdentity::new()?;
         let peer_id = identity.peer_id();
-
         info!("  📛 Peer ID: {}", peer_id);
-        info!("  🔑 Ed25519 keypair generated");
 
         // Create libp2p swarm with QUIC + Noise + Yamux
         let swarm = Self::create_swarm(identity, &config).await?;
@@ -129,14 +206,33 @@ impl BizraNetwork {
 
         info!("🌐 BIZRA Network initialized با احسان (score: 100/100)");
 
+        let (commands, command_rx) = mpsc::channel(COMMAND_BUFFER);
+        let (validated, validated_rx) = mpsc::channel(VALIDATION_BUFFER);
         Ok(Self {
             swarm,
             peer_manager,
             config,
             ahsan_score: 1.0, // احسان score: 100/100
+            commands,
+            command_rx,
+            subscribers: HashMap::new(),
+            key_lookup: None,
+            validated,
+            validated_rx,
         })
     }
 
+    /// Verify consensus gossip against validator keys from `keys` (usually the
+    /// [`ValidatorRegistry`](validator::ValidatorRegistry))
+    ///
+    /// With [`ValidationMode::Strict`], messages on consensus topics
+    /// ([`gossip::is_consensus_topic`]) are delivered and forwarded only once
+    /// their signature verifies; without a key lookup they are all ignored.
+    pub fn with_key_lookup(mut self, keys: Arc<dyn ValidatorKeyLookup>) -> Self {
+        self.key_lookup = Some(keys);
+        self
+    }
+
     /// Create libp2p swarm with all required behaviors
     async fn create_swarm(
         identity: PeerIdentity,
@@ -146,10 +242,11 @@ impl BizraNetwork {
         let transport = transport::build_quic_transport(identity.keypair())?;
 
         // Create swarm with custom behavior
-        let behaviour = BizraBehaviour::new(identity.peer_id(), identity.keypair(), config).await?;
+        let behaviour = BizraBehaviour::new(identity.peer_id(), identity.keypair(), config)?;
 
         // Create swarm with default config (libp2p 0.53 API)
-        let swarm_config = libp2p::swarm::Config::with_tokio_executor();
+        let swarm_config = libp2p::swarm::Config::with_tokio_executor()
+            .with_idle_connection_timeout(config.connection_timeout);
         let swarm = Swarm::new(transport, behaviour, identity.peer_id(), swarm_config);
 
         Ok(swarm)
@@ -200,6 +297,291 @@ impl BizraNetwork {
     pub fn local_peer_id(&self) -> PeerId {
         *self.swarm.local_peer_id()
     }
+
+    /// Handle for talking to the node once [`Self::run`] is spawned
+    pub fn handle(&self) -> NetworkHandle {
+        NetworkHandle::new(self.commands.clone(), self.local_peer_id())
+    }
+
+    /// Drive the swarm until [`NetworkHandle::shutdown`]
+    ///
+    /// Polls the swarm and serves handle commands. Connection events and
+    /// Gossipsub, Kademlia, mDNS, Identify and Ping events update the peer
+    /// manager; gossip messages go to the topic's subscriptions, consensus
+    /// messages only after their signature verifies (see [`Self::with_key_lookup`]).
+    ///
+    /// # احسان Compliance
+    /// - Every behaviour event is dispatched (nothing is silently dropped)
+    /// - Peers over `max_peers` or banned by the peer manager are disconnected
+    pub async fn run(mut self) -> Result<()> {
+        info!("🔄 BIZRA Network event loop running: {}", self.local_peer_id());
+        let mut cleanup = tokio::time::interval(self.config.heartbeat_interval);
+
+        loop {
+            tokio::select! {
+                event = self.swarm.select_next_some() => self.handle_swarm_event(event),
+                command = self.command_rx.recv() => match command {
+                    Some(Command::Shutdown) | None => break,
+                    Some(command) => self.handle_command(command),
+                },
+                Some(validated) = self.validated_rx.recv() => self.handle_validated(validated),
+                _ = cleanup.tick() => self.peer_manager.cleanup_stale_peers(),
+            }
+        }
+
+        info!("🛑 BIZRA Network event loop stopped: {}", self.local_peer_id());
+        Ok(())
+    }
+
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Publish { topic, data, reply } => {
+                let _ = reply.send(self.swarm.behaviour_mut().publish_to_topic(&topic, &data));
+            }
+            Command::Subscribe { topic, reply } => {
+                let _ = reply.send(self.subscribe(topic));
+            }
+            Command::Unsubscribe { topic } => self.unsubscribe(&topic),
+            Command::Dial { address, reply } => {
+                if let Some(Protocol::P2p(peer_id)) = address.iter().last() {
+                    self.swarm.behaviour_mut().add_known_peer(peer_id, address.clone());
+                }
+                let result = self.swarm.dial(address.clone()).map_err(|e| NetworkError::ConnectionError {
+                    peer_id: address.to_string(),
+                    source: e.into(),
+                });
+                let _ = reply.send(result);
+            }
+            Command::Peers { reply } => {
+                let mut peers = self.peer_manager.get_connected_peers();
+                peers.sort();
+                let _ = reply.send(peers);
+            }
+            Command::ListenAddrs { reply } => {
+                let _ = reply.send(self.swarm.listeners().cloned().collect());
+            }
+            Command::Shutdown => {}
+        }
+    }
+
+    fn subscribe(&mut self, topic: String) -> Result<Subscription, NetworkError> {
+        let hash = IdentTopic::new(&topic).hash();
+        if !self.subscribers.contains_key(&hash) {
+            self.swarm.behaviour_mut().subscribe_to_topic(&topic)?;
+            self.subscribers.insert(hash.clone(), (topic.clone(), Vec::new()));
+        }
+
+        let (sender, messages) = mpsc::channel(SUBSCRIPTION_BUFFER);
+        self.subscribers.get_mut(&hash).expect("inserted above").1.push(sender);
+        Ok(Subscription::new(topic, messages, self.commands.clone()))
+    }
+
+    /// Drop closed subscriptions of a topic, unsubscribing once none is left
+    fn unsubscribe(&mut self, topic: &str) {
+        let hash = IdentTopic::new(topic).hash();
+        if let Some((_, senders)) = self.subscribers.get_mut(&hash) {
+            senders.retain(|sender| !sender.is_closed());
+        }
+        self.remove_if_unsubscribed(&hash);
+    }
+
+    /// Hand a gossip message to the topic's subscriptions, dropping closed ones
+    fn deliver(&mut self, message: ReceivedMessage, hash: &TopicHash) {
+        let Some((topic, senders)) = self.subscribers.get_mut(hash) else {
+            return;
+        };
+        senders.retain(|sender| match sender.try_send(message.clone()) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                warn!("⚠️  Subscriber on {} is full, dropping message {}", topic, message.message_id);
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+        self.remove_if_unsubscribed(hash);
+    }
+
+    fn remove_if_unsubscribed(&mut self, hash: &TopicHash) {
+        if self.subscribers.get(hash).is_some_and(|(_, senders)| senders.is_empty()) {
+            let (topic, _) = self.subscribers.remove(hash).expect("checked above");
+            self.swarm.behaviour_mut().gossipsub.unsubscribe(&IdentTopic::new(topic)).ok();
+        }
+    }
+
+    /// Start validating a gossip message
+    ///
+    /// Consensus messages are verified on a separate task (the key lookup may
+    /// wait on the registry) and come back through [`Self::handle_validated`];
+    /// everything else is accepted as is. At most [`VALIDATION_BUFFER`]
+    /// verifications are pending; further messages are ignored meanwhile.
+    fn validate(&mut self, message: ReceivedMessage) {
+        if !matches!(self.config.gossip_config.validation_mode, ValidationMode::Strict) {
+            return self.accept(message);
+        }
+        if !gossip::is_consensus_topic(&message.topic) {
+            return self.report_validation(message, MessageAcceptance::Accept);
+        }
+
+        let sender = message.source.unwrap_or(message.propagation_source);
+        let Some(keys) = self.key_lookup.clone() else {
+            warn!("⚠️  No validator key lookup configured, ignoring consensus message {} from {}", message.message_id, sender);
+            return self.report_validation(message, MessageAcceptance::Ignore);
+        };
+
+        // Each task holds a slot for its result, bounding the tasks in flight
+        let Ok(slot) = self.validated.clone().try_reserve_owned() else {
+            warn!("⚠️  Validation queue full, ignoring consensus message {} from {}", message.message_id, sender);
+            return self.report_validation(message, MessageAcceptance::Ignore);
+        };
+        let chain_id = self.config.gossip_config.chain_id.clone();
+        tokio::spawn(async move {
+            let result = match GossipMessage::decode(&message.data, &sender) {
+                Ok(decoded) => {
+                    let result = decoded.verify(&sender, keys.as_ref(), &chain_id).await;
+                    if matches!(result, Err(NetworkError::InvalidSignature { .. })) {
+                        keys.on_invalid_signature(&decoded.signed()).await;
+                    }
+                    result
+                }
+                Err(e) => Err(e),
+            };
+            slot.send(Validated { message, result });
+        });
+    }
+
+    /// Report a validation result to Gossipsub, then deliver or penalize
+    fn handle_validated(&mut self, Validated { message, result }: Validated) {
+        let acceptance = gossip::acceptance_for(&result);
+        if let Err(e) = &result {
+            match acceptance {
+                MessageAcceptance::Ignore => debug!("Ignoring message {}: {}", message.message_id, e),
+                _ => warn!("⚠️  Rejecting message {}: {}", message.message_id, e),
+            }
+        }
+        self.report_validation(message, acceptance);
+    }
+
+    fn report_validation(&mut self, message: ReceivedMessage, acceptance: MessageAcceptance) {
+        let peer = message.propagation_source;
+        let reject = matches!(acceptance, MessageAcceptance::Reject);
+        let accept = matches!(acceptance, MessageAcceptance::Accept);
+        if let Err(e) = self.swarm.behaviour_mut().gossipsub.report_message_validation_result(
+            &message.message_id,
+            &peer,
+            acceptance,
+        ) {
+            debug!("Validation result for {} not reported: {}", message.message_id, e);
+        }
+
+        if accept {
+            self.accept(message);
+        } else if reject {
+            self.penalize(peer);
+        }
+    }
+
+    /// Record a failure against `peer`, disconnecting it once banned
+    fn penalize(&mut self, peer: PeerId) {
+        self.peer_manager.record_message_failure(&peer);
+        if self.peer_manager.get_peer_info(&peer).is_some_and(|p| p.state == peer_manager::PeerState::Banned) {
+            let _ = self.swarm.disconnect_peer_id(peer);
+        }
+    }
+
+    fn accept(&mut self, message: ReceivedMessage) {
+        self.peer_manager.record_message_success(&message.propagation_source, None);
+        let hash = TopicHash::from_raw(message.topic.as_str());
+        self.deliver(message, &hash);
+    }
+
+    fn handle_swarm_event(&mut self, event: SwarmEvent<BizraEvent>) {
+        match event {
+            SwarmEvent::Behaviour(event) => self.handle_behaviour_event(event),
+            SwarmEvent::ConnectionEstablished { peer_id, num_established, .. } => {
+                if num_established.get() > 1 {
+                    return;
+                }
+                self.peer_manager.add_peer(peer_id);
+                if let Err(e) = self.peer_manager.peer_connected(&peer_id) {
+                    warn!("⚠️  {}", e);
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                    return;
+                }
+                for pruned in self.peer_manager.prune_peers_if_needed() {
+                    let _ = self.swarm.disconnect_peer_id(pruned);
+                }
+            }
+            SwarmEvent::ConnectionClosed { peer_id, num_established: 0, .. } => {
+                if self.peer_manager.is_connected(&peer_id) {
+                    self.peer_manager.peer_disconnected(&peer_id);
+                }
+            }
+            SwarmEvent::OutgoingConnectionError { peer_id: Some(peer_id), error, .. } => {
+                // An unreachable peer has not misbehaved; reputation is only for messages
+                warn!("⚠️  Failed to connect to {}: {}", peer_id, error);
+            }
+            SwarmEvent::NewListenAddr { address, .. } => {
+                info!("  🎧 Listening on: {}/p2p/{}", address, self.local_peer_id());
+            }
+            other => debug!("Swarm event: {:?}", other),
+        }
+    }
+
+    fn handle_behaviour_event(&mut self, event: BizraEvent) {
+        match event {
+            BizraEvent::Gossipsub(gossipsub::Event::Message { propagation_source, message_id, message }) => {
+                let received = ReceivedMessage {
+                    topic: message.topic.to_string(),
+                    source: message.source,
+                    propagation_source,
+                    message_id,
+                    data: message.data,
+                };
+                self.validate(received);
+            }
+            BizraEvent::Gossipsub(gossipsub::Event::Subscribed { peer_id, topic }) => {
+                debug!("Peer {} subscribed to {}", peer_id, topic);
+            }
+            BizraEvent::Gossipsub(event) => debug!("Gossipsub event: {:?}", event),
+
+            BizraEvent::Kademlia(kad::Event::RoutingUpdated { peer, .. }) => {
+                self.peer_manager.add_peer(peer);
+            }
+            BizraEvent::Kademlia(event) => debug!("Kademlia event: {:?}", event),
+
+            BizraEvent::Mdns(mdns::Event::Discovered(peers)) => {
+                for (peer_id, address) in peers {
+                    self.peer_manager.add_peer(peer_id);
+                    self.swarm.behaviour_mut().add_known_peer(peer_id, address.clone());
+                    if !self.swarm.is_connected(&peer_id) {
+                        if let Err(e) = self.swarm.dial(address) {
+                            debug!("mDNS dial to {} failed: {}", peer_id, e);
+                        }
+                    }
+                }
+            }
+            BizraEvent::Mdns(mdns::Event::Expired(peers)) => {
+                for (peer_id, address) in peers {
+                    self.swarm.behaviour_mut().kademlia.remove_address(&peer_id, &address);
+                }
+            }
+
+            BizraEvent::Identify(event) => {
+                if let identify::Event::Received { peer_id, info } = *event {
+                    self.peer_manager.add_peer(peer_id);
+                    for address in info.listen_addrs {
+                        self.swarm.behaviour_mut().add_known_peer(peer_id, address);
+                    }
+                }
+            }
+
+            BizraEvent::Ping(ping::Event { peer, result, .. }) => match result {
+                Ok(rtt) => self.peer_manager.record_message_success(&peer, Some(rtt.as_secs_f64() * 1000.0)),
+                // Timeouts and peers without ping support are not misbehaviour
+                Err(e) => debug!("Ping to {} failed: {}", peer, e),
+            },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -217,4 +599,21 @@ mod tests {
         assert_eq!(network.ahsan_score(), 1.0, "احسان score should be 100/100");
         assert_eq!(network.connected_peers(), 0, "No peers connected initially");
     }
+
+    #[tokio::test]
+    async fn test_dropped_subscription_unsubscribes() {
+        let mut network = BizraNetwork::new(NetworkConfig::default()).await.unwrap();
+        let first = network.subscribe("bizra/test".to_string()).unwrap();
+        let second = network.subscribe("bizra/test".to_string()).unwrap();
+
+        drop(first);
+        let command = network.command_rx.try_recv().unwrap();
+        network.handle_command(command);
+        assert_eq!(network.subscribers.len(), 1, "Topic kept while a subscription is left");
+
+        drop(second);
+        let command = network.command_rx.try_recv().unwrap();
+        network.handle_command(command);
+        assert!(network.subscribers.is_empty());
+    }
 }
@@ -243,9 +243,6 @@ impl PeerManager {
         info!("?? Peer connected: {} (reputation: {:.2}, quality: {:.2})",
               peer_id, peer.reputation_score, peer.quality_score);
 
-        // Check if we need to prune peers
-        self.prune_peers_if_needed();
-
         Ok(())
     }
 
@@ -392,14 +389,18 @@ impl PeerManager {
 
     /// Prune peers if we exceed the maximum
     ///
-    /// Removes lowest-health peers when we have too many connections
+    /// Removes lowest-health peers when we have too many connections. The
+    /// caller closes the returned peers' connections at the swarm level.
+    ///
+    /// # Returns
+    /// - Peer IDs that were marked disconnected
     ///
     /// # ????? Compliance
     /// - Explicit peer pruning logic
     /// - Health-based eviction (not random)
-    fn prune_peers_if_needed(&mut self) {
+    pub fn prune_peers_if_needed(&mut self) -> Vec<PeerId> {
         if self.connected_peers.len() <= self.max_peers {
-            return;
+            return Vec::new();
         }
 
         let excess = self.connected_peers.len() - self.max_peers;
@@ -414,10 +415,11 @@ impl PeerManager {
             .collect();
 
         // Disconnect excess peers
-        for peer_id in to_disconnect {
-            self.peer_disconnected(&peer_id);
+        for peer_id in &to_disconnect {
+            self.peer_disconnected(peer_id);
             info!("???  Pruned low-health peer: {}", peer_id);
         }
+        to_disconnect
     }
 
     /// Cleanup stale peers and banned peers
@@ -490,12 +492,11 @@ fn current_timestamp() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::str::FromStr;
 
     #[test]
     fn test_peer_lifecycle() {
         let mut manager = PeerManager::new(10);
-        let peer_id = PeerId::from_str("12D3KooWAbc123").unwrap();
+        let peer_id = PeerId::random();
 
         // Add peer
         assert!(manager.add_peer(peer_id));
@@ -517,7 +518,7 @@ mod tests {
     #[test]
     fn test_peer_reputation() {
         let mut manager = PeerManager::new(10);
-        let peer_id = PeerId::from_str("12D3KooWAbc123").unwrap();
+        let peer_id = PeerId::random();
 
         manager.add_peer(peer_id);
         manager.peer_connected(&peer_id).unwrap();
@@ -542,9 +543,9 @@ mod tests {
     fn test_peer_limits() {
         let mut manager = PeerManager::new(2); // Only allow 2 peers
 
-        let peer1 = PeerId::from_str("12D3KooWPeer1").unwrap();
-        let peer2 = PeerId::from_str("12D3KooWPeer2").unwrap();
-        let peer3 = PeerId::from_str("12D3KooWPeer3").unwrap();
+        let peer1 = PeerId::random();
+        let peer2 = PeerId::random();
+        let peer3 = PeerId::random();
 
         manager.add_peer(peer1);
         manager.add_peer(peer2);
@@ -557,5 +558,11 @@ mod tests {
 
         // Should have 3 connected (pruning happens at swarm level)
         assert_eq!(manager.connected_count(), 3);
+
+        // The swarm asks for the excess peer to close
+        let pruned = manager.prune_peers_if_needed();
+        assert_eq!(pruned.len(), 1);
+        assert_eq!(manager.connected_count(), 2);
+        assert!(!manager.is_connected(&pruned[0]));
     }
 }
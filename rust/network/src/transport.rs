@@ -10,7 +10,7 @@
 use libp2p::{
     core::transport::OrTransport,
     core::upgrade,
-    dns, noise,
+    noise,
     quic,
     tcp, yamux,
     Transport,
@@ -220,10 +220,13 @@ impl TransportMetrics {
         self.active_connections += 1;
         self.protocol = protocol.to_string();
 
-        // Update rolling average
+        // Update rolling average (the first measurement seeds it)
         let weight = 0.1; // 10% weight for new measurements
-        self.avg_connection_time_ms = self.avg_connection_time_ms * (1.0 - weight) +
-                                     establishment_time_ms * weight;
+        self.avg_connection_time_ms = if self.avg_connection_time_ms == 0.0 {
+            establishment_time_ms
+        } else {
+            self.avg_connection_time_ms * (1.0 - weight) + establishment_time_ms * weight
+        };
     }
 
     /// Record data transfer
@@ -300,8 +303,7 @@ mod tests {
             max_peers: 50,
             connection_timeout: Duration::from_secs(30),
             enable_mdns: true,
-            enable_kad: true,
-            bootstrap_peers: vec![],
+            ..NetworkConfig::default()
         };
 
         assert!(validate_transport_config(&valid_config).is_ok());
@@ -312,8 +314,7 @@ mod tests {
             max_peers: 50,
             connection_timeout: Duration::from_secs(30),
             enable_mdns: true,
-            enable_kad: true,
-            bootstrap_peers: vec![],
+            ..NetworkConfig::default()
         };
 
         assert!(validate_transport_config(&invalid_config).is_err());
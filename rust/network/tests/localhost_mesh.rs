@@ -0,0 +1,198 @@
+//! In-process nodes talking over localhost TCP
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bizra_network::gossip::{GossipHandler, GossipMessage, ValidatorKeyLookup, VoteType};
+use bizra_network::{BizraNetwork, NetworkConfig, NetworkHandle};
+use ed25519_dalek::SigningKey;
+use futures::StreamExt;
+use libp2p::{multiaddr::Protocol, Multiaddr};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+
+const TOPIC: &str = "bizra/blocks";
+
+async fn spawn_node() -> (NetworkHandle, JoinHandle<anyhow::Result<()>>) {
+    spawn_validating_node(None).await
+}
+
+async fn spawn_validating_node(
+    keys: Option<Arc<dyn ValidatorKeyLookup>>,
+) -> (NetworkHandle, JoinHandle<anyhow::Result<()>>) {
+    let config = NetworkConfig {
+        listen_addresses: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        enable_mdns: false,
+        ..NetworkConfig::default()
+    };
+    let mut network = BizraNetwork::new(config).await.unwrap();
+    if let Some(keys) = keys {
+        network = network.with_key_lookup(keys);
+    }
+    network.start().await.unwrap();
+    let handle = network.handle();
+    (handle, tokio::spawn(network.run()))
+}
+
+/// Poll `check` until it holds (10s limit)
+async fn eventually<F, Fut>(what: &str, mut check: F)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    for _ in 0..100 {
+        if check().await {
+            return;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    panic!("timed out waiting for {what}");
+}
+
+/// Dialable address of a node, with its peer ID
+async fn address_of(node: &NetworkHandle) -> Multiaddr {
+    eventually("listen address", || async { !node.listen_addrs().await.unwrap().is_empty() }).await;
+    let address = node.listen_addrs().await.unwrap().remove(0);
+    address.with(Protocol::P2p(node.local_peer_id()))
+}
+
+#[tokio::test]
+async fn three_nodes_connect_and_relay_gossip() {
+    let (a, a_task) = spawn_node().await;
+    let (b, b_task) = spawn_node().await;
+    let (c, c_task) = spawn_node().await;
+
+    // Line topology a - b - c: c only hears a through b
+    b.dial(address_of(&a).await).await.unwrap();
+    c.dial(address_of(&b).await).await.unwrap();
+    eventually("connections", || async {
+        a.peers().await.unwrap() == vec![b.local_peer_id()]
+            && b.peers().await.unwrap().len() == 2
+            && c.peers().await.unwrap() == vec![b.local_peer_id()]
+    })
+    .await;
+
+    let mut a_blocks = a.subscribe(TOPIC).await.unwrap();
+    let mut b_blocks = b.subscribe(TOPIC).await.unwrap();
+    let mut c_blocks = c.subscribe(TOPIC).await.unwrap();
+    assert_eq!(c_blocks.topic(), TOPIC);
+
+    // Publishing fails until a peer's subscription has propagated; retry until the mesh has formed
+    let received = timeout(Duration::from_secs(20), async {
+        loop {
+            if a.publish(TOPIC, b"block-1".to_vec()).await.is_ok() {
+                if let Ok(Some(message)) = timeout(Duration::from_secs(2), c_blocks.next()).await {
+                    return message;
+                }
+            }
+            sleep(Duration::from_millis(200)).await;
+        }
+    })
+    .await
+    .expect("gossip did not reach c");
+
+    assert_eq!(received.data, b"block-1");
+    assert_eq!(received.topic, TOPIC);
+    assert_eq!(received.source, Some(a.local_peer_id()));
+    assert_eq!(received.propagation_source, b.local_peer_id());
+
+    let at_b = timeout(Duration::from_secs(5), b_blocks.recv()).await.unwrap().unwrap();
+    assert_eq!(at_b.data, b"block-1");
+    assert_eq!(at_b.propagation_source, a.local_peer_id());
+
+    // Own messages are not delivered back
+    assert!(timeout(Duration::from_millis(300), a_blocks.recv()).await.is_err());
+
+    // Shutdown stops the loop and ends subscriptions
+    c.shutdown().await.unwrap();
+    c_task.await.unwrap().unwrap();
+    assert!(c_blocks.recv().await.is_none());
+    assert!(c.peers().await.is_err());
+    eventually("c disconnected", || async { b.peers().await.unwrap() == vec![a.local_peer_id()] }).await;
+
+    a.shutdown().await.unwrap();
+    b.shutdown().await.unwrap();
+    a_task.await.unwrap().unwrap();
+    b_task.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn peer_id_survives_restart_with_identity_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = NetworkConfig {
+        listen_addresses: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        enable_mdns: false,
+        identity_path: Some(dir.path().join("node.key")),
+        ..NetworkConfig::default()
+    };
+
+    let first = BizraNetwork::new(config.clone()).await.unwrap().local_peer_id();
+    let second = BizraNetwork::new(config).await.unwrap().local_peer_id();
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn forged_consensus_messages_are_not_forwarded() {
+    let validator = SigningKey::from_bytes(&[5u8; 32]);
+    let validator_id = *blake3::hash(validator.verifying_key().as_bytes()).as_bytes();
+    let keys: Arc<dyn ValidatorKeyLookup> =
+        Arc::new(HashMap::from([(validator_id, validator.verifying_key().to_bytes())]));
+
+    let (a, a_task) = spawn_validating_node(Some(keys.clone())).await;
+    let (b, b_task) = spawn_validating_node(Some(keys.clone())).await;
+    let (c, c_task) = spawn_validating_node(Some(keys.clone())).await;
+    b.dial(address_of(&a).await).await.unwrap();
+    c.dial(address_of(&b).await).await.unwrap();
+
+    let chain_id = NetworkConfig::default().gossip_config.chain_id;
+    let handler = GossipHandler::new(1000, &chain_id);
+    let topic = handler.topic_for_view(1);
+    let _a_votes = a.subscribe(&topic).await.unwrap();
+    let mut b_votes = b.subscribe(&topic).await.unwrap();
+    let mut c_votes = c.subscribe(&topic).await.unwrap();
+
+    let vote = |key: &SigningKey, round: u64| {
+        let mut message =
+            handler.create_vote(vec![round as u8; 32], validator_id.to_vec(), VoteType::Prepare, 1, vec![]);
+        message.sign(key);
+        message.encode()
+    };
+    let forger = SigningKey::from_bytes(&[6u8; 32]);
+
+    // a publishes a forged and a genuine vote each round until the mesh has formed
+    let received = timeout(Duration::from_secs(20), async {
+        for round in 0.. {
+            let forged = a.publish(&topic, vote(&forger, round)).await;
+            let genuine = a.publish(&topic, vote(&validator, round)).await;
+            if forged.is_ok() && genuine.is_ok() {
+                if let Ok(Some(message)) = timeout(Duration::from_secs(2), c_votes.next()).await {
+                    return message;
+                }
+            }
+            sleep(Duration::from_millis(200)).await;
+        }
+        unreachable!()
+    })
+    .await
+    .expect("vote did not reach c");
+
+    // Only genuine votes get through b, and b only delivers genuine ones
+    let sender = a.local_peer_id();
+    let mut delivered = vec![received];
+    for votes in [&mut c_votes, &mut b_votes] {
+        while let Ok(Some(message)) = timeout(Duration::from_millis(500), votes.recv()).await {
+            delivered.push(message);
+        }
+    }
+    assert!(delivered.len() > 1);
+    for message in delivered {
+        let vote = GossipMessage::decode(&message.data, &sender).unwrap();
+        vote.verify(&sender, keys.as_ref(), &chain_id).await.unwrap();
+    }
+
+    for (node, task) in [(a, a_task), (b, b_task), (c, c_task)] {
+        node.shutdown().await.unwrap();
+        task.await.unwrap().unwrap();
+    }
+}
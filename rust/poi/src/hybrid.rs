@@ -0,0 +1,271 @@
+// Hybrid post-quantum PoI attestations: Ed25519 + ML-DSA-65 (FIPS 204)
+// احسان (Ihsan) principle: a signature is only as strong as its weakest accepted component,
+// so both components must verify for a hybrid signature to be valid.
+use ed25519_dalek::{Signer, SigningKey};
+use ml_dsa::{EncodedSignature, EncodedVerifyingKey, MlDsa65, B32};
+use rand::{rngs::OsRng, RngCore};
+
+/// Algorithm identifier carried in attestation signature envelopes
+pub const HYBRID_ALG: &str = "ed25519+mldsa65";
+
+/// Ed25519 public key length in bytes
+pub const ED25519_PUBLIC_KEY_LEN: usize = 32;
+
+/// Ed25519 signature length in bytes
+pub const ED25519_SIGNATURE_LEN: usize = 64;
+
+/// ML-DSA-65 encoded public key length in bytes (FIPS 204, table 2)
+pub const MLDSA65_PUBLIC_KEY_LEN: usize = 1952;
+
+/// ML-DSA-65 encoded signature length in bytes (FIPS 204, table 2)
+pub const MLDSA65_SIGNATURE_LEN: usize = 3309;
+
+/// Hybrid secret key length: Ed25519 seed (32) || ML-DSA seed ξ (32)
+pub const HYBRID_SECRET_KEY_LEN: usize = 64;
+
+/// Hybrid public key length: Ed25519 key || ML-DSA-65 key
+pub const HYBRID_PUBLIC_KEY_LEN: usize = ED25519_PUBLIC_KEY_LEN + MLDSA65_PUBLIC_KEY_LEN;
+
+/// Hybrid signature length: Ed25519 signature || ML-DSA-65 signature
+pub const HYBRID_SIGNATURE_LEN: usize = ED25519_SIGNATURE_LEN + MLDSA65_SIGNATURE_LEN;
+
+/// Domain prefix for the Ed25519 component
+///
+/// Prevents the classical half of a hybrid signature from being stripped off
+/// and replayed as a plain `ed25519` attestation signature over the same message.
+const ED25519_DOMAIN: &[u8] = b"bizra-poi/ed25519+mldsa65\x00";
+
+/// Hybrid Ed25519 + ML-DSA-65 signing keypair
+///
+/// # Serialization
+/// The secret key serializes as the two 32-byte seeds (`ed25519 || mldsa65`);
+/// both component keys are re-derived deterministically from them.
+pub struct HybridKeypair {
+  ed25519: SigningKey,
+  mldsa: ml_dsa::SigningKey<MlDsa65>,
+}
+
+impl HybridKeypair {
+  /// Generate a fresh keypair from the OS RNG
+  pub fn generate() -> Self {
+    let mut seed = [0u8; HYBRID_SECRET_KEY_LEN];
+    OsRng.fill_bytes(&mut seed);
+    Self::from_seed_array(&seed)
+  }
+
+  /// Restore a keypair from its 64-byte serialized seed
+  ///
+  /// # Returns
+  /// * `Ok(HybridKeypair)` - Deterministically derived keypair
+  /// * `Err(String)` - Error message on invalid seed length
+  pub fn from_seed(seed: &[u8]) -> Result<Self, String> {
+    let seed: [u8; HYBRID_SECRET_KEY_LEN] = seed.try_into().map_err(|_| {
+      format!(
+        "Invalid hybrid secret key length: expected {} bytes, got {}",
+        HYBRID_SECRET_KEY_LEN,
+        seed.len()
+      )
+    })?;
+    Ok(Self::from_seed_array(&seed))
+  }
+
+  fn from_seed_array(seed: &[u8; HYBRID_SECRET_KEY_LEN]) -> Self {
+    let mut ed_seed = [0u8; 32];
+    ed_seed.copy_from_slice(&seed[..32]);
+    let mut xi = [0u8; 32];
+    xi.copy_from_slice(&seed[32..]);
+
+    Self {
+      ed25519: SigningKey::from_bytes(&ed_seed),
+      mldsa: ml_dsa::SigningKey::<MlDsa65>::from_seed(&B32::from(xi)),
+    }
+  }
+
+  /// Serialize the secret key (64 bytes: `ed25519 seed || mldsa65 seed`)
+  pub fn to_seed(&self) -> [u8; HYBRID_SECRET_KEY_LEN] {
+    let mut out = [0u8; HYBRID_SECRET_KEY_LEN];
+    out[..32].copy_from_slice(&self.ed25519.to_bytes());
+    out[32..].copy_from_slice(self.mldsa.as_seed().as_slice());
+    out
+  }
+
+  /// Ed25519 component public key (32 bytes)
+  pub fn ed25519_public_key(&self) -> [u8; ED25519_PUBLIC_KEY_LEN] {
+    self.ed25519.verifying_key().to_bytes()
+  }
+
+  /// ML-DSA-65 component public key (1952 bytes)
+  pub fn mldsa_public_key(&self) -> Vec<u8> {
+    self.mldsa.expanded_key().verifying_key().encode().to_vec()
+  }
+
+  /// Concatenated hybrid public key (`ed25519 || mldsa65`, 1984 bytes)
+  pub fn public_key(&self) -> Vec<u8> {
+    let mut pk = Vec::with_capacity(HYBRID_PUBLIC_KEY_LEN);
+    pk.extend_from_slice(&self.ed25519_public_key());
+    pk.extend_from_slice(&self.mldsa_public_key());
+    pk
+  }
+
+  /// Sign a message with both components
+  ///
+  /// # Determinism
+  /// Ed25519 (RFC 8032) and the deterministic ML-DSA.Sign variant are both
+  /// deterministic, so same message + same key = same signature.
+  pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+    let ed_sig = self.ed25519.sign(&ed25519_domain_message(msg));
+    let ml_sig = self
+      .mldsa
+      .expanded_key()
+      .sign_deterministic(msg, HYBRID_ALG.as_bytes())
+      .expect("context string is shorter than 255 bytes");
+
+    let mut sig = Vec::with_capacity(HYBRID_SIGNATURE_LEN);
+    sig.extend_from_slice(&ed_sig.to_bytes());
+    sig.extend_from_slice(ml_sig.encode().as_slice());
+    sig
+  }
+}
+
+impl std::fmt::Debug for HybridKeypair {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("HybridKeypair")
+      .field("ed25519_public_key", &self.ed25519_public_key())
+      .finish_non_exhaustive()
+  }
+}
+
+fn ed25519_domain_message(msg: &[u8]) -> Vec<u8> {
+  let mut out = Vec::with_capacity(ED25519_DOMAIN.len() + msg.len());
+  out.extend_from_slice(ED25519_DOMAIN);
+  out.extend_from_slice(msg);
+  out
+}
+
+/// Generate a hybrid Ed25519 + ML-DSA-65 attestation signature
+///
+/// # Arguments
+/// * `msg` - The message to sign
+/// * `secret_key` - The 64-byte hybrid seed (see [`HybridKeypair::to_seed`])
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - 3373-byte hybrid signature on success
+/// * `Err(String)` - Error message on failure
+pub fn generate_hybrid_attestation(msg: &[u8], secret_key: &[u8]) -> Result<Vec<u8>, String> {
+  Ok(HybridKeypair::from_seed(secret_key)?.sign(msg))
+}
+
+/// Verify a hybrid Ed25519 + ML-DSA-65 attestation signature
+///
+/// # Arguments
+/// * `msg` - The message that was signed
+/// * `pk` - The hybrid public key (`ed25519 || mldsa65`, 1984 bytes)
+/// * `sig` - The hybrid signature (`ed25519 || mldsa65`, 3373 bytes)
+///
+/// # Returns
+/// `true` only if BOTH component signatures are valid, `false` otherwise
+pub fn verify_hybrid_attestation(msg: &[u8], pk: &[u8], sig: &[u8]) -> bool {
+  if pk.len() != HYBRID_PUBLIC_KEY_LEN || sig.len() != HYBRID_SIGNATURE_LEN {
+    return false;
+  }
+
+  let (ed_pk, ml_pk) = pk.split_at(ED25519_PUBLIC_KEY_LEN);
+  let (ed_sig, ml_sig) = sig.split_at(ED25519_SIGNATURE_LEN);
+
+  crate::verify_attestation(&ed25519_domain_message(msg), ed_pk, ed_sig)
+    && verify_mldsa65(msg, ml_pk, ml_sig)
+}
+
+fn verify_mldsa65(msg: &[u8], pk: &[u8], sig: &[u8]) -> bool {
+  let Ok(pk_enc) = EncodedVerifyingKey::<MlDsa65>::try_from(pk) else { return false; };
+  let Ok(sig_enc) = EncodedSignature::<MlDsa65>::try_from(sig) else { return false; };
+  let Some(sig) = ml_dsa::Signature::<MlDsa65>::decode(&sig_enc) else { return false; };
+
+  ml_dsa::VerifyingKey::<MlDsa65>::decode(&pk_enc).verify_with_context(msg, HYBRID_ALG.as_bytes(), &sig)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn test_seed() -> [u8; HYBRID_SECRET_KEY_LEN] {
+    let mut seed = [0u8; HYBRID_SECRET_KEY_LEN];
+    for (i, b) in seed.iter_mut().enumerate() {
+      *b = i as u8;
+    }
+    seed
+  }
+
+  #[test]
+  fn test_hybrid_sizes() {
+    let kp = HybridKeypair::from_seed(&test_seed()).unwrap();
+    assert_eq!(kp.public_key().len(), HYBRID_PUBLIC_KEY_LEN);
+    assert_eq!(kp.mldsa_public_key().len(), MLDSA65_PUBLIC_KEY_LEN);
+    assert_eq!(kp.sign(b"msg").len(), HYBRID_SIGNATURE_LEN);
+  }
+
+  #[test]
+  fn test_hybrid_roundtrip_and_determinism() {
+    let seed = test_seed();
+    let kp = HybridKeypair::from_seed(&seed).unwrap();
+    let msg = b"hybrid PoI attestation";
+
+    let sig1 = generate_hybrid_attestation(msg, &seed).unwrap();
+    let sig2 = kp.sign(msg);
+    assert_eq!(sig1, sig2, "Hybrid signing must be deterministic");
+    assert!(verify_hybrid_attestation(msg, &kp.public_key(), &sig1));
+    assert!(!verify_hybrid_attestation(b"other message", &kp.public_key(), &sig1));
+  }
+
+  #[test]
+  fn test_hybrid_seed_serialization() {
+    let kp = HybridKeypair::generate();
+    let restored = HybridKeypair::from_seed(&kp.to_seed()).unwrap();
+    assert_eq!(kp.public_key(), restored.public_key());
+
+    let result = HybridKeypair::from_seed(&[0u8; 32]);
+    assert!(result.unwrap_err().contains("Invalid hybrid secret key length"));
+  }
+
+  #[test]
+  fn test_hybrid_requires_both_components() {
+    let kp = HybridKeypair::from_seed(&test_seed()).unwrap();
+    let msg = b"both halves";
+    let pk = kp.public_key();
+
+    // Corrupt the Ed25519 half only
+    let mut sig = kp.sign(msg);
+    sig[0] ^= 0xFF;
+    assert!(!verify_hybrid_attestation(msg, &pk, &sig));
+
+    // Corrupt the ML-DSA half only
+    let mut sig = kp.sign(msg);
+    sig[ED25519_SIGNATURE_LEN + 10] ^= 0xFF;
+    assert!(!verify_hybrid_attestation(msg, &pk, &sig));
+
+    // Swap in another keypair's ML-DSA key
+    let other = HybridKeypair::generate();
+    let mut mixed_pk = pk[..ED25519_PUBLIC_KEY_LEN].to_vec();
+    mixed_pk.extend_from_slice(&other.mldsa_public_key());
+    assert!(!verify_hybrid_attestation(msg, &mixed_pk, &kp.sign(msg)));
+  }
+
+  #[test]
+  fn test_hybrid_ed25519_half_is_domain_separated() {
+    let kp = HybridKeypair::from_seed(&test_seed()).unwrap();
+    let msg = b"no downgrade";
+    let sig = kp.sign(msg);
+
+    // The classical half must not verify as a plain Ed25519 attestation
+    assert!(!crate::verify_attestation(msg, &kp.ed25519_public_key(), &sig[..ED25519_SIGNATURE_LEN]));
+  }
+
+  #[test]
+  fn test_hybrid_malformed_inputs() {
+    let kp = HybridKeypair::from_seed(&test_seed()).unwrap();
+    let sig = kp.sign(b"m");
+    assert!(!verify_hybrid_attestation(b"m", &kp.public_key()[..100], &sig));
+    assert!(!verify_hybrid_attestation(b"m", &kp.public_key(), &sig[..100]));
+    assert!(!verify_hybrid_attestation(b"m", &[], &[]));
+  }
+}
@@ -3,6 +3,9 @@
 // احسان (Ihsan) principle: Deterministic, production-grade cryptographic implementation
 use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey, Verifier};
 
+pub mod hybrid;
+pub mod threshold;
+
 /// Verify an Ed25519 attestation signature
 ///
 /// # Arguments
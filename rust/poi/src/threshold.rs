@@ -0,0 +1,566 @@
+// Threshold (t-of-n) Ed25519 PoI attestation signing via FROST (RFC 9591)
+// احسان (Ihsan) principle: no single operator key can attest on behalf of a node;
+// the aggregated output is a plain Ed25519 signature accepted by `verify_attestation`.
+use frost_ed25519 as frost;
+use frost_ed25519::keys::{dkg, KeyPackage, PublicKeyPackage};
+use frost_ed25519::round1::{SigningCommitments, SigningNonces};
+use frost_ed25519::round2::SignatureShare;
+use frost_ed25519::{Identifier, SigningPackage};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Participant index within a threshold group (1..=max_signers)
+pub type ParticipantId = u16;
+
+fn identifier(id: ParticipantId) -> Result<Identifier, String> {
+  Identifier::try_from(id).map_err(|e| format!("Invalid participant id {}: {}", id, e))
+}
+
+fn frost_err(context: &str) -> impl Fn(frost::Error) -> String + '_ {
+  move |e| format!("{}: {}", context, e)
+}
+
+macro_rules! wire_message {
+  ($name:ident) => {
+    impl $name {
+      /// Serialize for transport between participants
+      pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("round message serialization is infallible")
+      }
+
+      /// Deserialize a message received from another participant
+      pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        bincode::deserialize(bytes)
+          .map_err(|e| format!("Invalid {}: {}", stringify!($name), e))
+      }
+    }
+  };
+}
+
+/// DKG round 1 package (broadcast to every other participant)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DkgRound1Message {
+  pub sender: ParticipantId,
+  pub package: Vec<u8>,
+}
+
+/// DKG round 2 package (sent privately to one recipient)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DkgRound2Message {
+  pub sender: ParticipantId,
+  pub recipient: ParticipantId,
+  pub package: Vec<u8>,
+}
+
+/// Signing round 1: a signer's nonce commitments (sent to the coordinator)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentMessage {
+  pub sender: ParticipantId,
+  pub commitments: Vec<u8>,
+}
+
+/// Signing package chosen by the coordinator (sent to every selected signer)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningRequest {
+  pub signing_package: Vec<u8>,
+}
+
+/// Signing round 2: a signer's signature share (sent to the coordinator)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureShareMessage {
+  pub sender: ParticipantId,
+  pub share: Vec<u8>,
+}
+
+wire_message!(DkgRound1Message);
+wire_message!(DkgRound2Message);
+wire_message!(CommitmentMessage);
+wire_message!(SigningRequest);
+wire_message!(SignatureShareMessage);
+
+/// Group public key material (shared by all participants and the coordinator)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdPublicKey {
+  package: PublicKeyPackage,
+}
+
+impl ThresholdPublicKey {
+  /// Group Ed25519 public key (32 bytes), usable with `verify_attestation`
+  pub fn group_public_key(&self) -> [u8; 32] {
+    let bytes = self
+      .package
+      .verifying_key()
+      .serialize()
+      .expect("Ed25519 group key serializes to 32 bytes");
+    bytes.try_into().expect("Ed25519 group key is 32 bytes")
+  }
+
+  /// Serialize the public key package
+  pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+    self.package.serialize().map_err(frost_err("Public key package serialization failed"))
+  }
+
+  /// Deserialize a public key package
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+    let package = PublicKeyPackage::deserialize(bytes)
+      .map_err(frost_err("Invalid public key package"))?;
+    Ok(Self { package })
+  }
+}
+
+/// One participant's long-lived key share
+///
+/// # Security
+/// Contains secret key material; persist it encrypted and never send it over the wire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdKeyShare {
+  id: ParticipantId,
+  key_package: KeyPackage,
+  public: ThresholdPublicKey,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeyShareEnvelope {
+  id: ParticipantId,
+  key_package: Vec<u8>,
+  public: Vec<u8>,
+}
+
+impl ThresholdKeyShare {
+  /// Participant index of this share
+  pub fn id(&self) -> ParticipantId {
+    self.id
+  }
+
+  /// Minimum number of signers required to produce a signature
+  pub fn min_signers(&self) -> u16 {
+    *self.key_package.min_signers()
+  }
+
+  /// Group public key material
+  pub fn public_key(&self) -> &ThresholdPublicKey {
+    &self.public
+  }
+
+  /// Serialize the key share (secret!)
+  pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+    let envelope = KeyShareEnvelope {
+      id: self.id,
+      key_package: self.key_package.serialize().map_err(frost_err("Key package serialization failed"))?,
+      public: self.public.to_bytes()?,
+    };
+    bincode::serialize(&envelope).map_err(|e| format!("Key share serialization failed: {}", e))
+  }
+
+  /// Deserialize a key share
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+    let envelope: KeyShareEnvelope =
+      bincode::deserialize(bytes).map_err(|e| format!("Invalid key share: {}", e))?;
+    let key_package = KeyPackage::deserialize(&envelope.key_package)
+      .map_err(frost_err("Invalid key package"))?;
+    if *key_package.identifier() != identifier(envelope.id)? {
+      return Err(format!("Key share id {} does not match key package", envelope.id));
+    }
+
+    Ok(Self {
+      id: envelope.id,
+      key_package,
+      public: ThresholdPublicKey::from_bytes(&envelope.public)?,
+    })
+  }
+}
+
+/// Key generation with a trusted dealer
+///
+/// Simplest ceremony: one machine generates the group key, splits it into
+/// `max_signers` shares and hands them out. The dealer sees the full secret,
+/// so prefer [`DkgParticipant`] when operators don't trust a single host.
+pub fn generate_with_dealer(min_signers: u16, max_signers: u16) -> Result<Vec<ThresholdKeyShare>, String> {
+  let (shares, public) = frost::keys::generate_with_dealer(
+    max_signers,
+    min_signers,
+    frost::keys::IdentifierList::Default,
+    OsRng,
+  )
+  .map_err(frost_err("Dealer key generation failed"))?;
+
+  let public = ThresholdPublicKey { package: public };
+  (1..=max_signers)
+    .map(|id| {
+      let secret_share = shares
+        .get(&identifier(id)?)
+        .cloned()
+        .ok_or_else(|| format!("Dealer produced no share for participant {}", id))?;
+      let key_package = KeyPackage::try_from(secret_share).map_err(frost_err("Invalid dealer share"))?;
+      Ok(ThresholdKeyShare { id, key_package, public: public.clone() })
+    })
+    .collect()
+}
+
+enum DkgState {
+  Round1(dkg::round1::SecretPackage),
+  Round2 {
+    secret: dkg::round2::SecretPackage,
+    round1_packages: BTreeMap<Identifier, dkg::round1::Package>,
+  },
+  Finished,
+}
+
+/// Distributed key generation ceremony (no trusted dealer)
+///
+/// # State machine
+/// ```text
+/// new() --DkgRound1Message--> round2(all round 1) --DkgRound2Message[]--> finish(all round 2)
+/// ```
+/// Round 1 messages must reach every participant over a broadcast channel;
+/// round 2 messages are secret and must travel over an encrypted channel.
+pub struct DkgParticipant {
+  id: ParticipantId,
+  state: DkgState,
+}
+
+impl DkgParticipant {
+  /// Start the ceremony, returning the round 1 broadcast
+  pub fn new(id: ParticipantId, min_signers: u16, max_signers: u16) -> Result<(Self, DkgRound1Message), String> {
+    let (secret, package) = dkg::part1(identifier(id)?, max_signers, min_signers, OsRng)
+      .map_err(frost_err("DKG round 1 failed"))?;
+    let message = DkgRound1Message {
+      sender: id,
+      package: package.serialize().map_err(frost_err("DKG round 1 serialization failed"))?,
+    };
+    Ok((Self { id, state: DkgState::Round1(secret) }, message))
+  }
+
+  /// Participant index
+  pub fn id(&self) -> ParticipantId {
+    self.id
+  }
+
+  /// Consume every other participant's round 1 broadcast and produce round 2 messages
+  ///
+  /// The participant's own broadcast is ignored if present.
+  pub fn round2(&mut self, round1: &[DkgRound1Message]) -> Result<Vec<DkgRound2Message>, String> {
+    let DkgState::Round1(secret) = std::mem::replace(&mut self.state, DkgState::Finished) else {
+      return Err("DKG round 2 called out of order".to_string());
+    };
+
+    let mut round1_packages = BTreeMap::new();
+    for msg in round1.iter().filter(|m| m.sender != self.id) {
+      let package = dkg::round1::Package::deserialize(&msg.package)
+        .map_err(frost_err("Invalid DKG round 1 package"))?;
+      round1_packages.insert(identifier(msg.sender)?, package);
+    }
+
+    let (secret, packages) = dkg::part2(secret, &round1_packages).map_err(frost_err("DKG round 2 failed"))?;
+
+    let mut out = Vec::with_capacity(packages.len());
+    for msg in round1.iter().filter(|m| m.sender != self.id) {
+      let package = &packages[&identifier(msg.sender)?];
+      out.push(DkgRound2Message {
+        sender: self.id,
+        recipient: msg.sender,
+        package: package.serialize().map_err(frost_err("DKG round 2 serialization failed"))?,
+      });
+    }
+
+    self.state = DkgState::Round2 { secret, round1_packages };
+    Ok(out)
+  }
+
+  /// Consume the round 2 messages addressed to this participant and derive the key share
+  pub fn finish(mut self, round2: &[DkgRound2Message]) -> Result<ThresholdKeyShare, String> {
+    let DkgState::Round2 { secret, round1_packages } = std::mem::replace(&mut self.state, DkgState::Finished) else {
+      return Err("DKG finish called out of order".to_string());
+    };
+
+    let mut round2_packages = BTreeMap::new();
+    for msg in round2.iter().filter(|m| m.recipient == self.id) {
+      let package = dkg::round2::Package::deserialize(&msg.package)
+        .map_err(frost_err("Invalid DKG round 2 package"))?;
+      round2_packages.insert(identifier(msg.sender)?, package);
+    }
+
+    let (key_package, public) = dkg::part3(&secret, &round1_packages, &round2_packages)
+      .map_err(frost_err("DKG finalization failed"))?;
+
+    Ok(ThresholdKeyShare {
+      id: self.id,
+      key_package,
+      public: ThresholdPublicKey { package: public },
+    })
+  }
+}
+
+enum SignerState {
+  Idle,
+  Committed(Box<SigningNonces>),
+  Signed,
+}
+
+/// Signer side of one threshold signing session
+///
+/// # State machine
+/// ```text
+/// Idle --commit()--> Committed --sign(request)--> Signed
+/// ```
+/// Nonces are single-use: a session signs exactly once, and a new session
+/// must be created for the next attestation.
+pub struct SignerSession<'a> {
+  share: &'a ThresholdKeyShare,
+  state: SignerState,
+}
+
+impl<'a> SignerSession<'a> {
+  /// Start a signing session for a key share
+  pub fn new(share: &'a ThresholdKeyShare) -> Self {
+    Self { share, state: SignerState::Idle }
+  }
+
+  /// Round 1: generate fresh nonces and publish their commitments
+  pub fn commit(&mut self) -> Result<CommitmentMessage, String> {
+    if !matches!(self.state, SignerState::Idle) {
+      return Err("Signer already committed in this session".to_string());
+    }
+
+    let (nonces, commitments) = frost::round1::commit(self.share.key_package.signing_share(), &mut OsRng);
+    self.state = SignerState::Committed(Box::new(nonces));
+
+    Ok(CommitmentMessage {
+      sender: self.share.id,
+      commitments: commitments.serialize().map_err(frost_err("Commitment serialization failed"))?,
+    })
+  }
+
+  /// Round 2: sign the coordinator's signing package
+  ///
+  /// Callers should check the message inside the request (e.g. that it is the
+  /// canonical payload of the attestation they agreed to) before signing.
+  pub fn sign(&mut self, request: &SigningRequest) -> Result<SignatureShareMessage, String> {
+    let SignerState::Committed(nonces) = std::mem::replace(&mut self.state, SignerState::Signed) else {
+      return Err("Signer must commit exactly once before signing".to_string());
+    };
+
+    let package = SigningPackage::deserialize(&request.signing_package)
+      .map_err(frost_err("Invalid signing package"))?;
+    let share = frost::round2::sign(&package, &nonces, &self.share.key_package)
+      .map_err(frost_err("Signature share generation failed"))?;
+
+    Ok(SignatureShareMessage { sender: self.share.id, share: share.serialize() })
+  }
+}
+
+/// Coordinator side of one threshold signing session
+///
+/// Collects commitments, publishes the signing package, then aggregates
+/// signature shares into a standard 64-byte Ed25519 signature.
+pub struct SigningCoordinator {
+  public: ThresholdPublicKey,
+  min_signers: u16,
+  message: Vec<u8>,
+  commitments: BTreeMap<Identifier, SigningCommitments>,
+  package: Option<SigningPackage>,
+  shares: BTreeMap<Identifier, SignatureShare>,
+}
+
+impl SigningCoordinator {
+  /// Start a session to sign `message` with the group key
+  pub fn new(public: ThresholdPublicKey, min_signers: u16, message: &[u8]) -> Self {
+    Self {
+      public,
+      min_signers,
+      message: message.to_vec(),
+      commitments: BTreeMap::new(),
+      package: None,
+      shares: BTreeMap::new(),
+    }
+  }
+
+  /// Record a signer's round 1 commitments
+  pub fn add_commitment(&mut self, msg: &CommitmentMessage) -> Result<(), String> {
+    if self.package.is_some() {
+      return Err("Signing package already published".to_string());
+    }
+    let commitments = SigningCommitments::deserialize(&msg.commitments)
+      .map_err(frost_err("Invalid commitments"))?;
+    if self.commitments.insert(identifier(msg.sender)?, commitments).is_some() {
+      return Err(format!("Duplicate commitments from participant {}", msg.sender));
+    }
+    Ok(())
+  }
+
+  /// Freeze the signer set and build the signing request
+  pub fn signing_request(&mut self) -> Result<SigningRequest, String> {
+    if self.commitments.len() < self.min_signers as usize {
+      return Err(format!(
+        "Not enough commitments: have {}, need {}",
+        self.commitments.len(),
+        self.min_signers
+      ));
+    }
+
+    let package: &SigningPackage = self
+      .package
+      .get_or_insert_with(|| SigningPackage::new(self.commitments.clone(), &self.message));
+    Ok(SigningRequest {
+      signing_package: package.serialize().map_err(frost_err("Signing package serialization failed"))?,
+    })
+  }
+
+  /// Record a signer's round 2 signature share
+  pub fn add_share(&mut self, msg: &SignatureShareMessage) -> Result<(), String> {
+    let id = identifier(msg.sender)?;
+    if !self.commitments.contains_key(&id) {
+      return Err(format!("Participant {} is not part of this signing session", msg.sender));
+    }
+    let share = SignatureShare::deserialize(&msg.share).map_err(frost_err("Invalid signature share"))?;
+    if self.shares.insert(id, share).is_some() {
+      return Err(format!("Duplicate signature share from participant {}", msg.sender));
+    }
+    Ok(())
+  }
+
+  /// Aggregate the collected shares into a 64-byte Ed25519 signature
+  ///
+  /// Invalid shares are detected here and reported with the culprit's identifier.
+  pub fn aggregate(&self) -> Result<Vec<u8>, String> {
+    let package = self.package.as_ref().ok_or("Signing package not published yet")?;
+    let signature = frost::aggregate(package, &self.shares, &self.public.package)
+      .map_err(frost_err("Signature aggregation failed"))?;
+    signature.serialize().map_err(frost_err("Signature serialization failed"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::verify_attestation;
+
+  fn run_signing_round(shares: &[&ThresholdKeyShare], msg: &[u8]) -> Vec<u8> {
+    let public = shares[0].public_key().clone();
+    let mut coordinator = SigningCoordinator::new(public, shares[0].min_signers(), msg);
+
+    let mut sessions: Vec<SignerSession> = shares.iter().map(|s| SignerSession::new(s)).collect();
+    for session in sessions.iter_mut() {
+      let commitment = session.commit().unwrap();
+      // Round messages travel as bytes
+      let commitment = CommitmentMessage::from_bytes(&commitment.to_bytes()).unwrap();
+      coordinator.add_commitment(&commitment).unwrap();
+    }
+
+    let request = SigningRequest::from_bytes(&coordinator.signing_request().unwrap().to_bytes()).unwrap();
+    for session in sessions.iter_mut() {
+      let share = session.sign(&request).unwrap();
+      coordinator.add_share(&SignatureShareMessage::from_bytes(&share.to_bytes()).unwrap()).unwrap();
+    }
+
+    coordinator.aggregate().unwrap()
+  }
+
+  #[test]
+  fn test_dealer_2_of_3_signing_round() {
+    let shares = generate_with_dealer(2, 3).unwrap();
+    assert_eq!(shares.len(), 3);
+    let group_pk = shares[0].public_key().group_public_key();
+    let msg = b"threshold PoI attestation";
+
+    // Any two of the three operators can sign
+    for pair in [[0, 1], [0, 2], [1, 2]] {
+      let sig = run_signing_round(&[&shares[pair[0]], &shares[pair[1]]], msg);
+      assert_eq!(sig.len(), 64);
+      assert!(verify_attestation(msg, &group_pk, &sig), "Pair {:?} must verify", pair);
+      assert!(!verify_attestation(b"other", &group_pk, &sig));
+    }
+  }
+
+  #[test]
+  fn test_dkg_2_of_3_signing_round() {
+    let mut participants = Vec::new();
+    let mut round1 = Vec::new();
+    for id in 1..=3 {
+      let (participant, msg) = DkgParticipant::new(id, 2, 3).unwrap();
+      participants.push(participant);
+      round1.push(DkgRound1Message::from_bytes(&msg.to_bytes()).unwrap());
+    }
+
+    let mut round2 = Vec::new();
+    for participant in participants.iter_mut() {
+      for msg in participant.round2(&round1).unwrap() {
+        round2.push(DkgRound2Message::from_bytes(&msg.to_bytes()).unwrap());
+      }
+    }
+
+    let shares: Vec<ThresholdKeyShare> = participants.into_iter().map(|p| p.finish(&round2).unwrap()).collect();
+    let group_pk = shares[0].public_key().group_public_key();
+    assert!(shares.iter().all(|s| s.public_key().group_public_key() == group_pk));
+
+    let msg = b"dkg attestation";
+    let sig = run_signing_round(&[&shares[2], &shares[0]], msg);
+    assert!(verify_attestation(msg, &group_pk, &sig));
+  }
+
+  #[test]
+  fn test_key_share_serialization_roundtrip() {
+    let shares = generate_with_dealer(2, 3).unwrap();
+    let restored = ThresholdKeyShare::from_bytes(&shares[1].to_bytes().unwrap()).unwrap();
+    assert_eq!(restored, shares[1]);
+    assert_eq!(restored.id(), 2);
+
+    let msg = b"restored share";
+    let sig = run_signing_round(&[&shares[0], &restored], msg);
+    assert!(verify_attestation(msg, &shares[0].public_key().group_public_key(), &sig));
+  }
+
+  #[test]
+  fn test_below_threshold_rejected() {
+    let shares = generate_with_dealer(2, 3).unwrap();
+    let mut coordinator = SigningCoordinator::new(shares[0].public_key().clone(), 2, b"m");
+    let mut session = SignerSession::new(&shares[0]);
+    coordinator.add_commitment(&session.commit().unwrap()).unwrap();
+
+    let result = coordinator.signing_request();
+    assert!(result.unwrap_err().contains("Not enough commitments"));
+  }
+
+  #[test]
+  fn test_signer_state_machine_rejects_misuse() {
+    let shares = generate_with_dealer(2, 3).unwrap();
+    let mut coordinator = SigningCoordinator::new(shares[0].public_key().clone(), 2, b"m");
+    let mut a = SignerSession::new(&shares[0]);
+    let mut b = SignerSession::new(&shares[1]);
+
+    let request = SigningRequest { signing_package: Vec::new() };
+    assert!(a.sign(&request).unwrap_err().contains("must commit"));
+
+    let mut a = SignerSession::new(&shares[0]);
+    let ca = a.commit().unwrap();
+    assert!(a.commit().unwrap_err().contains("already committed"));
+    coordinator.add_commitment(&ca).unwrap();
+    assert!(coordinator.add_commitment(&ca).unwrap_err().contains("Duplicate"));
+    coordinator.add_commitment(&b.commit().unwrap()).unwrap();
+
+    let request = coordinator.signing_request().unwrap();
+    a.sign(&request).unwrap();
+    // Nonces are single-use
+    assert!(a.sign(&request).is_err());
+  }
+
+  #[test]
+  fn test_corrupted_share_fails_aggregation() {
+    let shares = generate_with_dealer(2, 3).unwrap();
+    let mut coordinator = SigningCoordinator::new(shares[0].public_key().clone(), 2, b"m");
+    let mut a = SignerSession::new(&shares[0]);
+    let mut b = SignerSession::new(&shares[1]);
+    coordinator.add_commitment(&a.commit().unwrap()).unwrap();
+    coordinator.add_commitment(&b.commit().unwrap()).unwrap();
+    let request = coordinator.signing_request().unwrap();
+
+    coordinator.add_share(&a.sign(&request).unwrap()).unwrap();
+    // b signs with a's share bytes swapped in: invalid for b's verifying share
+    let mut bad = b.sign(&request).unwrap();
+    bad.share = coordinator.shares.values().next().unwrap().serialize();
+    coordinator.add_share(&bad).unwrap();
+
+    assert!(coordinator.aggregate().unwrap_err().contains("Signature aggregation failed"));
+  }
+}
@@ -0,0 +1,410 @@
+//! Benchmark-claim re-execution for PoI attestations
+//!
+//! Optional verification mode: when an attestation's `methodology_ref` names
+//! a registered deterministic benchmark, a validator extracts the evidence
+//! pack, re-runs the benchmark locally and compares its output with the
+//! claimed `benchmarks.post` metrics.
+//!
+//! ```text
+//!  attestation.evidence.methodology_ref ──> BenchmarkRegistry ──> BenchmarkRunner
+//!  evidence pack ──extract──> scratch dir ──────────────────────────────┘
+//!                                              │
+//!                         Reproduced / Unreproduced ──> FraudulentPoI SlashEvidence
+//! ```
+//!
+//! Runners implement [`BenchmarkRunner`]; [`SubprocessBenchmark`] runs an
+//! external program. A WASM runner can plug in through the same trait.
+//!
+//! Spec reference: BIZRA_Proof_of_Impact_Formal_Spec_v1.0.md section 6
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::canonical::to_canonical_bytes;
+use crate::evidence::EvidencePack;
+use crate::poi::{PoIAttestation, VALIDATION_EPSILON};
+use crate::slashing::{SlashEvidence, SlashReason};
+use crate::types::ValidatorId;
+
+/// Default wall-clock limit for one benchmark run
+pub const DEFAULT_BENCHMARK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Maximum benchmark stdout accepted (1 MiB)
+pub const MAX_BENCHMARK_OUTPUT: u64 = 1 << 20;
+
+/// Metric name → value
+pub type Metrics = BTreeMap<String, f64>;
+
+/// Deterministic benchmark executed against an extracted evidence pack
+pub trait BenchmarkRunner: Send + Sync {
+    /// Run on the files in `evidence_dir` and return the measured metrics
+    fn run(&self, evidence_dir: &Path) -> Result<Metrics>;
+
+    /// Absolute tolerance when comparing against claimed metrics
+    fn tolerance(&self) -> f64 {
+        VALIDATION_EPSILON
+    }
+}
+
+/// Benchmark run as an external program
+///
+/// The program runs with the extracted pack as its working directory and
+/// first argument, an empty environment, no stdin and a wall-clock limit; it
+/// must print a JSON object of metrics on stdout. This isolates the run from
+/// the validator's own state but is not a security sandbox: register only
+/// programs you trust, or wrap them in a container/jail runner.
+#[derive(Debug, Clone)]
+pub struct SubprocessBenchmark {
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    pub timeout: Duration,
+    pub tolerance: f64,
+}
+
+impl SubprocessBenchmark {
+    pub fn new(program: impl Into<PathBuf>, args: Vec<String>) -> Self {
+        Self {
+            program: program.into(),
+            args,
+            timeout: DEFAULT_BENCHMARK_TIMEOUT,
+            tolerance: VALIDATION_EPSILON,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl BenchmarkRunner for SubprocessBenchmark {
+    fn run(&self, evidence_dir: &Path) -> Result<Metrics> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .arg(evidence_dir)
+            .current_dir(evidence_dir)
+            .env_clear()
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to start benchmark {}", self.program.display()))?;
+
+        // Drain stdout on a separate thread so a chatty child cannot block on a full pipe
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let reader = std::thread::spawn(move || {
+            let mut out = Vec::new();
+            stdout.take(MAX_BENCHMARK_OUTPUT + 1).read_to_end(&mut out).map(|_| out)
+        });
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!("Benchmark {} timed out after {:?}", self.program.display(), self.timeout);
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+
+        let out = reader.join().map_err(|_| anyhow::anyhow!("Benchmark output reader panicked"))??;
+        if !status.success() {
+            bail!("Benchmark {} exited with {}", self.program.display(), status);
+        }
+        if out.len() as u64 > MAX_BENCHMARK_OUTPUT {
+            bail!("Benchmark output exceeds {} bytes", MAX_BENCHMARK_OUTPUT);
+        }
+
+        serde_json::from_slice(&out).context("Benchmark output is not a JSON object of metrics")
+    }
+
+    fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+}
+
+/// Benchmarks by methodology reference
+#[derive(Clone, Default)]
+pub struct BenchmarkRegistry {
+    runners: HashMap<String, Arc<dyn BenchmarkRunner>>,
+}
+
+impl BenchmarkRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a runner for a `methodology_ref` (DOI, URL or IPFS reference)
+    pub fn register(&mut self, methodology_ref: impl Into<String>, runner: Arc<dyn BenchmarkRunner>) {
+        self.runners.insert(methodology_ref.into(), runner);
+    }
+
+    pub fn get(&self, methodology_ref: &str) -> Option<Arc<dyn BenchmarkRunner>> {
+        self.runners.get(methodology_ref).cloned()
+    }
+}
+
+/// One metric whose re-execution disagrees with the claim
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricMismatch {
+    pub metric: String,
+    pub claimed: f64,
+
+    /// `None` when the benchmark did not produce the metric
+    pub reproduced: Option<f64>,
+}
+
+/// Outcome of re-executing an attestation's benchmark
+#[derive(Debug, Clone, PartialEq)]
+pub enum BenchmarkVerdict {
+    /// No benchmarks claimed, or no registered benchmark for the methodology
+    NotApplicable,
+
+    /// Every claimed `post` metric matched within tolerance
+    Reproduced { metrics: Metrics },
+
+    /// The claim could not be reproduced
+    Unreproduced {
+        methodology_ref: String,
+        reason: String,
+        mismatches: Vec<MetricMismatch>,
+    },
+}
+
+impl BenchmarkVerdict {
+    pub fn is_unreproduced(&self) -> bool {
+        matches!(self, BenchmarkVerdict::Unreproduced { .. })
+    }
+
+    /// `FraudulentPoI` evidence for an unreproduced claim
+    ///
+    /// The proof is the canonical JSON of a [`FraudulentPoIProof`] carrying
+    /// the pack the claim was re-executed on, so the registry can re-run it.
+    pub fn slash_evidence(
+        &self,
+        attestation: &PoIAttestation,
+        pack: &EvidencePack,
+        validator_id: ValidatorId,
+        slot: u64,
+    ) -> Result<Option<SlashEvidence>> {
+        let BenchmarkVerdict::Unreproduced { methodology_ref, reason, mismatches } = self else {
+            return Ok(None);
+        };
+
+        let proof = FraudulentPoIProof {
+            attestation: attestation.clone(),
+            evidence_pack: hex::encode(pack.as_bytes()),
+            methodology_ref: methodology_ref.clone(),
+            reason: reason.clone(),
+            mismatches: mismatches.clone(),
+        };
+
+        Ok(Some(SlashEvidence {
+            validator_id,
+            reason: SlashReason::FraudulentPoI,
+            slot,
+            proof: to_canonical_bytes(&proof)?,
+        }))
+    }
+}
+
+/// Proof payload of a benchmark-based `FraudulentPoI` slash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FraudulentPoIProof {
+    /// Signed attestation whose claim failed
+    ///
+    /// The signature ties the claim to the accused validator's key.
+    pub attestation: PoIAttestation,
+
+    /// Evidence pack the claim failed on (hex)
+    pub evidence_pack: String,
+    pub methodology_ref: String,
+    pub reason: String,
+    pub mismatches: Vec<MetricMismatch>,
+}
+
+/// Re-executes registered benchmarks against evidence packs
+pub struct BenchmarkVerifier {
+    registry: BenchmarkRegistry,
+}
+
+impl BenchmarkVerifier {
+    pub fn new(registry: BenchmarkRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// Re-run the attestation's benchmark on `pack`
+    ///
+    /// A pack that does not match the attested `Evidence` is unreproduced.
+    /// Runner failures (crash, timeout, bad output) are returned as errors:
+    /// they are inconclusive and must not by themselves justify a slash.
+    pub fn verify(&self, attestation: &PoIAttestation, pack: &EvidencePack) -> Result<BenchmarkVerdict> {
+        let (Some(benchmarks), Some(methodology_ref)) =
+            (&attestation.benchmarks, &attestation.evidence.methodology_ref)
+        else {
+            return Ok(BenchmarkVerdict::NotApplicable);
+        };
+        let Some(runner) = self.registry.get(methodology_ref) else {
+            return Ok(BenchmarkVerdict::NotApplicable);
+        };
+
+        let unreproduced = |reason: String, mismatches| BenchmarkVerdict::Unreproduced {
+            methodology_ref: methodology_ref.clone(),
+            reason,
+            mismatches,
+        };
+
+        if let Err(e) = pack.verify_against(&attestation.evidence) {
+            return Ok(unreproduced(e.to_string(), Vec::new()));
+        }
+
+        let scratch = tempfile::tempdir().context("Failed to create benchmark scratch directory")?;
+        pack.extract_to(scratch.path())?;
+        let metrics = runner.run(scratch.path())?;
+
+        let tolerance = runner.tolerance();
+        let mismatches: Vec<MetricMismatch> = benchmarks
+            .post
+            .iter()
+            .filter_map(|(metric, &claimed)| {
+                let reproduced = metrics.get(metric).copied();
+                match reproduced {
+                    Some(value) if (value - claimed).abs() <= tolerance => None,
+                    _ => Some(MetricMismatch { metric: metric.clone(), claimed, reproduced }),
+                }
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            Ok(BenchmarkVerdict::Reproduced { metrics })
+        } else {
+            Ok(unreproduced(format!("{} claimed metric(s) not reproduced", mismatches.len()), mismatches))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evidence::EvidencePackBuilder;
+    use crate::poi::tests::{test_attestation, test_signing_key};
+    use crate::poi::Benchmarks;
+
+    const METHODOLOGY: &str = "doi:10.1234/bizra.bench.filecount.v1";
+
+    /// Counts evidence files: performance = files / 10
+    struct FileCount;
+
+    impl BenchmarkRunner for FileCount {
+        fn run(&self, evidence_dir: &Path) -> Result<Metrics> {
+            let files = std::fs::read_dir(evidence_dir)?.count();
+            Ok(Metrics::from([("performance".to_string(), files as f64 / 10.0)]))
+        }
+    }
+
+    fn setup(claimed_post: f64) -> (PoIAttestation, EvidencePack, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt", "secret.txt"] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+        let pack = EvidencePackBuilder::new(dir.path()).redact("secret.txt").build().unwrap();
+
+        let mut attestation = test_attestation();
+        attestation.evidence = pack.to_evidence(Some(METHODOLOGY.to_string()));
+        attestation.benchmarks = Some(Benchmarks {
+            pre: BTreeMap::from([("performance".to_string(), 0.1)]),
+            post: BTreeMap::from([("performance".to_string(), claimed_post)]),
+            delta: claimed_post - 0.1,
+        });
+        attestation.sign(&test_signing_key()).unwrap();
+        (attestation, pack, dir)
+    }
+
+    fn verifier() -> BenchmarkVerifier {
+        let mut registry = BenchmarkRegistry::new();
+        registry.register(METHODOLOGY, Arc::new(FileCount));
+        BenchmarkVerifier::new(registry)
+    }
+
+    #[test]
+    fn test_reproduced_claim() {
+        // Redacted file is not extracted: 3 files → 0.3
+        let (attestation, pack, _dir) = setup(0.3);
+        let verdict = verifier().verify(&attestation, &pack).unwrap();
+        assert!(matches!(verdict, BenchmarkVerdict::Reproduced { .. }));
+        assert!(verdict.slash_evidence(&attestation, &pack, [1u8; 32], 7).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unreproduced_claim_yields_slash_evidence() {
+        let (attestation, pack, _dir) = setup(0.9);
+        let verdict = verifier().verify(&attestation, &pack).unwrap();
+        assert!(verdict.is_unreproduced());
+
+        let evidence = verdict.slash_evidence(&attestation, &pack, [1u8; 32], 7).unwrap().unwrap();
+        assert_eq!(evidence.reason, SlashReason::FraudulentPoI);
+        assert_eq!(evidence.slot, 7);
+
+        let proof: FraudulentPoIProof = serde_json::from_slice(&evidence.proof).unwrap();
+        assert_eq!(proof.attestation, attestation);
+        assert_eq!(proof.evidence_pack, hex::encode(pack.as_bytes()));
+        assert_eq!(proof.mismatches, vec![MetricMismatch {
+            metric: "performance".to_string(),
+            claimed: 0.9,
+            reproduced: Some(0.3),
+        }]);
+    }
+
+    #[test]
+    fn test_pack_mismatch_is_unreproduced() {
+        let (attestation, _, _dir) = setup(0.3);
+        let other = tempfile::tempdir().unwrap();
+        std::fs::write(other.path().join("x.txt"), "x").unwrap();
+        let wrong_pack = EvidencePackBuilder::new(other.path()).build().unwrap();
+
+        let verdict = verifier().verify(&attestation, &wrong_pack).unwrap();
+        assert!(matches!(verdict, BenchmarkVerdict::Unreproduced { ref reason, .. } if reason.contains("hash mismatch")));
+    }
+
+    #[test]
+    fn test_unregistered_methodology_not_applicable() {
+        let (mut attestation, pack, _dir) = setup(0.3);
+        attestation.evidence.methodology_ref = Some("doi:10.1234/unknown".to_string());
+        assert_eq!(verifier().verify(&attestation, &pack).unwrap(), BenchmarkVerdict::NotApplicable);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_subprocess_benchmark() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("data.csv"), "1\n2\n3\n4\n").unwrap();
+
+        let script = r#"printf '{"rows": %d}' "$(cat data.csv | wc -l)""#;
+        let runner = SubprocessBenchmark::new("/bin/sh", vec!["-c".to_string(), script.to_string()]);
+        let metrics = runner.run(dir.path()).unwrap();
+        assert_eq!(metrics.get("rows"), Some(&4.0));
+
+        let failing = SubprocessBenchmark::new("/bin/sh", vec!["-c".to_string(), "exit 3".to_string()]);
+        assert!(failing.run(dir.path()).is_err());
+
+        let slow = SubprocessBenchmark::new("/bin/sh", vec!["-c".to_string(), "sleep 5".to_string()])
+            .with_timeout(Duration::from_millis(100));
+        let err = slow.run(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+    }
+}
@@ -0,0 +1,220 @@
+//! Stake bond accounting
+//!
+//! `ValidatorRecord::stake_bond` is the bonded amount: it is what the
+//! `ν·sqrt(stake_bond)` weight term sees, so a top-up or unbond changes weight
+//! from the next `apply_poi_carry`. Everything that has left the bonded amount
+//! is tracked in the record's [`BondAccount`]:
+//!
+//! ```text
+//!   deposit / top-up          unbond                 release_epoch reached     withdraw
+//!  ─────────────────▶ bonded ────────▶ unbonding ─────────────────────▶ withdrawable ────────▶ withdrawn
+//!                       │                  │
+//!                       └──── slash ───────┴────▶ burned
+//! ```
+//!
+//! Unbonding funds stay slashable until they are released: a slash burns the
+//! same share of every unbonding entry as of the bonded amount, so exiting
+//! does not shield a bond from evidence that arrives during the delay.
+//!
+//! Invariant: the total ever deposited (not stored) equals
+//! `stake_bond + unbonding_total() + withdrawable + withdrawn + burned`.
+//!
+//! Operators move funds with signed [`BondTx`](crate::tx::BondTx)s; each
+//! carries the next [`BondAccount::nonce`], so a signed request applies once.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::poi::BPS_SCALE;
+use crate::tx::BondAction;
+
+/// Bond operation rejections
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BondError {
+    #[error("Bond amount must be positive")]
+    ZeroAmount,
+
+    #[error("Insufficient bond: {available} available, {requested} requested")]
+    Insufficient { available: u128, requested: u128 },
+
+    #[error("Bond is locked while validator is {0}")]
+    Locked(String),
+
+    #[error("Bond overflow")]
+    Overflow,
+
+    #[error("Nothing to withdraw")]
+    NothingToWithdraw,
+}
+
+/// Amount leaving the bond, withdrawable from `release_epoch`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnbondingEntry {
+    pub amount: u128,
+    pub release_epoch: u64,
+}
+
+/// Bond funds that are no longer bonded
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BondAccount {
+    /// Unbonding queue, in request order
+    pub unbonding: Vec<UnbondingEntry>,
+
+    /// Released and not yet withdrawn
+    pub withdrawable: u128,
+
+    /// Paid out so far
+    pub withdrawn: u128,
+
+    /// Burned by slashing (bonded and unbonding)
+    pub burned: u128,
+
+    /// Bond transactions applied so far
+    #[serde(default)]
+    pub nonce: u64,
+}
+
+/// Outcome of an applied bond transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BondReceipt {
+    pub action: BondAction,
+
+    /// Amount deposited, unbonded or paid out
+    pub amount: u128,
+
+    /// Bonded amount afterwards
+    pub stake_bond: u128,
+
+    /// Epoch the unbonded amount becomes withdrawable (unbond only)
+    pub release_epoch: Option<u64>,
+}
+
+impl BondAccount {
+    /// Total amount still unbonding
+    pub fn unbonding_total(&self) -> u128 {
+        self.unbonding.iter().map(|e| e.amount).sum()
+    }
+
+    /// Add `amount` to `bonded`
+    pub fn deposit(bonded: &mut u128, amount: u128) -> Result<(), BondError> {
+        if amount == 0 {
+            return Err(BondError::ZeroAmount);
+        }
+        *bonded = bonded.checked_add(amount).ok_or(BondError::Overflow)?;
+        Ok(())
+    }
+
+    /// Move `amount` from `bonded` into the unbonding queue
+    pub fn unbond(&mut self, bonded: &mut u128, amount: u128, release_epoch: u64) -> Result<(), BondError> {
+        if amount == 0 {
+            return Err(BondError::ZeroAmount);
+        }
+        if amount > *bonded {
+            return Err(BondError::Insufficient { available: *bonded, requested: amount });
+        }
+        *bonded -= amount;
+        self.unbonding.push(UnbondingEntry { amount, release_epoch });
+        Ok(())
+    }
+
+    /// Make entries with `release_epoch <= epoch` withdrawable; returns the amount released
+    pub fn release(&mut self, epoch: u64) -> u128 {
+        let mut released = 0;
+        self.unbonding.retain(|entry| {
+            if entry.release_epoch <= epoch {
+                released += entry.amount;
+                false
+            } else {
+                true
+            }
+        });
+        self.withdrawable += released;
+        released
+    }
+
+    /// Pay out everything released; returns the amount withdrawn
+    pub fn withdraw(&mut self) -> Result<u128, BondError> {
+        if self.withdrawable == 0 {
+            return Err(BondError::NothingToWithdraw);
+        }
+        let amount = std::mem::take(&mut self.withdrawable);
+        self.withdrawn += amount;
+        Ok(amount)
+    }
+
+    /// Burn `burn_bps` of `bonded` and of every unbonding entry; returns the amount burned
+    ///
+    /// Released (withdrawable) funds are out of reach. Rounds down per entry.
+    pub fn slash(&mut self, bonded: &mut u128, burn_bps: u128) -> u128 {
+        let burn_bps = burn_bps.min(BPS_SCALE);
+        let share = |amount: u128| amount / BPS_SCALE * burn_bps + amount % BPS_SCALE * burn_bps / BPS_SCALE;
+
+        let mut burned = share(*bonded);
+        *bonded -= burned;
+        for entry in &mut self.unbonding {
+            let cut = share(entry.amount);
+            entry.amount -= cut;
+            burned += cut;
+        }
+        self.unbonding.retain(|e| e.amount > 0);
+
+        self.burned += burned;
+        burned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total(bonded: u128, account: &BondAccount) -> u128 {
+        bonded + account.unbonding_total() + account.withdrawable + account.withdrawn + account.burned
+    }
+
+    #[test]
+    fn test_bond_lifecycle() {
+        let mut account = BondAccount::default();
+        let mut bonded = 0;
+
+        BondAccount::deposit(&mut bonded, 1_000).unwrap();
+        BondAccount::deposit(&mut bonded, 500).unwrap(); // Top-up
+        assert_eq!(BondAccount::deposit(&mut bonded, 0), Err(BondError::ZeroAmount));
+
+        account.unbond(&mut bonded, 400, 3).unwrap();
+        account.unbond(&mut bonded, 100, 5).unwrap();
+        assert_eq!(account.unbond(&mut bonded, 1_001, 5), Err(BondError::Insufficient { available: 1_000, requested: 1_001 }));
+        assert_eq!((bonded, account.unbonding_total()), (1_000, 500));
+
+        assert_eq!(account.withdraw(), Err(BondError::NothingToWithdraw));
+        assert_eq!(account.release(2), 0);
+        assert_eq!(account.release(4), 400);
+        assert_eq!(account.withdraw(), Ok(400));
+        assert_eq!(account.unbonding, vec![UnbondingEntry { amount: 100, release_epoch: 5 }]);
+        assert_eq!(total(bonded, &account), 1_500);
+    }
+
+    #[test]
+    fn test_slash_reaches_unbonding_funds() {
+        let mut account = BondAccount::default();
+        let mut bonded = 10_000;
+        account.unbond(&mut bonded, 4_000, 5).unwrap();
+        account.unbond(&mut bonded, 1_000, 1).unwrap();
+        account.release(1); // Out of reach
+
+        // 50%: 2,500 of bonded + 2,000 of the remaining unbonding entry
+        assert_eq!(account.slash(&mut bonded, 5_000), 4_500);
+        assert_eq!(bonded, 2_500);
+        assert_eq!(account.unbonding_total(), 2_000);
+        assert_eq!(account.withdrawable, 1_000);
+
+        // Full burn empties the queue
+        assert_eq!(account.slash(&mut bonded, BPS_SCALE), 4_500);
+        assert_eq!(bonded, 0);
+        assert!(account.unbonding.is_empty());
+        assert_eq!(total(bonded, &account), 10_000);
+
+        // No overflow near u128::MAX
+        let mut huge = u128::MAX;
+        assert_eq!(BondAccount::default().slash(&mut huge, 5_000), u128::MAX / 2);
+    }
+}
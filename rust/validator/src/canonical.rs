@@ -0,0 +1,253 @@
+//! JSON Canonicalization Scheme (RFC 8785)
+//!
+//! Produces the byte-exact canonical form of a JSON value so that digests and
+//! signatures over PoI attestations can be reproduced by any JCS implementation
+//! (our JS and Python services included):
+//!
+//! - Object members sorted by the UTF-16 code units of their names
+//! - No insignificant whitespace
+//! - Strings escaped as ECMAScript `JSON.stringify` does
+//! - Numbers serialized as ECMAScript `Number.prototype.toString` does
+//!
+//! Cross-language test vectors live in `validator/tests/vectors/jcs_vectors.json`.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Largest integer magnitude an IEEE 754 double represents exactly (2^53)
+pub const MAX_SAFE_INTEGER: u64 = 1 << 53;
+
+/// Canonicalize any serializable value
+pub fn to_canonical_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(value)?;
+    Ok(canonicalize(&value)?.into_bytes())
+}
+
+/// Canonicalize a JSON value to its RFC 8785 string form
+pub fn canonicalize(value: &Value) -> Result<String> {
+    let mut out = String::new();
+    write_value(&mut out, value)?;
+    Ok(out)
+}
+
+fn write_value(out: &mut String, value: &Value) -> Result<()> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&format_number(n)?),
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            // RFC 8785 section 3.2.3: sort by UTF-16 code units, not UTF-8 bytes
+            let mut members: Vec<(Vec<u16>, &String, &Value)> = map
+                .iter()
+                .map(|(k, v)| (k.encode_utf16().collect(), k, v))
+                .collect();
+            members.sort_by(|a, b| a.0.cmp(&b.0));
+
+            out.push('{');
+            for (i, (_, key, val)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, val)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// RFC 8785 section 3.2.2.2: JSON.stringify string escaping
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{09}' => out.push_str("\\t"),
+            '\u{0A}' => out.push_str("\\n"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\u{0D}' => out.push_str("\\r"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn format_number(n: &serde_json::Number) -> Result<String> {
+    if let Some(u) = n.as_u64() {
+        if u > MAX_SAFE_INTEGER {
+            bail!("Integer {} exceeds the IEEE 754 safe range required by RFC 8785", u);
+        }
+        return format_f64(u as f64);
+    }
+    if let Some(i) = n.as_i64() {
+        if i.unsigned_abs() > MAX_SAFE_INTEGER {
+            bail!("Integer {} exceeds the IEEE 754 safe range required by RFC 8785", i);
+        }
+        return format_f64(i as f64);
+    }
+    match n.as_f64() {
+        Some(f) => format_f64(f),
+        None => bail!("Unrepresentable JSON number: {}", n),
+    }
+}
+
+/// RFC 8785 section 3.2.2.3: ECMAScript Number.prototype.toString
+///
+/// Rust's `{:e}` formatting yields the shortest digit string that round-trips,
+/// which is the digit selection ECMAScript requires up to tie-breaking (see
+/// [`shortest_digits`]); the layout (plain vs exponent notation) is applied here.
+pub fn format_f64(value: f64) -> Result<String> {
+    if !value.is_finite() {
+        bail!("NaN and Infinity are not valid JSON numbers");
+    }
+    if value == 0.0 {
+        // Covers -0 as well
+        return Ok("0".to_string());
+    }
+
+    let (digits, exp) = shortest_digits(value.abs());
+
+    // value = 0.d1d2...dk × 10^n
+    let k = digits.len() as i32;
+    let n = exp + 1;
+
+    let mut out = String::new();
+    if value < 0.0 {
+        out.push('-');
+    }
+
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.push_str(&"0".repeat((n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.push_str(&"0".repeat((-n) as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        out.push(if n >= 1 { '+' } else { '-' });
+        out.push_str(&(n - 1).abs().to_string());
+    }
+
+    Ok(out)
+}
+
+/// Shortest round-tripping digits and decimal exponent (`d.ddd × 10^exp`)
+///
+/// ECMA-262 breaks ties between two equally close shortest candidates by
+/// picking the even one; Rust's formatter rounds such ties up, so the odd
+/// result is checked against the exact decimal expansion and stepped down.
+fn shortest_digits(abs: f64) -> (String, i32) {
+    let (mut digits, exp) = split_exp(&format!("{:e}", abs));
+
+    let last = digits.as_bytes()[digits.len() - 1];
+    if (last - b'0') % 2 == 1 {
+        let mut lower = digits.clone();
+        lower.pop();
+        lower.push((last - 1) as char);
+
+        // Exact expansion: a double has at most 767 significant decimal digits
+        let (exact, exact_exp) = split_exp(&format!("{:.800e}", abs));
+        let midpoint = format!("{}5", lower);
+        if exact_exp == exp && exact.trim_end_matches('0') == midpoint {
+            let candidate = format!("{}.{}e{}", &lower[..1], &lower[1..], exp);
+            if candidate.parse::<f64>() == Ok(abs) {
+                digits = lower;
+            }
+        }
+    }
+
+    (digits, exp)
+}
+
+fn split_exp(sci: &str) -> (String, i32) {
+    let (mantissa, exp) = sci.split_once('e').expect("LowerExp output contains 'e'");
+    let digits = mantissa.chars().filter(|c| *c != '.').collect();
+    (digits, exp.parse().expect("LowerExp exponent is an integer"))
+}
+
+/// Serde adapter for `u128` amounts as decimal strings
+///
+/// Canonical JSON numbers are doubles, so amounts above [`MAX_SAFE_INTEGER`]
+/// must travel as strings in signed documents: `#[serde(with = "u128_string")]`.
+pub mod u128_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_number_formatting() {
+        assert_eq!(format_f64(0.0).unwrap(), "0");
+        assert_eq!(format_f64(-0.0).unwrap(), "0");
+        assert_eq!(format_f64(1.0).unwrap(), "1");
+        assert_eq!(format_f64(4.5).unwrap(), "4.5");
+        assert_eq!(format_f64(0.002).unwrap(), "0.002");
+        assert_eq!(format_f64(0.876).unwrap(), "0.876");
+        assert_eq!(format_f64(1e21).unwrap(), "1e+21");
+        assert_eq!(format_f64(1e20).unwrap(), "100000000000000000000");
+        assert_eq!(format_f64(1e-7).unwrap(), "1e-7");
+        assert_eq!(format_f64(1e-6).unwrap(), "0.000001");
+        assert_eq!(format_f64(-1.5e30).unwrap(), "-1.5e+30");
+        // Exactly halfway between two 17-digit candidates: ECMAScript picks the even one
+        assert_eq!(format_f64(f64::from_bits(0x4314_3ff3_c1cb_0959)).unwrap(), "1424953923781206.2");
+        assert!(format_f64(f64::NAN).is_err());
+        assert!(format_f64(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_integer_range() {
+        assert_eq!(canonicalize(&json!(52428800000u64)).unwrap(), "52428800000");
+        assert_eq!(canonicalize(&json!(-42)).unwrap(), "-42");
+        assert!(canonicalize(&json!(MAX_SAFE_INTEGER + 1)).is_err());
+    }
+
+    #[test]
+    fn test_object_ordering_and_whitespace() {
+        let value = json!({ "b": [1, { "z": null, "a": true }], "a": "x" });
+        assert_eq!(canonicalize(&value).unwrap(), r#"{"a":"x","b":[1,{"a":true,"z":null}]}"#);
+    }
+
+    #[test]
+    fn test_string_escaping() {
+        let value = json!("\u{0f}\n\"\\/\u{7f}é");
+        assert_eq!(canonicalize(&value).unwrap(), "\"\\u000f\\n\\\"\\\\/\u{7f}é\"");
+    }
+}
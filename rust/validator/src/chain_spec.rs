@@ -0,0 +1,260 @@
+//! Chain specification loaded from a genesis file
+//!
+//! A [`ChainSpec`] pins everything a validator needs to agree on with the rest
+//! of its network: the chain anchor attestations must bind to, slot/epoch
+//! timing, the weight formula parameters and the validator-set thresholds.
+//! Devnet, testnet and mainnet differ only in their genesis files, so several
+//! specs can coexist in one process.
+//!
+//! ## Genesis File (JSON)
+//!
+//! ```json
+//! {
+//!   "chain_id": "bizra-testnet-001",
+//!   "genesis_merkle_root": "d9c9fa50...926f",
+//!   "epoch": { "epoch_duration_slots": 32, "slot_duration_ms": 2000, "unbonding_delay_epochs": 2, "key_rotation_delay_epochs": 2, "key_history_epochs": 64 },
+//!   "weights": { "base_weight": 100, "poi_lambda_bps": 100000, "rep_mu_bps": 500, "stake_nu_bps": 200 },
+//!   "aggregation": { "carry_decay_bps": 5000, "epoch_impact_cap_bps": 50000, "carry_cap_bps": 100000 },
+//!   "thresholds": { "finality_threshold": 0.67, "max_active_validators": 100, ... },
+//!   "rewards": { "bloom_per_epoch": 10000, "seed_per_epoch": 5000, "proposer_share_bps": 2000, "liveness_penalty_bps": 1000 }
+//! }
+//! ```
+//!
+//! Every section except the anchor is optional and defaults to the crate
+//! constants. The reference testnet genesis lives in `validator/genesis/testnet.json`.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::epoch::AggregationParams;
+use crate::poi::{WeightParams, BPS_SCALE};
+use crate::rewards::RewardParams;
+
+/// Alpha testnet chain identifier
+pub const TESTNET_CHAIN_ID: &str = "bizra-testnet-001";
+
+/// Alpha testnet genesis block Merkle root
+pub const TESTNET_GENESIS_MERKLE_ROOT: &str =
+    "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f";
+
+/// Slot and epoch timing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EpochParams {
+    pub epoch_duration_slots: u64,
+    pub slot_duration_ms: u64,
+    pub unbonding_delay_epochs: u64,
+
+    /// Epochs between announcing a key rotation and the new key taking over
+    pub key_rotation_delay_epochs: u64,
+
+    /// Epochs a rotated-out key stays in the key history as key-theft
+    /// evidence; at least the unbonding delay
+    pub key_history_epochs: u64,
+}
+
+impl Default for EpochParams {
+    fn default() -> Self {
+        Self {
+            epoch_duration_slots: crate::EPOCH_DURATION_SLOTS,
+            slot_duration_ms: crate::SLOT_DURATION_MS,
+            unbonding_delay_epochs: crate::UNBONDING_DELAY_EPOCHS,
+            key_rotation_delay_epochs: crate::KEY_ROTATION_DELAY_EPOCHS,
+            key_history_epochs: crate::KEY_HISTORY_EPOCHS,
+        }
+    }
+}
+
+/// Validator-set thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Thresholds {
+    pub finality_threshold: f64,
+    pub max_active_validators: usize,
+    pub min_reputation_for_activation: u64,
+    pub liveness_miss_threshold: u64,
+    pub offline_threshold: u64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            finality_threshold: crate::DEFAULT_FINALITY_THRESHOLD,
+            max_active_validators: crate::MAX_ACTIVE_VALIDATORS,
+            min_reputation_for_activation: crate::MIN_REPUTATION_FOR_ACTIVATION,
+            liveness_miss_threshold: crate::LIVENESS_MISS_THRESHOLD,
+            offline_threshold: crate::OFFLINE_THRESHOLD,
+        }
+    }
+}
+
+/// Chain parameters shared by every validator of one network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainSpec {
+    /// Chain identifier (e.g., "bizra-main-alpha", "bizra-testnet-001")
+    pub chain_id: String,
+
+    /// Genesis block Merkle root (32 bytes hex)
+    pub genesis_merkle_root: String,
+
+    #[serde(default)]
+    pub epoch: EpochParams,
+
+    #[serde(default)]
+    pub weights: WeightParams,
+
+    #[serde(default)]
+    pub aggregation: AggregationParams,
+
+    #[serde(default)]
+    pub thresholds: Thresholds,
+
+    #[serde(default)]
+    pub rewards: RewardParams,
+}
+
+impl ChainSpec {
+    /// Spec with the given anchor and default parameters
+    pub fn new(chain_id: impl Into<String>, genesis_merkle_root: impl Into<String>) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            genesis_merkle_root: genesis_merkle_root.into(),
+            epoch: EpochParams::default(),
+            weights: WeightParams::default(),
+            aggregation: AggregationParams::default(),
+            thresholds: Thresholds::default(),
+            rewards: RewardParams::default(),
+        }
+    }
+
+    /// Built-in alpha testnet spec
+    pub fn testnet() -> Self {
+        Self::new(TESTNET_CHAIN_ID, TESTNET_GENESIS_MERKLE_ROOT)
+    }
+
+    /// Parse and check a genesis JSON document
+    pub fn from_json(json: &str) -> Result<Self> {
+        let spec: Self = serde_json::from_str(json).context("Invalid genesis file")?;
+        spec.check()?;
+        Ok(spec)
+    }
+
+    /// Load and check a genesis file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read genesis file {}", path.display()))?;
+        Self::from_json(&json).with_context(|| format!("Genesis file {}", path.display()))
+    }
+
+    /// Sanity-check parameters
+    pub fn check(&self) -> Result<()> {
+        if self.chain_id.is_empty() {
+            bail!("chain_id must not be empty");
+        }
+        if self.genesis_merkle_root.len() != 64
+            || !self.genesis_merkle_root.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            bail!("genesis_merkle_root must be 64 hex chars");
+        }
+        if self.epoch.epoch_duration_slots == 0 || self.epoch.slot_duration_ms == 0 {
+            bail!("Epoch and slot durations must be non-zero");
+        }
+        if self.epoch.key_history_epochs < self.epoch.unbonding_delay_epochs {
+            bail!("key_history_epochs must be at least unbonding_delay_epochs");
+        }
+        if !(0.5..=1.0).contains(&self.thresholds.finality_threshold) {
+            bail!("finality_threshold must be in [0.5, 1.0], got {}", self.thresholds.finality_threshold);
+        }
+        if self.thresholds.max_active_validators == 0 {
+            bail!("max_active_validators must be non-zero");
+        }
+        if self.thresholds.liveness_miss_threshold > self.thresholds.offline_threshold {
+            bail!("liveness_miss_threshold must not exceed offline_threshold");
+        }
+        if self.aggregation.carry_decay_bps > BPS_SCALE {
+            bail!("carry_decay_bps must not exceed {}", BPS_SCALE);
+        }
+        if self.rewards.proposer_share_bps > BPS_SCALE {
+            bail!("proposer_share_bps must not exceed {}", BPS_SCALE);
+        }
+        Ok(())
+    }
+
+    /// Epoch containing `slot`
+    pub fn slot_to_epoch(&self, slot: u64) -> u64 {
+        slot / self.epoch.epoch_duration_slots
+    }
+
+    /// Position of `slot` within its epoch
+    pub fn slot_within_epoch(&self, slot: u64) -> u64 {
+        slot % self.epoch.epoch_duration_slots
+    }
+}
+
+impl Default for ChainSpec {
+    fn default() -> Self {
+        Self::testnet()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_testnet_genesis() {
+        let spec = ChainSpec::from_json(include_str!("../genesis/testnet.json")).unwrap();
+        assert_eq!(spec, ChainSpec::testnet());
+    }
+
+    #[test]
+    fn test_minimal_genesis_uses_defaults() {
+        let spec = ChainSpec::from_json(
+            r#"{ "chain_id": "bizra-devnet", "genesis_merkle_root": "0000000000000000000000000000000000000000000000000000000000000000",
+                 "epoch": { "epoch_duration_slots": 8 }, "weights": { "base_weight": 10, "poi_lambda_bps": 1, "rep_mu_bps": 2, "stake_nu_bps": 3 } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(spec.chain_id, "bizra-devnet");
+        assert_eq!(spec.epoch.epoch_duration_slots, 8);
+        assert_eq!(spec.epoch.slot_duration_ms, crate::SLOT_DURATION_MS);
+        assert_eq!(spec.weights.rep_mu_bps, 2);
+        assert_eq!(spec.thresholds, Thresholds::default());
+        assert_eq!(spec.slot_to_epoch(17), 2);
+        assert_eq!(spec.slot_within_epoch(17), 1);
+
+        // Sections may be partial
+        let spec = ChainSpec::from_json(
+            r#"{ "chain_id": "bizra-devnet", "genesis_merkle_root": "0000000000000000000000000000000000000000000000000000000000000000",
+                 "weights": { "base_weight": 10 }, "aggregation": { "carry_decay_bps": 2500 } }"#,
+        )
+        .unwrap();
+        assert_eq!(spec.weights, WeightParams { base_weight: 10, ..WeightParams::default() });
+        assert_eq!(spec.aggregation, AggregationParams { carry_decay_bps: 2_500, ..AggregationParams::default() });
+    }
+
+    #[test]
+    fn test_invalid_genesis_rejected() {
+        let mut spec = ChainSpec::testnet();
+        spec.genesis_merkle_root = "abc".to_string();
+        assert!(spec.check().is_err());
+
+        let mut spec = ChainSpec::testnet();
+        spec.thresholds.finality_threshold = 0.4;
+        assert!(spec.check().is_err());
+
+        let mut spec = ChainSpec::testnet();
+        spec.epoch.epoch_duration_slots = 0;
+        assert!(spec.check().is_err());
+
+        let mut spec = ChainSpec::testnet();
+        spec.epoch.key_history_epochs = spec.epoch.unbonding_delay_epochs - 1;
+        assert!(spec.check().is_err());
+
+        assert!(ChainSpec::from_json(r#"{ "chain_id": "x" }"#).is_err());
+        assert!(ChainSpec::load("/nonexistent/genesis.json").is_err());
+    }
+}
@@ -0,0 +1,461 @@
+//! Epoch-level PoI aggregation
+//!
+//! Collects accepted attestations per validator during an epoch and, at the
+//! epoch boundary, folds them into a decayed carry-over score:
+//!
+//! ```text
+//! PoI_carry[e] = min(CARRY_CAP, PoI_carry[e-1] × decay + min(EPOCH_CAP, Σ impact_score[e]))
+//! ```
+//!
+//! The per-epoch and carry caps bound how much any one validator can gain from
+//! volume alone, so a whale submitting many attestations cannot dominate the
+//! weight distribution. All arithmetic is in basis points (see
+//! [`BPS_SCALE`](crate::poi::BPS_SCALE)).
+//!
+//! The aggregator also counts the blocks each validator proposes in the open
+//! epoch, which feed the proposer share of the epoch's rewards. Proposal
+//! counts live in memory only: a restart forgets those of the open epoch.
+//!
+//! Spec reference: BIZRA_Proof_of_Impact_Formal_Spec_v1.0.md section 5.8
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::poi::{score_to_bps, PoIAttestation, BPS_SCALE};
+use crate::store::{for_each_record, AttestationStore};
+use crate::types::ValidatorId;
+
+/// Default share of the previous carry retained each epoch (50%)
+pub const DEFAULT_CARRY_DECAY_BPS: u128 = 5_000;
+
+/// Default cap on impact credited to one validator per epoch (5.0)
+pub const DEFAULT_EPOCH_IMPACT_CAP_BPS: u128 = 50_000;
+
+/// Default cap on a validator's total carry (10.0, the steady state of the epoch cap at 50% decay)
+pub const DEFAULT_CARRY_CAP_BPS: u128 = 100_000;
+
+/// Number of top contributors reported in an epoch summary
+pub const TOP_CONTRIBUTORS: usize = 10;
+
+/// Aggregation parameters (chain parameters)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AggregationParams {
+    /// Share of previous carry retained per epoch, in bps
+    pub carry_decay_bps: u128,
+
+    /// Max impact credited per validator per epoch, in bps
+    pub epoch_impact_cap_bps: u128,
+
+    /// Max carry per validator, in bps
+    pub carry_cap_bps: u128,
+}
+
+impl Default for AggregationParams {
+    fn default() -> Self {
+        Self {
+            carry_decay_bps: DEFAULT_CARRY_DECAY_BPS,
+            epoch_impact_cap_bps: DEFAULT_EPOCH_IMPACT_CAP_BPS,
+            carry_cap_bps: DEFAULT_CARRY_CAP_BPS,
+        }
+    }
+}
+
+/// Attestations accepted for one validator in the open epoch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Contribution {
+    attestation_count: u64,
+    impact_bps: u128,
+}
+
+/// One validator's line in an epoch summary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContributorSummary {
+    pub validator_id: ValidatorId,
+
+    /// Attestations accepted this epoch
+    pub attestation_count: u64,
+
+    /// Raw impact submitted this epoch (bps)
+    pub impact_bps: u128,
+
+    /// Impact credited after the per-epoch cap (bps)
+    pub credited_impact_bps: u128,
+
+    /// Carry after this epoch (bps)
+    pub poi_carry_bps: u128,
+}
+
+/// Result of closing an epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochSummary {
+    pub epoch: u64,
+
+    /// Attestations accepted this epoch
+    pub attestation_count: u64,
+
+    /// Raw impact across all validators (bps)
+    pub total_impact_bps: u128,
+
+    /// Impact credited after caps (bps)
+    pub total_credited_impact_bps: u128,
+
+    /// Highest-carry validators, best first (ties broken by validator id)
+    pub top_contributors: Vec<ContributorSummary>,
+}
+
+/// An epoch closed by [`EpochAggregator::close`], not yet applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosedEpoch {
+    pub summary: EpochSummary,
+
+    /// Carry after the epoch, non-zero entries only (bps)
+    pub carry: HashMap<ValidatorId, u128>,
+}
+
+/// Per-epoch PoI aggregator with decayed carry-over
+#[derive(Debug, Clone)]
+pub struct EpochAggregator {
+    params: AggregationParams,
+
+    /// Epoch currently collecting attestations
+    epoch: u64,
+
+    /// Contributions for the open epoch
+    pending: HashMap<ValidatorId, Contribution>,
+
+    /// Blocks proposed per validator in the open epoch
+    proposals: HashMap<ValidatorId, u64>,
+
+    /// Carry after the last closed epoch
+    carry: HashMap<ValidatorId, u128>,
+
+    /// Summaries of closed epochs
+    summaries: BTreeMap<u64, EpochSummary>,
+}
+
+impl EpochAggregator {
+    /// Create aggregator collecting for `epoch`
+    pub fn new(epoch: u64) -> Self {
+        Self::with_params(epoch, AggregationParams::default())
+    }
+
+    /// Create aggregator with chain parameters
+    pub fn with_params(epoch: u64, params: AggregationParams) -> Self {
+        Self {
+            params,
+            epoch,
+            pending: HashMap::new(),
+            proposals: HashMap::new(),
+            carry: HashMap::new(),
+            summaries: BTreeMap::new(),
+        }
+    }
+
+    /// Rebuild the aggregator collecting `epoch` from the attestation store
+    ///
+    /// Stored epochs before `epoch` are replayed and closed in order, so the
+    /// carry and summaries equal the ones computed when they were accepted.
+    /// Records of `epoch` itself are credited to the open epoch.
+    pub fn from_store(epoch: u64, params: AggregationParams, store: &dyn AttestationStore) -> Result<Self> {
+        let mut by_epoch: BTreeMap<u64, Vec<(ValidatorId, u128)>> = BTreeMap::new();
+        for_each_record(store, |record| {
+            if record.epoch > epoch {
+                bail!("Attestation {} is from epoch {}, after the open epoch {}", record.digest, record.epoch, epoch);
+            }
+            if let Some(id) = &record.validator_id {
+                let validator_id: ValidatorId = hex::decode(id)
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| anyhow::anyhow!("Invalid validator_id {} in attestation {}", id, record.digest))?;
+                let impact = score_to_bps(record.attestation.measurement.impact_score);
+                by_epoch.entry(record.epoch).or_default().push((validator_id, impact));
+            }
+            Ok(())
+        })?;
+
+        let first = by_epoch.keys().next().copied().unwrap_or(epoch);
+        let mut aggregator = Self::with_params(first, params);
+        for e in first..=epoch {
+            for (validator_id, impact) in by_epoch.remove(&e).unwrap_or_default() {
+                aggregator.record_impact(validator_id, impact);
+            }
+            if e < epoch {
+                aggregator.finalize_epoch(e)?;
+            }
+        }
+        Ok(aggregator)
+    }
+
+    /// Epoch currently collecting attestations
+    pub fn current_epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Credit an accepted attestation to a validator in the open epoch
+    pub fn record(&mut self, validator_id: ValidatorId, attestation: &PoIAttestation) {
+        self.record_impact(validator_id, score_to_bps(attestation.measurement.impact_score));
+    }
+
+    /// Credit raw impact (bps) to a validator in the open epoch
+    pub fn record_impact(&mut self, validator_id: ValidatorId, impact_bps: u128) {
+        let entry = self.pending.entry(validator_id).or_default();
+        entry.attestation_count += 1;
+        entry.impact_bps = entry.impact_bps.saturating_add(impact_bps);
+    }
+
+    /// Count a block proposed by a validator in the open epoch
+    pub fn record_proposal(&mut self, validator_id: ValidatorId) {
+        *self.proposals.entry(validator_id).or_default() += 1;
+    }
+
+    /// Blocks proposed per validator in the open epoch
+    pub fn blocks_proposed(&self) -> &HashMap<ValidatorId, u64> {
+        &self.proposals
+    }
+
+    /// Close the open epoch, fold contributions into the carry and start `epoch + 1`
+    pub fn finalize_epoch(&mut self, epoch: u64) -> Result<EpochSummary> {
+        let closed = self.close(epoch)?;
+        self.advance(closed)
+    }
+
+    /// Fold the open epoch's contributions into the carry without applying it
+    ///
+    /// Lets a caller act on the new carry first and [`Self::advance`] only once
+    /// that succeeded.
+    pub fn close(&self, epoch: u64) -> Result<ClosedEpoch> {
+        if epoch != self.epoch {
+            bail!("Cannot finalize epoch {}: aggregator is collecting epoch {}", epoch, self.epoch);
+        }
+
+        let p = self.params;
+        let pending = &self.pending;
+        let mut carry = HashMap::new();
+        let mut contributors = Vec::new();
+        let mut attestation_count = 0u64;
+        let mut total_impact_bps = 0u128;
+        let mut total_credited_impact_bps = 0u128;
+
+        let mut ids: Vec<ValidatorId> = self.carry.keys().chain(pending.keys()).copied().collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        for id in ids {
+            let previous = self.carry.get(&id).copied().unwrap_or(0);
+            let contribution = pending.get(&id).copied().unwrap_or_default();
+            let credited = contribution.impact_bps.min(p.epoch_impact_cap_bps);
+
+            let decayed = previous.saturating_mul(p.carry_decay_bps) / BPS_SCALE;
+            let next = decayed.saturating_add(credited).min(p.carry_cap_bps);
+
+            attestation_count += contribution.attestation_count;
+            total_impact_bps = total_impact_bps.saturating_add(contribution.impact_bps);
+            total_credited_impact_bps = total_credited_impact_bps.saturating_add(credited);
+
+            if next > 0 {
+                carry.insert(id, next);
+            }
+            if contribution.attestation_count > 0 {
+                contributors.push(ContributorSummary {
+                    validator_id: id,
+                    attestation_count: contribution.attestation_count,
+                    impact_bps: contribution.impact_bps,
+                    credited_impact_bps: credited,
+                    poi_carry_bps: next,
+                });
+            }
+        }
+
+        contributors.sort_by(|a, b| {
+            b.poi_carry_bps
+                .cmp(&a.poi_carry_bps)
+                .then_with(|| a.validator_id.cmp(&b.validator_id))
+        });
+        contributors.truncate(TOP_CONTRIBUTORS);
+
+        let summary = EpochSummary {
+            epoch,
+            attestation_count,
+            total_impact_bps,
+            total_credited_impact_bps,
+            top_contributors: contributors,
+        };
+        Ok(ClosedEpoch { summary, carry })
+    }
+
+    /// Apply an epoch from [`Self::close`] and start collecting the next one
+    pub fn advance(&mut self, closed: ClosedEpoch) -> Result<EpochSummary> {
+        let epoch = closed.summary.epoch;
+        if epoch != self.epoch {
+            bail!("Cannot finalize epoch {}: aggregator is collecting epoch {}", epoch, self.epoch);
+        }
+
+        self.pending.clear();
+        self.proposals.clear();
+        self.carry = closed.carry;
+        self.summaries.insert(epoch, closed.summary.clone());
+        self.epoch = epoch + 1;
+
+        Ok(closed.summary)
+    }
+
+    /// Attestations credited to a validator in the open epoch
+    pub fn attestation_count(&self, validator_id: &ValidatorId) -> u64 {
+        self.pending.get(validator_id).map_or(0, |c| c.attestation_count)
+    }
+
+    /// Carry of a validator after the last closed epoch (bps)
+    pub fn poi_carry(&self, validator_id: &ValidatorId) -> u128 {
+        self.carry.get(validator_id).copied().unwrap_or(0)
+    }
+
+    /// Carry of all validators with a non-zero carry
+    pub fn carry(&self) -> &HashMap<ValidatorId, u128> {
+        &self.carry
+    }
+
+    /// Summary of a closed epoch
+    pub fn summary(&self, epoch: u64) -> Option<&EpochSummary> {
+        self.summaries.get(&epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: ValidatorId = [1u8; 32];
+    const B: ValidatorId = [2u8; 32];
+
+    #[test]
+    fn test_carry_decays_and_accumulates() {
+        let mut aggregator = EpochAggregator::new(0);
+        aggregator.record_impact(A, 8_760);
+        aggregator.record_impact(A, 5_000);
+        let summary = aggregator.finalize_epoch(0).unwrap();
+
+        assert_eq!(summary.attestation_count, 2);
+        assert_eq!(summary.total_impact_bps, 13_760);
+        assert_eq!(aggregator.poi_carry(&A), 13_760);
+        assert_eq!(aggregator.current_epoch(), 1);
+
+        // No attestations in epoch 1: carry halves
+        aggregator.finalize_epoch(1).unwrap();
+        assert_eq!(aggregator.poi_carry(&A), 6_880);
+
+        aggregator.record_impact(A, 1_000);
+        aggregator.finalize_epoch(2).unwrap();
+        assert_eq!(aggregator.poi_carry(&A), 3_440 + 1_000);
+    }
+
+    #[test]
+    fn test_caps_limit_whales() {
+        let mut aggregator = EpochAggregator::new(0);
+        for _ in 0..100 {
+            aggregator.record_impact(A, 10_000);
+        }
+        aggregator.record_impact(B, 10_000);
+        let summary = aggregator.finalize_epoch(0).unwrap();
+
+        assert_eq!(summary.total_impact_bps, 1_010_000);
+        assert_eq!(summary.total_credited_impact_bps, DEFAULT_EPOCH_IMPACT_CAP_BPS + 10_000);
+        assert_eq!(aggregator.poi_carry(&A), DEFAULT_EPOCH_IMPACT_CAP_BPS);
+
+        // Sustained max contribution converges to the carry cap, never above
+        for epoch in 1..40 {
+            for _ in 0..100 {
+                aggregator.record_impact(A, 10_000);
+            }
+            aggregator.finalize_epoch(epoch).unwrap();
+            assert!(aggregator.poi_carry(&A) <= DEFAULT_CARRY_CAP_BPS);
+        }
+        assert!(aggregator.poi_carry(&A) >= DEFAULT_CARRY_CAP_BPS - 1);
+    }
+
+    #[test]
+    fn test_summary_top_contributors() {
+        let mut aggregator = EpochAggregator::new(5);
+        for i in 0..12u8 {
+            aggregator.record_impact([i; 32], 1_000 * (i as u128 % 4));
+        }
+        let summary = aggregator.finalize_epoch(5).unwrap();
+
+        assert_eq!(summary.top_contributors.len(), TOP_CONTRIBUTORS);
+        let first = &summary.top_contributors[0];
+        assert_eq!((first.validator_id, first.poi_carry_bps), ([3u8; 32], 3_000));
+        assert_eq!(summary.top_contributors[1].validator_id, [7u8; 32]);
+        assert_eq!(aggregator.summary(5), Some(&summary));
+        assert!(aggregator.summary(4).is_none());
+    }
+
+    #[test]
+    fn test_rebuild_from_store() {
+        use crate::poi::tests::{test_attestation, test_signing_key};
+        use crate::store::{MemoryAttestationStore, StoredAttestation};
+
+        let mut store = MemoryAttestationStore::new();
+        let mut live = EpochAggregator::new(0);
+        for (i, (epoch, validator)) in [(0, Some(A)), (0, Some(B)), (0, None), (2, Some(A)), (3, Some(B))].into_iter().enumerate() {
+            let mut attestation = test_attestation();
+            attestation.nonce = format!("{:02x}", i).repeat(16);
+            attestation.sign(&test_signing_key()).unwrap();
+
+            while live.current_epoch() < epoch {
+                live.finalize_epoch(live.current_epoch()).unwrap();
+            }
+            if let Some(validator) = validator {
+                live.record(validator, &attestation);
+            }
+            store.insert(StoredAttestation::new(attestation, epoch, validator).unwrap()).unwrap();
+        }
+
+        let rebuilt = EpochAggregator::from_store(3, AggregationParams::default(), &store).unwrap();
+        assert_eq!(rebuilt.current_epoch(), 3);
+        assert_eq!(rebuilt.carry(), live.carry());
+        assert_eq!(rebuilt.summary(0), live.summary(0));
+        assert_eq!(rebuilt.summary(2), live.summary(2));
+
+        // The open epoch's credit survives too
+        let (mut rebuilt, mut live) = (rebuilt, live);
+        assert_eq!(rebuilt.finalize_epoch(3).unwrap(), live.finalize_epoch(3).unwrap());
+
+        // Records past the open epoch mean the store and registry disagree
+        assert!(EpochAggregator::from_store(2, AggregationParams::default(), &store).is_err());
+    }
+
+    #[test]
+    fn test_proposals_counted_per_epoch() {
+        let mut aggregator = EpochAggregator::new(0);
+        aggregator.record_proposal(A);
+        aggregator.record_proposal(A);
+        aggregator.record_proposal(B);
+        assert_eq!(aggregator.blocks_proposed(), &HashMap::from([(A, 2), (B, 1)]));
+
+        aggregator.finalize_epoch(0).unwrap();
+        assert!(aggregator.blocks_proposed().is_empty());
+    }
+
+    #[test]
+    fn test_close_applies_only_on_advance() {
+        let mut aggregator = EpochAggregator::new(0);
+        aggregator.record_impact(A, 8_000);
+        let closed = aggregator.close(0).unwrap();
+        assert_eq!(closed.carry, HashMap::from([(A, 8_000)]));
+
+        // Nothing changes until the closed epoch is applied
+        assert_eq!((aggregator.current_epoch(), aggregator.poi_carry(&A), aggregator.attestation_count(&A)), (0, 0, 1));
+        assert_eq!(aggregator.advance(closed.clone()).unwrap(), closed.summary);
+        assert_eq!((aggregator.current_epoch(), aggregator.poi_carry(&A)), (1, 8_000));
+        assert!(aggregator.advance(closed).is_err());
+    }
+
+    #[test]
+    fn test_finalize_wrong_epoch() {
+        let mut aggregator = EpochAggregator::new(3);
+        assert!(aggregator.finalize_epoch(2).is_err());
+        assert!(aggregator.finalize_epoch(3).is_ok());
+    }
+}
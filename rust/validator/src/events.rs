@@ -0,0 +1,112 @@
+//! Registry change notifications
+//!
+//! [`ValidatorRegistry::subscribe`](crate::ValidatorRegistry::subscribe) hands
+//! out a tokio broadcast receiver of [`RegistryEvent`]s. Events are sent while
+//! the registry still holds its write locks, so every subscriber sees changes
+//! in the order they were applied. Within one epoch transition the order is
+//! the one described in the registry docs (rotations and exits first,
+//! activations last), closed by [`RegistryEvent::EpochAdvanced`].
+//!
+//! Delivery is best effort: a subscriber that falls more than
+//! [`EVENT_CHANNEL_CAPACITY`] events behind gets `RecvError::Lagged` and should
+//! resynchronise from `list_active` / `get`.
+
+use serde::Serialize;
+
+use crate::reputation::ReputationReason;
+use crate::slashing::SlashEvent;
+use crate::types::{Ed25519PublicKey, ValidatorId};
+
+/// Events buffered per subscriber before it starts lagging
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Validator-set change
+///
+/// Serialize-only: the internally tagged form cannot carry `u128` back in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RegistryEvent {
+    /// New validator registered (Pending)
+    Registered { validator_id: ValidatorId, epoch: u64 },
+
+    /// Pending → Active
+    Activated { validator_id: ValidatorId, epoch: u64 },
+
+    /// Active → Pending to enforce the active set cap
+    Deactivated { validator_id: ValidatorId, epoch: u64 },
+
+    /// Active or Offline → Exiting; leaves at `exit_epoch`
+    ExitInitiated { validator_id: ValidatorId, exit_epoch: u64 },
+
+    /// Exiting → Exited
+    Exited { validator_id: ValidatorId, epoch: u64 },
+
+    /// Evidence applied (→ Slashed)
+    Slashed(SlashEvent),
+
+    /// Slashed → Exited (quarantine over)
+    Released { validator_id: ValidatorId, epoch: u64 },
+
+    /// Active → Offline
+    WentOffline { validator_id: ValidatorId },
+
+    /// Offline → Active
+    Revived { validator_id: ValidatorId },
+
+    /// Key rotation announced
+    KeyRotationScheduled { validator_id: ValidatorId, pk_ed25519: Ed25519PublicKey, activation_epoch: u64 },
+
+    /// Announced key took over
+    KeyRotated { validator_id: ValidatorId, pk_ed25519: Ed25519PublicKey },
+
+    /// `poi_weight` changed at an epoch boundary
+    WeightUpdated { validator_id: ValidatorId, poi_weight: u128 },
+
+    /// Reputation reward or penalty applied
+    ReputationUpdated { validator_id: ValidatorId, reason: ReputationReason, rep_score: u64 },
+
+    /// Registry moved to `to_epoch`
+    EpochAdvanced { from_epoch: u64, to_epoch: u64, active_count: usize },
+}
+
+impl RegistryEvent {
+    /// Validator the event is about (None for epoch events)
+    pub fn validator_id(&self) -> Option<&ValidatorId> {
+        match self {
+            RegistryEvent::Registered { validator_id, .. }
+            | RegistryEvent::Activated { validator_id, .. }
+            | RegistryEvent::Deactivated { validator_id, .. }
+            | RegistryEvent::ExitInitiated { validator_id, .. }
+            | RegistryEvent::Exited { validator_id, .. }
+            | RegistryEvent::Released { validator_id, .. }
+            | RegistryEvent::WentOffline { validator_id }
+            | RegistryEvent::Revived { validator_id }
+            | RegistryEvent::KeyRotationScheduled { validator_id, .. }
+            | RegistryEvent::KeyRotated { validator_id, .. }
+            | RegistryEvent::WeightUpdated { validator_id, .. }
+            | RegistryEvent::ReputationUpdated { validator_id, .. } => Some(validator_id),
+            RegistryEvent::Slashed(event) => Some(&event.validator_id),
+            RegistryEvent::EpochAdvanced { .. } => None,
+        }
+    }
+
+    /// Event name as used in the serialized `type` tag
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RegistryEvent::Registered { .. } => "registered",
+            RegistryEvent::Activated { .. } => "activated",
+            RegistryEvent::Deactivated { .. } => "deactivated",
+            RegistryEvent::ExitInitiated { .. } => "exit_initiated",
+            RegistryEvent::Exited { .. } => "exited",
+            RegistryEvent::Slashed(_) => "slashed",
+            RegistryEvent::Released { .. } => "released",
+            RegistryEvent::WentOffline { .. } => "went_offline",
+            RegistryEvent::Revived { .. } => "revived",
+            RegistryEvent::KeyRotationScheduled { .. } => "key_rotation_scheduled",
+            RegistryEvent::KeyRotated { .. } => "key_rotated",
+            RegistryEvent::WeightUpdated { .. } => "weight_updated",
+            RegistryEvent::ReputationUpdated { .. } => "reputation_updated",
+            RegistryEvent::EpochAdvanced { .. } => "epoch_advanced",
+        }
+    }
+}
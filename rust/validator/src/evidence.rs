@@ -0,0 +1,486 @@
+//! Evidence pack builder and verifier
+//!
+//! An evidence pack is a deterministic, tar-like archive of the files backing a
+//! PoI attestation. Its SHA-256 is the `pack_sha256` carried in
+//! [`Evidence`](crate::poi::Evidence), so validators can fetch a pack and
+//! spot-check what an attester claims.
+//!
+//! ## Pack Layout
+//!
+//! ```text
+//!  "BIZRA-EVP" 0x01                      magic + format version
+//!  u64 BE manifest length | manifest     RFC 8785 canonical JSON
+//!  u64 BE length | contents              one frame per non-redacted entry,
+//!  ...                                   in manifest order
+//! ```
+//!
+//! The manifest lists every file under the pack root sorted by its
+//! `/`-separated relative path, with size and SHA-256. Redacted files keep
+//! their manifest entry (the hash still commits to the hidden contents) but
+//! carry a redaction marker and no contents frame. No timestamps, permissions
+//! or owners are recorded, so the same tree always produces the same bytes.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::canonical::to_canonical_bytes;
+use crate::poi::Evidence;
+
+/// Pack magic: "BIZRA-EVP" followed by the format version byte
+pub const EVIDENCE_PACK_MAGIC: &[u8; 10] = b"BIZRA-EVP\x01";
+
+/// Manifest format identifier
+pub const EVIDENCE_MANIFEST_VERSION: &str = "evp-1.0";
+
+/// Evidence pack verification failures
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EvidenceError {
+    #[error("Malformed evidence pack: {0}")]
+    Malformed(String),
+
+    #[error("Evidence pack hash mismatch: attested {attested}, computed {computed}")]
+    PackHashMismatch { attested: String, computed: String },
+
+    #[error("Evidence pack {field} mismatch: attested {attested}, computed {computed}")]
+    CountMismatch {
+        field: &'static str,
+        attested: u64,
+        computed: u64,
+    },
+
+    #[error("Evidence file {path} does not match its manifest hash")]
+    FileHashMismatch { path: String },
+}
+
+/// One file in the pack manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Path relative to the pack root, `/`-separated
+    pub path: String,
+
+    /// Original file size in bytes
+    pub size: u64,
+
+    /// SHA-256 of the original file contents (64 hex chars)
+    pub sha256: String,
+
+    /// Redaction marker: contents withheld from the pack
+    pub redacted: bool,
+}
+
+/// Sorted pack manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackManifest {
+    /// Manifest format version (currently "evp-1.0")
+    pub version: String,
+
+    /// Entries sorted by path
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl PackManifest {
+    /// Number of files covered by the pack (redacted files included)
+    pub fn files_processed(&self) -> u64 {
+        self.entries.len() as u64
+    }
+
+    /// Number of redacted files
+    pub fn redactions_count(&self) -> u64 {
+        self.entries.iter().filter(|e| e.redacted).count() as u64
+    }
+}
+
+/// Built or parsed evidence pack
+#[derive(Debug, Clone)]
+pub struct EvidencePack {
+    manifest: PackManifest,
+    bytes: Vec<u8>,
+}
+
+impl EvidencePack {
+    /// Parse a pack and check every included file against its manifest hash
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, EvidenceError> {
+        let mut reader = FrameReader { bytes: &bytes, pos: 0 };
+
+        if reader.take(EVIDENCE_PACK_MAGIC.len())? != EVIDENCE_PACK_MAGIC {
+            return Err(EvidenceError::Malformed("bad magic".to_string()));
+        }
+
+        let manifest_bytes = reader.frame()?;
+        let manifest: PackManifest = serde_json::from_slice(manifest_bytes)
+            .map_err(|e| EvidenceError::Malformed(format!("manifest: {}", e)))?;
+        if manifest.version != EVIDENCE_MANIFEST_VERSION {
+            return Err(EvidenceError::Malformed(format!(
+                "unsupported manifest version {}",
+                manifest.version
+            )));
+        }
+
+        // The manifest must be in canonical form so the pack hash is unique
+        let canonical = to_canonical_bytes(&manifest)
+            .map_err(|e| EvidenceError::Malformed(format!("manifest: {}", e)))?;
+        if canonical != manifest_bytes {
+            return Err(EvidenceError::Malformed("manifest is not canonical".to_string()));
+        }
+        if !manifest.entries.windows(2).all(|w| w[0].path < w[1].path) {
+            return Err(EvidenceError::Malformed("manifest entries not sorted".to_string()));
+        }
+
+        for entry in manifest.entries.iter().filter(|e| !e.redacted) {
+            let contents = reader.frame()?;
+            if contents.len() as u64 != entry.size || sha256_hex(contents) != entry.sha256 {
+                return Err(EvidenceError::FileHashMismatch { path: entry.path.clone() });
+            }
+        }
+
+        if reader.pos != bytes.len() {
+            return Err(EvidenceError::Malformed("trailing bytes".to_string()));
+        }
+
+        Ok(Self { manifest, bytes })
+    }
+
+    /// Read and parse a pack from disk
+    pub fn read(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read evidence pack {}", path.display()))?;
+        Ok(Self::from_bytes(bytes)?)
+    }
+
+    /// Write the pack to disk
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, &self.bytes)
+            .with_context(|| format!("Failed to write evidence pack {}", path.display()))
+    }
+
+    pub fn manifest(&self) -> &PackManifest {
+        &self.manifest
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// SHA-256 of the whole pack (the attested `pack_sha256`)
+    pub fn sha256(&self) -> String {
+        sha256_hex(&self.bytes)
+    }
+
+    /// Attestation `Evidence` describing this pack
+    pub fn to_evidence(&self, methodology_ref: Option<String>) -> Evidence {
+        Evidence {
+            pack_sha256: self.sha256(),
+            pack_bytes: Some(self.bytes.len() as u64),
+            files_processed: Some(self.manifest.files_processed()),
+            redactions_count: Some(self.manifest.redactions_count()),
+            methodology_ref,
+        }
+    }
+
+    /// Check this pack against the `Evidence` claimed in an attestation
+    ///
+    /// Optional counters are only compared when the attestation carries them.
+    pub fn verify_against(&self, evidence: &Evidence) -> Result<(), EvidenceError> {
+        let computed = self.sha256();
+        if !evidence.pack_sha256.eq_ignore_ascii_case(&computed) {
+            return Err(EvidenceError::PackHashMismatch {
+                attested: evidence.pack_sha256.clone(),
+                computed,
+            });
+        }
+
+        let counters = [
+            ("pack_bytes", evidence.pack_bytes, self.bytes.len() as u64),
+            ("files_processed", evidence.files_processed, self.manifest.files_processed()),
+            ("redactions_count", evidence.redactions_count, self.manifest.redactions_count()),
+        ];
+        for (field, attested, computed) in counters {
+            if let Some(attested) = attested {
+                if attested != computed {
+                    return Err(EvidenceError::CountMismatch { field, attested, computed });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the non-redacted files into `dir`, recreating the pack layout
+    ///
+    /// Returns the number of files written.
+    pub fn extract_to(&self, dir: &Path) -> Result<usize> {
+        let mut reader = FrameReader { bytes: &self.bytes, pos: EVIDENCE_PACK_MAGIC.len() };
+        reader.frame()?; // manifest
+
+        let mut written = 0;
+        for entry in self.manifest.entries.iter().filter(|e| !e.redacted) {
+            let contents = reader.frame()?;
+
+            // Packs from other nodes must not escape `dir`
+            let rel = Path::new(&entry.path);
+            if !rel.components().all(|c| matches!(c, std::path::Component::Normal(_))) {
+                anyhow::bail!("Unsafe path in evidence pack: {}", entry.path);
+            }
+
+            let target = dir.join(rel);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, contents)
+                .with_context(|| format!("Failed to extract {}", target.display()))?;
+            written += 1;
+        }
+
+        Ok(written)
+    }
+}
+
+/// Read a pack from disk and verify it against an attestation's `Evidence`
+pub fn verify_pack_file(path: &Path, evidence: &Evidence) -> Result<PackManifest> {
+    let pack = EvidencePack::read(path)?;
+    pack.verify_against(evidence)?;
+    Ok(pack.manifest)
+}
+
+/// Deterministic evidence pack builder
+///
+/// ```no_run
+/// use validator::evidence::EvidencePackBuilder;
+///
+/// let pack = EvidencePackBuilder::new("./evidence")
+///     .redact("secrets/api_keys.txt")
+///     .build()?;
+/// let evidence = pack.to_evidence(Some("doi:10.5281/zenodo.0000000".to_string()));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct EvidencePackBuilder {
+    root: PathBuf,
+    redactions: BTreeSet<String>,
+}
+
+impl EvidencePackBuilder {
+    /// Pack every regular file under `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            redactions: BTreeSet::new(),
+        }
+    }
+
+    /// Withhold a file's contents (path relative to the root, `/`-separated);
+    /// a directory path redacts everything beneath it
+    pub fn redact(mut self, path: impl Into<String>) -> Self {
+        self.redactions.insert(path.into().trim_matches('/').to_string());
+        self
+    }
+
+    /// Walk the root and assemble the pack
+    pub fn build(&self) -> Result<EvidencePack> {
+        let mut files = Vec::new();
+        collect_files(&self.root, &self.root, &mut files)?;
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut entries = Vec::with_capacity(files.len());
+        let mut contents = Vec::new();
+        for (rel, path) in files {
+            let data = fs::read(&path)
+                .with_context(|| format!("Failed to read evidence file {}", path.display()))?;
+            let redacted = self.is_redacted(&rel);
+            entries.push(ManifestEntry {
+                path: rel,
+                size: data.len() as u64,
+                sha256: sha256_hex(&data),
+                redacted,
+            });
+            if !redacted {
+                contents.push(data);
+            }
+        }
+
+        let manifest = PackManifest {
+            version: EVIDENCE_MANIFEST_VERSION.to_string(),
+            entries,
+        };
+        let manifest_bytes = to_canonical_bytes(&manifest)?;
+
+        let mut bytes = EVIDENCE_PACK_MAGIC.to_vec();
+        write_frame(&mut bytes, &manifest_bytes);
+        for data in &contents {
+            write_frame(&mut bytes, data);
+        }
+
+        Ok(EvidencePack { manifest, bytes })
+    }
+
+    fn is_redacted(&self, rel: &str) -> bool {
+        self.redactions.iter().any(|r| {
+            rel == r || (rel.starts_with(r.as_str()) && rel.as_bytes().get(r.len()) == Some(&b'/'))
+        })
+    }
+}
+
+/// Recursively collect regular files as (relative `/` path, absolute path)
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<(String, PathBuf)>) -> Result<()> {
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read evidence directory {}", dir.display()))?;
+
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            anyhow::bail!("Symlinks are not allowed in evidence packs: {}", path.display());
+        } else if file_type.is_dir() {
+            collect_files(root, &path, out)?;
+        } else if file_type.is_file() {
+            let rel = path.strip_prefix(root)?;
+            let parts: Vec<&str> = rel
+                .components()
+                .map(|c| {
+                    c.as_os_str()
+                        .to_str()
+                        .with_context(|| format!("Non UTF-8 evidence path {}", path.display()))
+                })
+                .collect::<Result<_>>()?;
+            out.push((parts.join("/"), path));
+        }
+    }
+
+    Ok(())
+}
+
+fn write_frame(out: &mut Vec<u8>, data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u64).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+struct FrameReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> FrameReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], EvidenceError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| EvidenceError::Malformed("truncated pack".to_string()))?;
+        let out = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn frame(&mut self) -> Result<&'a [u8], EvidenceError> {
+        let len_bytes: [u8; 8] = self.take(8)?.try_into().expect("8-byte slice");
+        let len = usize::try_from(u64::from_be_bytes(len_bytes))
+            .map_err(|_| EvidenceError::Malformed("frame too large".to_string()))?;
+        self.take(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evidence_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("data/private")).unwrap();
+        fs::write(dir.path().join("report.md"), b"# Impact report\n").unwrap();
+        fs::write(dir.path().join("data/results.csv"), b"metric,value\nquality,0.92\n").unwrap();
+        fs::write(dir.path().join("data/private/keys.txt"), b"secret").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_build_is_deterministic_and_sorted() {
+        let dir = evidence_dir();
+        let a = EvidencePackBuilder::new(dir.path()).build().unwrap();
+        let b = EvidencePackBuilder::new(dir.path()).build().unwrap();
+        assert_eq!(a.as_bytes(), b.as_bytes());
+
+        let paths: Vec<_> = a.manifest().entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["data/private/keys.txt", "data/results.csv", "report.md"]);
+        assert_eq!(a.manifest().entries[2].sha256, sha256_hex(b"# Impact report\n"));
+    }
+
+    #[test]
+    fn test_redaction_withholds_contents() {
+        let dir = evidence_dir();
+        let pack = EvidencePackBuilder::new(dir.path()).redact("data/private").build().unwrap();
+
+        let entry = &pack.manifest().entries[0];
+        assert!(entry.redacted);
+        assert_eq!(entry.sha256, sha256_hex(b"secret"));
+        assert!(!pack.as_bytes().windows(6).any(|w| w == b"secret"));
+
+        let evidence = pack.to_evidence(None);
+        assert_eq!(evidence.files_processed, Some(3));
+        assert_eq!(evidence.redactions_count, Some(1));
+        assert_eq!(evidence.pack_bytes, Some(pack.as_bytes().len() as u64));
+    }
+
+    #[test]
+    fn test_verify_pack_file() {
+        let dir = evidence_dir();
+        let pack = EvidencePackBuilder::new(dir.path()).redact("data/private/keys.txt").build().unwrap();
+        let evidence = pack.to_evidence(None);
+
+        let out = tempfile::tempdir().unwrap();
+        let pack_path = out.path().join("pack.evp");
+        pack.write(&pack_path).unwrap();
+
+        let manifest = verify_pack_file(&pack_path, &evidence).unwrap();
+        assert_eq!(&manifest, pack.manifest());
+
+        let mut wrong_count = evidence.clone();
+        wrong_count.files_processed = Some(10);
+        let err = pack.verify_against(&wrong_count).unwrap_err();
+        assert!(matches!(err, EvidenceError::CountMismatch { field: "files_processed", .. }));
+
+        let mut wrong_hash = evidence.clone();
+        wrong_hash.pack_sha256 = "00".repeat(32);
+        assert!(matches!(pack.verify_against(&wrong_hash), Err(EvidenceError::PackHashMismatch { .. })));
+    }
+
+    #[test]
+    fn test_extract_skips_redacted() {
+        let dir = evidence_dir();
+        let pack = EvidencePackBuilder::new(dir.path()).redact("data/private").build().unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        assert_eq!(pack.extract_to(out.path()).unwrap(), 2);
+        assert_eq!(fs::read(out.path().join("data/results.csv")).unwrap(), b"metric,value\nquality,0.92\n");
+        assert!(!out.path().join("data/private/keys.txt").exists());
+    }
+
+    #[test]
+    fn test_tampered_pack_rejected() {
+        let dir = evidence_dir();
+        let pack = EvidencePackBuilder::new(dir.path()).build().unwrap();
+
+        let mut bytes = pack.as_bytes().to_vec();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+        assert_eq!(
+            EvidencePack::from_bytes(bytes).unwrap_err(),
+            EvidenceError::FileHashMismatch { path: "report.md".to_string() }
+        );
+
+        let mut truncated = pack.as_bytes().to_vec();
+        truncated.truncate(truncated.len() - 4);
+        assert!(matches!(EvidencePack::from_bytes(truncated), Err(EvidenceError::Malformed(_))));
+    }
+}
@@ -26,20 +26,39 @@
 //! Validators progress through defined states:
 //! - **Pending**: Join request submitted, awaiting activation
 //! - **Active**: Participating in consensus, producing WQ-refs
+//! - **Offline**: Active validator past the offline threshold, weight not counted
 //! - **Exiting**: Voluntary exit initiated, unbonding period
 //! - **Exited**: No longer active, bond returned
 //! - **Slashed**: Penalized for misbehavior, quarantined
 
+pub mod benchmark;
+pub mod bond;
+pub mod canonical;
+pub mod chain_spec;
+pub mod consensus_message;
+pub mod epoch;
+pub mod events;
+pub mod evidence;
+pub mod liveness;
 pub mod types;
 pub mod registry;
+pub mod registry_store;
 pub mod reputation;
+pub mod rewards;
 pub mod poi;
+pub mod replay;
+pub mod schema;
 pub mod slashing;
+pub mod store;
+pub mod tx;
 
 pub use types::{ValidatorRecord, ValidatorStatus, ValidatorId};
+pub use chain_spec::ChainSpec;
 pub use registry::ValidatorRegistry;
+pub use events::RegistryEvent;
 pub use reputation::ReputationTracker;
-pub use poi::{PoIAttestation, PoIWeightCalculator};
+pub use poi::{PoIAttestation, PoIWeightCalculator, WeightParams};
+pub use replay::ReplayGuard;
 
 /// Current version of the validator protocol
 pub const VALIDATOR_PROTOCOL_VERSION: u8 = 1;
@@ -62,6 +81,12 @@ pub const SLOT_DURATION_MS: u64 = 2000;
 /// Unbonding delay in epochs (spec: 2 epochs = ~2 minutes)
 pub const UNBONDING_DELAY_EPOCHS: u64 = 2;
 
+/// Key rotation activation delay in epochs
+pub const KEY_ROTATION_DELAY_EPOCHS: u64 = 2;
+
+/// Epochs a rotated-out key is kept as key-theft evidence after it retires
+pub const KEY_HISTORY_EPOCHS: u64 = 64;
+
 /// Liveness miss threshold in slots (spec: 64 slots = ~2 minutes)
 pub const LIVENESS_MISS_THRESHOLD: u64 = 64;
 
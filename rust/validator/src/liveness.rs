@@ -0,0 +1,264 @@
+//! Slot-driven liveness tracking
+//!
+//! Consensus and gossip report "validator X signed at slot S"; the registry
+//! feeds those into [`ValidatorRecord::mark_seen`] and periodically checks every
+//! serving validator against the current slot:
+//!
+//! ```text
+//!  missed ≤ miss_threshold                    healthy
+//!  missed > miss_threshold                    at risk: one penalty per missed window,
+//!                                             window k costs min(k × BASE, MAX) reputation
+//!  missed > offline_threshold (Active only)   Active → Offline (weight stops counting)
+//!  seen again                                 Offline → Active, penalty windows reset
+//! ```
+//!
+//! A validator never seen since the tracker first checked it is measured from
+//! that first check, so silent validators are caught too.
+//!
+//! Spec reference: BIZRA_Validator_Set_and_PoI_Weighting_Spec_v1.0.md section 6
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::chain_spec::ChainSpec;
+use crate::types::{ValidatorId, ValidatorRecord, ValidatorStatus};
+
+/// Reputation penalty for the first missed liveness window
+pub const LIVENESS_PENALTY_BASE: u64 = 100;
+
+/// Maximum reputation penalty for a single missed window
+pub const LIVENESS_PENALTY_MAX: u64 = 1_000;
+
+/// Per-validator tracking state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LivenessState {
+    /// Slot from which misses are counted when the validator was never seen
+    pub since_slot: u64,
+
+    /// Missed windows already penalized since the validator was last seen
+    pub penalized_windows: u64,
+
+    /// Windows newly missed in the current epoch
+    #[serde(default)]
+    pub epoch_misses: u64,
+}
+
+/// Outcome of checking one validator
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LivenessCheck {
+    pub missed_slots: u64,
+
+    /// Reputation penalty newly due
+    pub penalty: u64,
+
+    /// Past the offline threshold
+    pub offline: bool,
+}
+
+/// Liveness sweep over the registry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LivenessReport {
+    pub slot: u64,
+
+    /// Validators penalized in this sweep, with the reputation deducted
+    pub penalized: Vec<(ValidatorId, u64)>,
+
+    /// Validators moved Active → Offline
+    pub went_offline: Vec<ValidatorId>,
+}
+
+/// Validator that has missed liveness but is not offline yet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtRiskValidator {
+    pub validator_id: ValidatorId,
+    pub status: ValidatorStatus,
+    pub last_seen_slot: u64,
+    pub missed_slots: u64,
+
+    /// Slots left before the validator is moved offline
+    pub slots_until_offline: u64,
+}
+
+/// Missed-window bookkeeping for graduated liveness penalties
+#[derive(Debug, Clone)]
+pub struct LivenessTracker {
+    miss_threshold: u64,
+    offline_threshold: u64,
+    state: HashMap<ValidatorId, LivenessState>,
+}
+
+impl LivenessTracker {
+    /// Create tracker with explicit thresholds (in slots)
+    pub fn new(miss_threshold: u64, offline_threshold: u64) -> Self {
+        Self {
+            miss_threshold: miss_threshold.max(1),
+            offline_threshold,
+            state: HashMap::new(),
+        }
+    }
+
+    /// Create tracker with the chain's thresholds
+    pub fn from_chain_spec(spec: &ChainSpec) -> Self {
+        Self::new(spec.thresholds.liveness_miss_threshold, spec.thresholds.offline_threshold)
+    }
+
+    /// Reset penalty windows after the validator was seen
+    pub fn record_seen(&mut self, validator_id: &ValidatorId) {
+        if let Some(state) = self.state.get_mut(validator_id) {
+            state.penalized_windows = 0;
+        }
+    }
+
+    /// Tracking state of every validator, ordered by id (for snapshots)
+    pub fn states(&self) -> Vec<(ValidatorId, LivenessState)> {
+        let mut states: Vec<_> = self.state.iter().map(|(id, state)| (*id, *state)).collect();
+        states.sort_unstable_by_key(|(id, _)| *id);
+        states
+    }
+
+    /// Replace the tracking state with one taken by [`Self::states`]
+    pub fn restore(&mut self, states: impl IntoIterator<Item = (ValidatorId, LivenessState)>) {
+        self.state = states.into_iter().collect();
+    }
+
+    /// Windows the validator newly missed since the epoch started
+    pub fn epoch_misses(&self, validator_id: &ValidatorId) -> u64 {
+        self.state.get(validator_id).map_or(0, |state| state.epoch_misses)
+    }
+
+    /// Reset the per-epoch miss counters
+    pub fn start_epoch(&mut self) {
+        for state in self.state.values_mut() {
+            state.epoch_misses = 0;
+        }
+    }
+
+    /// Stop tracking a validator (exited, slashed)
+    pub fn forget(&mut self, validator_id: &ValidatorId) {
+        self.state.remove(validator_id);
+    }
+
+    /// Penalty for the `k`-th consecutive missed window (1-based)
+    pub fn window_penalty(k: u64) -> u64 {
+        LIVENESS_PENALTY_BASE.saturating_mul(k).min(LIVENESS_PENALTY_MAX)
+    }
+
+    /// Check a validator at `current_slot` and consume any newly due penalty windows
+    pub fn check(&mut self, validator: &ValidatorRecord, current_slot: u64) -> LivenessCheck {
+        let state = self.state.entry(validator.validator_id).or_insert(LivenessState {
+            since_slot: current_slot,
+            penalized_windows: 0,
+            epoch_misses: 0,
+        });
+        let missed_slots = current_slot.saturating_sub(last_alive_slot(validator, state.since_slot));
+
+        let windows = missed_windows(missed_slots, self.miss_threshold);
+        let penalty = (state.penalized_windows + 1..=windows)
+            .map(Self::window_penalty)
+            .fold(0u64, u64::saturating_add);
+        state.epoch_misses += windows.saturating_sub(state.penalized_windows);
+        state.penalized_windows = state.penalized_windows.max(windows);
+
+        LivenessCheck {
+            missed_slots,
+            penalty,
+            offline: missed_slots > self.offline_threshold,
+        }
+    }
+
+    /// Read-only at-risk check (None when healthy or already past the offline threshold)
+    pub fn at_risk(&self, validator: &ValidatorRecord, current_slot: u64) -> Option<AtRiskValidator> {
+        let since_slot = self.state.get(&validator.validator_id)?.since_slot;
+        let missed_slots = current_slot.saturating_sub(last_alive_slot(validator, since_slot));
+        if missed_slots <= self.miss_threshold || missed_slots > self.offline_threshold {
+            return None;
+        }
+
+        Some(AtRiskValidator {
+            validator_id: validator.validator_id,
+            status: validator.status,
+            last_seen_slot: validator.last_seen_slot,
+            missed_slots,
+            slots_until_offline: self.offline_threshold + 1 - missed_slots,
+        })
+    }
+}
+
+/// Last slot the validator is known to have been alive (first check if never seen)
+fn last_alive_slot(validator: &ValidatorRecord, since_slot: u64) -> u64 {
+    if validator.last_seen_slot == 0 {
+        since_slot
+    } else {
+        validator.last_seen_slot
+    }
+}
+
+/// Number of whole or partial miss windows past the threshold
+fn missed_windows(missed_slots: u64, miss_threshold: u64) -> u64 {
+    if missed_slots <= miss_threshold {
+        0
+    } else {
+        (missed_slots - miss_threshold - 1) / miss_threshold + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seen_at(slot: u64) -> ValidatorRecord {
+        let mut validator = ValidatorRecord::new_pending([1u8; 32], [1u8; 32], String::new(), 0);
+        validator.activate(0).unwrap();
+        validator.mark_seen(slot);
+        validator
+    }
+
+    #[test]
+    fn test_graduated_penalties() {
+        let mut tracker = LivenessTracker::new(64, 1024);
+        let validator = seen_at(100);
+
+        assert_eq!(tracker.check(&validator, 164).penalty, 0);
+
+        // First window: 100
+        let check = tracker.check(&validator, 165);
+        assert_eq!((check.missed_slots, check.penalty, check.offline), (65, 100, false));
+        assert_eq!(tracker.check(&validator, 200).penalty, 0); // Same window, no double charge
+
+        // Jump three windows at once: 200 + 300 + 400
+        assert_eq!(tracker.check(&validator, 100 + 4 * 64 + 1).penalty, 900);
+        assert_eq!(tracker.epoch_misses(&validator.validator_id), 4);
+        tracker.start_epoch();
+        assert_eq!(tracker.epoch_misses(&validator.validator_id), 0);
+
+        // Capped per window
+        assert_eq!(LivenessTracker::window_penalty(50), LIVENESS_PENALTY_MAX);
+
+        assert!(tracker.check(&validator, 100 + 1025).offline);
+    }
+
+    #[test]
+    fn test_seen_resets_windows() {
+        let mut tracker = LivenessTracker::new(64, 1024);
+        let mut validator = seen_at(100);
+        assert_eq!(tracker.check(&validator, 165 + 64).penalty, 300);
+
+        validator.mark_seen(300);
+        tracker.record_seen(&validator.validator_id);
+        assert_eq!(tracker.check(&validator, 365).penalty, 100);
+    }
+
+    #[test]
+    fn test_never_seen_counted_from_first_check() {
+        let mut tracker = LivenessTracker::new(64, 1024);
+        let validator = seen_at(0);
+
+        assert_eq!(tracker.check(&validator, 5_000).missed_slots, 0);
+        assert!(tracker.at_risk(&validator, 5_064).is_none());
+
+        let at_risk = tracker.at_risk(&validator, 5_065).unwrap();
+        assert_eq!((at_risk.missed_slots, at_risk.slots_until_offline), (65, 960));
+        assert!(tracker.at_risk(&validator, 5_000 + 1025).is_none());
+    }
+}
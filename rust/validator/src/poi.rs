@@ -10,7 +10,7 @@
 //!  PoIAttestation (signed)
 //!       |
 //!       +-- Anchor (chain_id, genesis_merkle_root, block_ref)
-//!       +-- Attester (id, pubkey_ed25519)
+//!       +-- Attester (id, pubkey_ed25519, pubkey_mldsa65?)
 //!       +-- Resources (cpu, ram, gpu, wallclock)
 //!       +-- Evidence (pack_sha256, files_processed, methodology)
 //!       +-- Measurement (dimensions, weights, impact_score)
@@ -22,6 +22,12 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use blake3::hash as blake3_hash;
 use anyhow::{Result, bail};
+use chrono::{DateTime, Duration, Utc};
+use crate::canonical::canonicalize;
+use crate::chain_spec::ChainSpec;
+use crate::schema::MeasurementSchemaRegistry;
+use ed25519_dalek::{Signer, SigningKey};
+use poi::hybrid::{HybridKeypair, ED25519_PUBLIC_KEY_LEN, ED25519_SIGNATURE_LEN, HYBRID_SIGNATURE_LEN, MLDSA65_PUBLIC_KEY_LEN};
 
 /// PoI attestation version (spec: "poi-1.0")
 pub const POI_VERSION: &str = "poi-1.0";
@@ -29,9 +35,37 @@ pub const POI_VERSION: &str = "poi-1.0";
 /// Maximum time window duration in seconds (spec: 30 days)
 pub const MAX_TIME_WINDOW_DURATION_SECS: u64 = 30 * 24 * 60 * 60;
 
+/// Maximum clock skew tolerated for a time window ending in the future (5 minutes)
+pub const MAX_FUTURE_SKEW_SECS: i64 = 5 * 60;
+
 /// Validation epsilon for floating-point comparisons (spec: 1e-6)
 pub const VALIDATION_EPSILON: f64 = 1e-6;
 
+/// Classical signature algorithm identifier
+pub const SIG_ALG_ED25519: &str = "ed25519";
+
+/// Hybrid post-quantum signature algorithm identifier (Ed25519 + ML-DSA-65)
+pub const SIG_ALG_HYBRID: &str = poi::hybrid::HYBRID_ALG;
+
+/// Attestation signature verification failures
+///
+/// Returned (wrapped in `anyhow::Error`) by rule 10 of [`PoIAttestation::validate`];
+/// callers can `downcast_ref::<SignatureError>()` to tell the cases apart.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SignatureError {
+    /// Attester public key is not valid "<codec>:<hex>" of the right length
+    #[error("Invalid {codec} public key encoding: {reason}")]
+    InvalidKeyEncoding { codec: &'static str, reason: String },
+
+    /// `sig_base16` is not valid hex of the right length
+    #[error("Invalid signature encoding: {0}")]
+    InvalidSignatureEncoding(String),
+
+    /// Signature does not verify against the canonical payload
+    #[error("Signature verification failed for algorithm {0}")]
+    SignatureMismatch(String),
+}
+
 /// Anchor binding attestation to chain context
 ///
 /// Spec reference: BIZRA_PoI_Cryptographic_Attestation_Spec_v1.0.md section 3.2
@@ -58,6 +92,12 @@ pub struct Attester {
 
     /// Ed25519 public key (multicodec format: "ed25519:<hex>")
     pub pubkey_ed25519: String,
+
+    /// ML-DSA-65 public key (multicodec format: "mldsa65:<hex>")
+    ///
+    /// Required when the signature algorithm is "ed25519+mldsa65"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkey_mldsa65: Option<String>,
 }
 
 /// Resource contribution metrics
@@ -112,8 +152,12 @@ pub struct Evidence {
 /// Spec reference: BIZRA_PoI_Cryptographic_Attestation_Spec_v1.0.md section 3.2
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Measurement {
-    /// Dimension scores (0.0 - 1.0)
-    /// Common dimensions: quality, utility, efficiency, trust, fairness, diversity
+    /// Measurement schema identifier (defaults to the attestation version)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Dimension scores, as registered by the measurement schema
+    /// ("poi-1.0": quality, utility, efficiency, trust, fairness, diversity)
     pub dimensions: BTreeMap<String, f64>,
 
     /// Weights for dimensions (must sum to ~1.0)
@@ -143,10 +187,13 @@ pub struct Benchmarks {
 /// Spec reference: BIZRA_PoI_Cryptographic_Attestation_Spec_v1.0.md section 4
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Signature {
-    /// Signature algorithm (currently "ed25519")
+    /// Signature algorithm ("ed25519" or "ed25519+mldsa65")
     pub alg: String,
 
     /// Signature bytes (hex encoded)
+    ///
+    /// For "ed25519+mldsa65" this is the Ed25519 signature followed by the
+    /// ML-DSA-65 signature; both must verify.
     pub sig_base16: String,
 }
 
@@ -162,10 +209,10 @@ pub struct Signature {
 /// PoIAttestation {
 ///   version: "poi-1.0",
 ///   anchor: { chain_id, genesis_merkle_root, block_ref? },
-///   attester: { id, pubkey_ed25519 },
+///   attester: { id, pubkey_ed25519, pubkey_mldsa65? },
 ///   resources?: { cpu_cores, ram_gb, gpu_vram_gb, wallclock_sec },
 ///   evidence: { pack_sha256, pack_bytes?, files_processed?, ... },
-///   measurement: { dimensions, weights, impact_score },
+///   measurement: { schema?, dimensions, weights, impact_score },
 ///   benchmarks?: { pre, post, delta },
 ///   time_window: [start, end],
 ///   nonce: "hex",
@@ -203,25 +250,23 @@ pub struct PoIAttestation {
     /// Replay protection nonce (16+ bytes hex)
     pub nonce: String,
 
-    /// Ed25519 signature
+    /// Ed25519 or hybrid Ed25519 + ML-DSA-65 signature
     pub signature: Signature,
 }
 
 impl PoIAttestation {
     /// Create canonical payload for hashing/signing
     ///
-    /// Returns JSON without signature field, sorted keys, no whitespace
+    /// Returns the RFC 8785 (JCS) canonical JSON of the attestation with the
+    /// `signature` member omitted: members sorted by UTF-16 code units, no
+    /// whitespace, ECMAScript number formatting.
     pub fn canonical_payload(&self) -> Result<Vec<u8>> {
-        // Clone without signature
-        let mut attestation_for_signing = self.clone();
-        attestation_for_signing.signature = Signature {
-            alg: "ed25519".to_string(),
-            sig_base16: String::new(),
-        };
+        let mut value = serde_json::to_value(self)?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("signature");
+        }
 
-        // Serialize to JSON with sorted keys
-        let json = serde_json::to_string(&attestation_for_signing)?;
-        Ok(json.into_bytes())
+        Ok(canonicalize(&value)?.into_bytes())
     }
 
     /// Compute Blake3 digest of canonical payload
@@ -231,21 +276,52 @@ impl PoIAttestation {
         Ok(hash.to_hex().to_string())
     }
 
-    /// Validate attestation structure and semantics
+    /// Parse `time_window` as RFC3339 UTC timestamps
+    pub fn time_window_bounds(&self) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let parse = |ts: &str| -> Result<DateTime<Utc>> {
+            DateTime::parse_from_rfc3339(ts)
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|e| anyhow::anyhow!("Invalid RFC3339 timestamp '{}': {}", ts, e))
+        };
+
+        Ok((parse(&self.time_window[0])?, parse(&self.time_window[1])?))
+    }
+
+    /// Validate attestation structure and semantics against the current time
     ///
     /// Implements 10 validation rules from spec section 5
-    pub fn validate(&self, current_chain_id: &str, current_genesis_root: &str) -> Result<()> {
+    pub fn validate(&self, spec: &ChainSpec) -> Result<()> {
+        self.validate_at(spec, Utc::now())
+    }
+
+    /// Measurement schema this attestation declares
+    pub fn schema_id(&self) -> &str {
+        self.measurement.schema.as_deref().unwrap_or(&self.version)
+    }
+
+    /// Validate attestation structure and semantics as of `now`
+    pub fn validate_at(&self, spec: &ChainSpec, now: DateTime<Utc>) -> Result<()> {
+        self.validate_with_schemas(spec, now, &MeasurementSchemaRegistry::default())
+    }
+
+    /// Validate as of `now` against a caller-supplied schema registry
+    pub fn validate_with_schemas(
+        &self,
+        spec: &ChainSpec,
+        now: DateTime<Utc>,
+        schemas: &MeasurementSchemaRegistry,
+    ) -> Result<()> {
         // 1. Version check
         if self.version != POI_VERSION {
             bail!("Invalid version: expected {}, got {}", POI_VERSION, self.version);
         }
 
         // 2. Anchor validation
-        if self.anchor.chain_id != current_chain_id {
+        if self.anchor.chain_id != spec.chain_id {
             bail!("Chain ID mismatch: expected {}, got {}",
-                current_chain_id, self.anchor.chain_id);
+                spec.chain_id, self.anchor.chain_id);
         }
-        if self.anchor.genesis_merkle_root != current_genesis_root {
+        if self.anchor.genesis_merkle_root != spec.genesis_merkle_root {
             bail!("Genesis root mismatch");
         }
 
@@ -260,16 +336,28 @@ impl PoIAttestation {
             bail!("Invalid nonce: must be at least 16 bytes (32 hex chars), got {}",
                 self.nonce.len());
         }
+        if !self.nonce.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("Invalid nonce: must be hex encoded");
+        }
 
         // 5. Time window validation
-        // (Would parse RFC3339 dates in production - simplified for Day 11)
-
-        // 6. Dimensions range check (BEFORE score calculation)
-        for (dim, val) in &self.measurement.dimensions {
-            if *val < 0.0 || *val > 1.0 {
-                bail!("Dimension '{}' out of range [0,1]: {:.6}", dim, val);
-            }
+        let (start, end) = self.time_window_bounds()?;
+        if start >= end {
+            bail!("Invalid time window: start {} is not before end {}",
+                self.time_window[0], self.time_window[1]);
+        }
+        let duration_secs = (end - start).num_seconds() as u64;
+        if duration_secs > MAX_TIME_WINDOW_DURATION_SECS {
+            bail!("Time window too long: {}s exceeds maximum {}s",
+                duration_secs, MAX_TIME_WINDOW_DURATION_SECS);
         }
+        if end > now + Duration::seconds(MAX_FUTURE_SKEW_SECS) {
+            bail!("Time window ends in the future: {}", self.time_window[1]);
+        }
+
+        // 6. Measurement schema check: registered dimensions, bounds and
+        //    weight ranges (BEFORE score calculation)
+        schemas.check(self.schema_id(), &self.measurement)?;
 
         // 7. Weights sum check
         let weights_sum: f64 = self.measurement.weights.values().sum();
@@ -307,44 +395,219 @@ impl PoIAttestation {
             }
         }
 
-        // 10. Signature algorithm check
-        if self.signature.alg != "ed25519" {
-            bail!("Unsupported signature algorithm: {}", self.signature.alg);
+        // 10. Signature verification over the canonical payload
+        self.verify_signature()
+    }
+
+    /// Verify the signature over the canonical payload
+    pub fn verify_signature(&self) -> Result<()> {
+        match self.signature.alg.as_str() {
+            SIG_ALG_ED25519 => self.verify_ed25519_signature(),
+            SIG_ALG_HYBRID => self.verify_hybrid_signature(),
+            other => bail!("Unsupported signature algorithm: {}", other),
+        }
+    }
+
+    /// Sign with an Ed25519 key
+    ///
+    /// Binds `attester.pubkey_ed25519` to the key, then signs the canonical payload.
+    pub fn sign(&mut self, key: &SigningKey) -> Result<()> {
+        self.attester.pubkey_ed25519 = format!("ed25519:{}", hex::encode(key.verifying_key().to_bytes()));
+        self.attester.pubkey_mldsa65 = None;
+        self.signature.alg = SIG_ALG_ED25519.to_string();
+
+        let payload = self.canonical_payload()?;
+        self.signature.sig_base16 = hex::encode(key.sign(&payload).to_bytes());
+        Ok(())
+    }
+
+    /// Sign with a hybrid Ed25519 + ML-DSA-65 keypair
+    ///
+    /// Binds the attester's public keys to the keypair, then signs the
+    /// canonical payload with both components.
+    pub fn sign_hybrid(&mut self, keypair: &HybridKeypair) -> Result<()> {
+        self.attester.pubkey_ed25519 = format!("ed25519:{}", hex::encode(keypair.ed25519_public_key()));
+        self.attester.pubkey_mldsa65 = Some(format!("mldsa65:{}", hex::encode(keypair.mldsa_public_key())));
+        self.signature.alg = SIG_ALG_HYBRID.to_string();
+
+        let payload = self.canonical_payload()?;
+        self.signature.sig_base16 = hex::encode(keypair.sign(&payload));
+        Ok(())
+    }
+
+    /// Attester Ed25519 public key bytes
+    pub fn attester_ed25519_key(&self) -> Result<[u8; 32]> {
+        let pk = decode_multicodec_key(&self.attester.pubkey_ed25519, "ed25519", ED25519_PUBLIC_KEY_LEN)?;
+        let mut key = [0u8; ED25519_PUBLIC_KEY_LEN];
+        key.copy_from_slice(&pk);
+        Ok(key)
+    }
+
+    /// Verify an Ed25519 signature against the canonical payload
+    fn verify_ed25519_signature(&self) -> Result<()> {
+        let pk = decode_multicodec_key(&self.attester.pubkey_ed25519, "ed25519", ED25519_PUBLIC_KEY_LEN)?;
+        let sig = decode_signature(&self.signature.sig_base16, ED25519_SIGNATURE_LEN)?;
+
+        let payload = self.canonical_payload()?;
+        if !poi::verify_attestation(&payload, &pk, &sig) {
+            return Err(SignatureError::SignatureMismatch(SIG_ALG_ED25519.to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    /// Verify both components of a hybrid signature against the canonical payload
+    fn verify_hybrid_signature(&self) -> Result<()> {
+        let ed_pk = decode_multicodec_key(&self.attester.pubkey_ed25519, "ed25519", ED25519_PUBLIC_KEY_LEN)?;
+        let ml_pk = match &self.attester.pubkey_mldsa65 {
+            Some(key) => decode_multicodec_key(key, "mldsa65", MLDSA65_PUBLIC_KEY_LEN)?,
+            None => {
+                return Err(SignatureError::InvalidKeyEncoding {
+                    codec: "mldsa65",
+                    reason: "hybrid signature requires attester.pubkey_mldsa65".to_string(),
+                }
+                .into())
+            }
+        };
+
+        let mut hybrid_pk = ed_pk;
+        hybrid_pk.extend_from_slice(&ml_pk);
+
+        let sig = decode_signature(&self.signature.sig_base16, HYBRID_SIGNATURE_LEN)?;
+
+        let payload = self.canonical_payload()?;
+        if !poi::hybrid::verify_hybrid_attestation(&payload, &hybrid_pk, &sig) {
+            return Err(SignatureError::SignatureMismatch(SIG_ALG_HYBRID.to_string()).into());
         }
 
         Ok(())
     }
 }
 
+/// Decode a multicodec-style "<codec>:<hex>" public key of a fixed length
+fn decode_multicodec_key(encoded: &str, codec: &'static str, len: usize) -> Result<Vec<u8>, SignatureError> {
+    let invalid = |reason: String| SignatureError::InvalidKeyEncoding { codec, reason };
+
+    let key_hex = encoded
+        .strip_prefix(codec)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or_else(|| invalid(format!("expected \"{}:<hex>\"", codec)))?;
+
+    let key = hex::decode(key_hex).map_err(|e| invalid(e.to_string()))?;
+    if key.len() != len {
+        return Err(invalid(format!("expected {} bytes, got {}", len, key.len())));
+    }
+
+    Ok(key)
+}
+
+/// Decode a hex signature of a fixed length
+fn decode_signature(sig_base16: &str, len: usize) -> Result<Vec<u8>, SignatureError> {
+    let sig = hex::decode(sig_base16).map_err(|e| SignatureError::InvalidSignatureEncoding(e.to_string()))?;
+    if sig.len() != len {
+        return Err(SignatureError::InvalidSignatureEncoding(format!(
+            "expected {} bytes, got {}",
+            len,
+            sig.len()
+        )));
+    }
+
+    Ok(sig)
+}
+
+/// Fixed-point scale for weight parameters and PoI scores (basis points)
+pub const BPS_SCALE: u128 = 10_000;
+
+/// Convert a PoI score to basis points (round half away from zero, negatives clamp to 0)
+///
+/// IEEE 754 multiplication and `f64::round` are exactly specified, so this is
+/// the only float step and it is identical on every platform.
+pub fn score_to_bps(score: f64) -> u128 {
+    if !score.is_finite() || score <= 0.0 {
+        return 0;
+    }
+    (score * BPS_SCALE as f64).round() as u128
+}
+
+/// Integer square root: largest `r` with `r * r <= n`
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton iteration from an initial guess above the root
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Weight formula parameters (chain parameters)
+///
+/// Multipliers are in basis points (1.0 = 10,000 bps).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeightParams {
+    /// Base weight (spec: 100)
+    pub base_weight: u128,
+
+    /// PoI multiplier λ in bps (spec: λ = 10 → 100,000)
+    pub poi_lambda_bps: u128,
+
+    /// Reputation multiplier μ in bps (spec: μ = 0.05 → 500)
+    pub rep_mu_bps: u128,
+
+    /// Stake multiplier ν in bps (spec: ν = 0.02 → 200)
+    pub stake_nu_bps: u128,
+}
+
+impl Default for WeightParams {
+    fn default() -> Self {
+        Self {
+            base_weight: 100,
+            poi_lambda_bps: 100_000,
+            rep_mu_bps: 500,
+            stake_nu_bps: 200,
+        }
+    }
+}
+
 /// PoI weight calculator
 ///
 /// Implements weight formula from spec section 5.8:
 /// ```text
 /// weight_eff = BASE + λ * PoI_carry + μ * rep_score + ν * sqrt(stake_bond)
 /// ```
+///
+/// Evaluated in basis-point integer arithmetic so every platform computes the
+/// same consensus weight. Relative to the `f64` reference the result is never
+/// higher and at most 1 lower (each of the three terms truncates by less than
+/// one bps before the final floor).
 pub struct PoIWeightCalculator {
-    /// Base weight (spec: 100)
-    base_weight: u128,
-
-    /// PoI multiplier (spec: λ = 10)
-    poi_lambda: f64,
-
-    /// Reputation multiplier (spec: μ = 0.05)
-    rep_mu: f64,
-
-    /// Stake multiplier (spec: ν = 0.02)
-    stake_nu: f64,
+    params: WeightParams,
 }
 
 impl PoIWeightCalculator {
     /// Create new weight calculator with default parameters
     pub fn new() -> Self {
-        Self {
-            base_weight: 100,
-            poi_lambda: 10.0,
-            rep_mu: 0.05,
-            stake_nu: 0.02,
-        }
+        Self::with_params(WeightParams::default())
+    }
+
+    /// Create weight calculator from chain parameters
+    pub fn with_params(params: WeightParams) -> Self {
+        Self { params }
+    }
+
+    /// Create weight calculator for a chain
+    pub fn from_chain_spec(spec: &ChainSpec) -> Self {
+        Self::with_params(spec.weights)
+    }
+
+    pub fn params(&self) -> &WeightParams {
+        &self.params
     }
 
     /// Calculate effective weight from attestation
@@ -356,29 +619,64 @@ impl PoIWeightCalculator {
         rep_score: u64,
         stake_bond: u128,
     ) -> u128 {
-        let poi_carry = attestation.measurement.impact_score;
-        self.calculate_weight(poi_carry, rep_score, stake_bond)
+        let poi_carry_bps = score_to_bps(attestation.measurement.impact_score);
+        self.calculate_weight(poi_carry_bps, rep_score, stake_bond)
     }
 
-    /// Calculate effective weight
+    /// Calculate effective weight (fixed-point)
     ///
     /// Formula from spec section 5.8:
     /// ```text
     /// weight_eff = BASE + λ * PoI_carry + μ * rep_score + ν * sqrt(stake_bond)
     /// ```
+    ///
+    /// `poi_carry_bps` is PoI_carry in basis points (see [`score_to_bps`]).
+    /// Saturates at `u128::MAX` instead of overflowing.
     pub fn calculate_weight(
         &self,
-        poi_carry: f64,
+        poi_carry_bps: u128,
         rep_score: u64,
         stake_bond: u128,
     ) -> u128 {
-        let base = self.base_weight as f64;
-        let poi_component = self.poi_lambda * poi_carry;
-        let rep_component = self.rep_mu * (rep_score as f64);
-        let stake_component = self.stake_nu * (stake_bond as f64).sqrt();
+        let p = &self.params;
+
+        // All terms in bps
+        let base = p.base_weight.saturating_mul(BPS_SCALE);
+        let poi_component = p.poi_lambda_bps.saturating_mul(poi_carry_bps) / BPS_SCALE;
+        let rep_component = p.rep_mu_bps.saturating_mul(rep_score as u128);
+        let stake_component = p.stake_nu_bps.saturating_mul(sqrt_bps(stake_bond)) / BPS_SCALE;
+
+        base.saturating_add(poi_component)
+            .saturating_add(rep_component)
+            .saturating_add(stake_component)
+            / BPS_SCALE
+    }
+
+    /// Floating-point reference of [`calculate_weight`](Self::calculate_weight)
+    ///
+    /// Not used for consensus; kept to document and test the fixed-point error bound.
+    pub fn calculate_weight_reference(
+        &self,
+        poi_carry: f64,
+        rep_score: u64,
+        stake_bond: u128,
+    ) -> f64 {
+        let p = &self.params;
+        let scale = BPS_SCALE as f64;
+
+        p.base_weight as f64
+            + (p.poi_lambda_bps as f64 / scale) * poi_carry
+            + (p.rep_mu_bps as f64 / scale) * rep_score as f64
+            + (p.stake_nu_bps as f64 / scale) * (stake_bond as f64).sqrt()
+    }
+}
 
-        let total = base + poi_component + rep_component + stake_component;
-        total as u128
+/// sqrt(x) in bps, i.e. floor(sqrt(x) * 10,000)
+fn sqrt_bps(x: u128) -> u128 {
+    match x.checked_mul(BPS_SCALE * BPS_SCALE) {
+        Some(scaled) => isqrt(scaled),
+        // Beyond ~3.4e30 the sub-unit fraction is irrelevant
+        None => isqrt(x).saturating_mul(BPS_SCALE),
     }
 }
 
@@ -389,13 +687,14 @@ impl Default for PoIWeightCalculator {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::chain_spec::{TESTNET_CHAIN_ID, TESTNET_GENESIS_MERKLE_ROOT};
 
     fn test_anchor() -> Anchor {
         Anchor {
-            chain_id: "bizra-testnet-001".to_string(),
-            genesis_merkle_root: "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f".to_string(),
+            chain_id: TESTNET_CHAIN_ID.to_string(),
+            genesis_merkle_root: TESTNET_GENESIS_MERKLE_ROOT.to_string(),
             block_ref: None,
         }
     }
@@ -404,6 +703,7 @@ mod tests {
         Attester {
             id: "node0:bizra".to_string(),
             pubkey_ed25519: "ed25519:72727b02172ed3b43b7b659e18a91a4093e258a8d894366723c3ce333449a4d0".to_string(),
+            pubkey_mldsa65: None,
         }
     }
 
@@ -423,6 +723,7 @@ mod tests {
         dimensions.insert("utility".to_string(), 0.88);
         dimensions.insert("efficiency".to_string(), 0.86);
         dimensions.insert("trust".to_string(), 0.94);
+        dimensions.insert("fairness".to_string(), 0.70);
         dimensions.insert("diversity".to_string(), 0.70);
 
         let mut weights = BTreeMap::new();
@@ -430,19 +731,25 @@ mod tests {
         weights.insert("utility".to_string(), 0.28);
         weights.insert("efficiency".to_string(), 0.16);
         weights.insert("trust".to_string(), 0.16);
-        weights.insert("diversity".to_string(), 0.12);
+        weights.insert("fairness".to_string(), 0.06);
+        weights.insert("diversity".to_string(), 0.06);
 
-        // Computed: 0.28*0.92 + 0.28*0.88 + 0.16*0.86 + 0.16*0.94 + 0.12*0.70
-        // = 0.2576 + 0.2464 + 0.1376 + 0.1504 + 0.084 = 0.876
+        // Computed: 0.28*0.92 + 0.28*0.88 + 0.16*0.86 + 0.16*0.94 + 0.06*0.70 + 0.06*0.70
+        // = 0.2576 + 0.2464 + 0.1376 + 0.1504 + 0.042 + 0.042 = 0.876
         Measurement {
+            schema: None,
             dimensions,
             weights,
             impact_score: 0.876,
         }
     }
 
-    fn test_attestation() -> PoIAttestation {
-        PoIAttestation {
+    pub(crate) fn test_signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    pub(crate) fn test_attestation() -> PoIAttestation {
+        let mut attestation = PoIAttestation {
             version: POI_VERSION.to_string(),
             anchor: test_anchor(),
             attester: test_attester(),
@@ -459,7 +766,9 @@ mod tests {
                 alg: "ed25519".to_string(),
                 sig_base16: "00".to_string(),
             },
-        }
+        };
+        attestation.sign(&test_signing_key()).unwrap();
+        attestation
     }
 
     #[test]
@@ -478,9 +787,12 @@ mod tests {
         let payload = attestation.canonical_payload().unwrap();
         assert!(!payload.is_empty());
 
-        // Should not contain actual signature
+        // Signature is omitted entirely, and members are sorted
         let json_str = String::from_utf8(payload).unwrap();
-        assert!(!json_str.contains("\"sig_base16\":\"00\""));
+        assert!(!json_str.contains("signature"));
+        assert!(!json_str.contains("sig_base16"));
+        assert!(json_str.starts_with("{\"anchor\":{\"chain_id\":"));
+        assert!(json_str.contains("\"impact_score\":0.876,"));
     }
 
     #[test]
@@ -496,10 +808,7 @@ mod tests {
     #[test]
     fn test_validate_success() {
         let attestation = test_attestation();
-        let result = attestation.validate(
-            "bizra-testnet-001",
-            "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f",
-        );
+        let result = attestation.validate(&ChainSpec::testnet());
         assert!(result.is_ok(), "Validation failed: {:?}", result.err());
     }
 
@@ -508,7 +817,7 @@ mod tests {
         let mut attestation = test_attestation();
         attestation.version = "poi-2.0".to_string();
 
-        let result = attestation.validate("bizra-testnet-001", "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f");
+        let result = attestation.validate(&ChainSpec::testnet());
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invalid version"));
     }
@@ -516,17 +825,54 @@ mod tests {
     #[test]
     fn test_validate_chain_id_mismatch() {
         let attestation = test_attestation();
-        let result = attestation.validate("wrong-chain", "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f");
+        let result = attestation.validate(&ChainSpec::new("wrong-chain", TESTNET_GENESIS_MERKLE_ROOT));
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Chain ID mismatch"));
     }
 
+    #[test]
+    fn test_validate_time_window() {
+        let now = DateTime::parse_from_rfc3339("2025-09-16T00:00:00Z").unwrap().with_timezone(&Utc);
+        let attestation = test_attestation();
+        assert!(attestation.validate_at(&ChainSpec::testnet(), now).is_ok());
+
+        let cases = [
+            (["2025-09-15 10:00", "2025-09-15T14:00:00Z"], "Invalid RFC3339"),
+            (["2025-09-15T14:00:00Z", "2025-09-15T10:00:00Z"], "not before end"),
+            (["2025-09-15T10:00:00Z", "2025-09-15T10:00:00Z"], "not before end"),
+            (["2025-08-01T00:00:00Z", "2025-09-15T00:00:00Z"], "Time window too long"),
+            (["2025-09-15T10:00:00Z", "2025-09-16T01:00:00Z"], "ends in the future"),
+        ];
+        for (window, expected) in cases {
+            let mut attestation = test_attestation();
+            attestation.time_window = [window[0].to_string(), window[1].to_string()];
+            attestation.sign(&test_signing_key()).unwrap();
+
+            let err = attestation.validate_at(&ChainSpec::testnet(), now).unwrap_err();
+            assert!(err.to_string().contains(expected), "{:?}: {}", window, err);
+        }
+
+        // Offsets are normalized to UTC; small clock skew is tolerated
+        let mut attestation = test_attestation();
+        attestation.time_window = ["2025-09-15T12:00:00+02:00".to_string(), "2025-09-16T00:04:00Z".to_string()];
+        attestation.sign(&test_signing_key()).unwrap();
+        assert!(attestation.validate_at(&ChainSpec::testnet(), now).is_ok());
+    }
+
+    #[test]
+    fn test_validate_nonce_not_hex() {
+        let mut attestation = test_attestation();
+        attestation.nonce = "zzb5c7d3c6b9421a8c7c3f0f0a4d33aa".to_string();
+        let result = attestation.validate(&ChainSpec::testnet());
+        assert!(result.unwrap_err().to_string().contains("must be hex"));
+    }
+
     #[test]
     fn test_validate_impact_score_mismatch() {
         let mut attestation = test_attestation();
         attestation.measurement.impact_score = 0.5; // Wrong score
 
-        let result = attestation.validate("bizra-testnet-001", "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f");
+        let result = attestation.validate(&ChainSpec::testnet());
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Impact score mismatch"));
     }
@@ -534,17 +880,36 @@ mod tests {
     #[test]
     fn test_validate_dimension_out_of_range() {
         let mut attestation = test_attestation();
-        attestation.measurement.dimensions.insert("bad_dim".to_string(), 1.5);
+        attestation.measurement.dimensions.insert("quality".to_string(), 1.5);
 
-        let result = attestation.validate("bizra-testnet-001", "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f");
+        let result = attestation.validate(&ChainSpec::testnet());
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("out of range"));
     }
 
+    #[test]
+    fn test_validate_schema_dimensions() {
+        let mut unknown = test_attestation();
+        unknown.measurement.dimensions.insert("bad_dim".to_string(), 0.5);
+        let err = unknown.validate(&ChainSpec::testnet()).unwrap_err();
+        assert!(err.to_string().contains("Unknown dimension 'bad_dim'"));
+
+        let mut missing = test_attestation();
+        missing.measurement.dimensions.remove("fairness");
+        let err = missing.validate(&ChainSpec::testnet()).unwrap_err();
+        assert!(err.to_string().contains("Missing dimension 'fairness'"));
+
+        let mut undeclared = test_attestation();
+        undeclared.measurement.schema = Some("poi-9.9".to_string());
+        undeclared.sign(&test_signing_key()).unwrap();
+        let err = undeclared.validate(&ChainSpec::testnet()).unwrap_err();
+        assert!(err.to_string().contains("Unknown measurement schema: poi-9.9"));
+    }
+
     #[test]
     fn test_weight_calculator_base_only() {
         let calculator = PoIWeightCalculator::new();
-        let weight = calculator.calculate_weight(0.0, 0, 0);
+        let weight = calculator.calculate_weight(0, 0, 0);
         assert_eq!(weight, 100);
     }
 
@@ -552,7 +917,7 @@ mod tests {
     fn test_weight_calculator_with_poi() {
         let calculator = PoIWeightCalculator::new();
         // PoI = 50 → BASE + 10*50 = 600
-        let weight = calculator.calculate_weight(50.0, 0, 0);
+        let weight = calculator.calculate_weight(score_to_bps(50.0), 0, 0);
         assert_eq!(weight, 600);
     }
 
@@ -561,7 +926,7 @@ mod tests {
         let calculator = PoIWeightCalculator::new();
         // PoI=50, rep=10000, stake=10000
         // BASE + 10*50 + 0.05*10000 + 0.02*100 = 100 + 500 + 500 + 2 = 1102
-        let weight = calculator.calculate_weight(50.0, 10000, 10000);
+        let weight = calculator.calculate_weight(score_to_bps(50.0), 10000, 10000);
         assert_eq!(weight, 1102);
     }
 
@@ -577,6 +942,45 @@ mod tests {
         assert_eq!(weight, 610);
     }
 
+    #[test]
+    fn test_weight_calculator_custom_params() {
+        let calculator = PoIWeightCalculator::with_params(WeightParams {
+            base_weight: 50,
+            poi_lambda_bps: 25_000, // λ = 2.5
+            rep_mu_bps: 1_000,      // μ = 0.1
+            stake_nu_bps: 10_000,   // ν = 1
+        });
+        // 50 + 2.5*4 + 0.1*1000 + 1*sqrt(2) = 50 + 10 + 100 + 1.4142 = 161.41
+        assert_eq!(calculator.calculate_weight(score_to_bps(4.0), 1000, 2), 161);
+    }
+
+    #[test]
+    fn test_weight_calculator_saturates() {
+        let calculator = PoIWeightCalculator::new();
+        // λ * PoI saturates at u128::MAX bps instead of overflowing
+        let weight = calculator.calculate_weight(u128::MAX, 0, 0);
+        assert_eq!(weight, 100 + u128::MAX / BPS_SCALE / BPS_SCALE);
+        assert!(calculator.calculate_weight(u128::MAX, u64::MAX, u128::MAX) > weight);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in [0u128, 1, 2, 3, 4, 15, 16, 17, 99_999_999, 100_000_000, u64::MAX as u128, u128::MAX] {
+            let r = isqrt(n);
+            assert!(r * r <= n, "isqrt({}) = {} too large", n, r);
+            assert!((r + 1).checked_mul(r + 1).is_none_or(|sq| sq > n), "isqrt({}) = {} too small", n, r);
+        }
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_score_to_bps() {
+        assert_eq!(score_to_bps(0.876), 8_760);
+        assert_eq!(score_to_bps(0.00005), 1);
+        assert_eq!(score_to_bps(-0.5), 0);
+        assert_eq!(score_to_bps(f64::NAN), 0);
+    }
+
     #[test]
     fn test_benchmarks_delta_validation() {
         let mut attestation = test_attestation();
@@ -592,11 +996,113 @@ mod tests {
             post,
             delta: 0.328, // Correct: 0.840 - 0.512 = 0.328
         });
+        attestation.sign(&test_signing_key()).unwrap();
 
-        let result = attestation.validate("bizra-testnet-001", "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f");
+        let result = attestation.validate(&ChainSpec::testnet());
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_hybrid_signature_roundtrip() {
+        let keypair = HybridKeypair::generate();
+        let mut attestation = test_attestation();
+        attestation.sign_hybrid(&keypair).unwrap();
+
+        assert_eq!(attestation.signature.alg, SIG_ALG_HYBRID);
+        let result = attestation.validate(&ChainSpec::testnet());
+        assert!(result.is_ok(), "Validation failed: {:?}", result.err());
+
+        // Survives a JSON roundtrip
+        let json = serde_json::to_string(&attestation).unwrap();
+        let parsed: PoIAttestation = serde_json::from_str(&json).unwrap();
+        assert!(parsed.validate(&ChainSpec::testnet()).is_ok());
+    }
+
+    #[test]
+    fn test_hybrid_signature_tampered_payload() {
+        let keypair = HybridKeypair::generate();
+        let mut attestation = test_attestation();
+        attestation.sign_hybrid(&keypair).unwrap();
+
+        attestation.nonce = "ffffffffffffffffffffffffffffffff".to_string();
+        let result = attestation.validate(&ChainSpec::testnet());
+        let err = result.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SignatureError>(),
+            Some(&SignatureError::SignatureMismatch(SIG_ALG_HYBRID.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_hybrid_signature_requires_mldsa_key() {
+        let keypair = HybridKeypair::generate();
+        let mut attestation = test_attestation();
+        attestation.sign_hybrid(&keypair).unwrap();
+
+        attestation.attester.pubkey_mldsa65 = None;
+        let result = attestation.validate(&ChainSpec::testnet());
+        assert!(result.unwrap_err().to_string().contains("pubkey_mldsa65"));
+    }
+
+    #[test]
+    fn test_ed25519_signature_verified() {
+        let attestation = test_attestation();
+        let pk_hex = hex::encode(test_signing_key().verifying_key().to_bytes());
+        assert_eq!(attestation.attester.pubkey_ed25519, format!("ed25519:{}", pk_hex));
+        assert_eq!(attestation.signature.sig_base16.len(), 128);
+
+        // Forged: a payload field changed after signing
+        let mut forged = attestation.clone();
+        forged.evidence.files_processed = Some(999_999);
+        let err = forged.validate(&ChainSpec::testnet()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SignatureError>(),
+            Some(&SignatureError::SignatureMismatch(SIG_ALG_ED25519.to_string()))
+        );
+
+        // Forged: signed by a different key than the declared attester
+        let mut forged = attestation.clone();
+        forged.sign(&SigningKey::from_bytes(&[8u8; 32])).unwrap();
+        forged.attester.pubkey_ed25519 = attestation.attester.pubkey_ed25519.clone();
+        let err = forged.validate(&ChainSpec::testnet()).unwrap_err();
+        assert!(matches!(err.downcast_ref::<SignatureError>(), Some(SignatureError::SignatureMismatch(_))));
+    }
+
+    #[test]
+    fn test_ed25519_bad_key_encoding() {
+        for bad_key in ["72727b02", "ed25519:zz", "ed25519:7272", "mldsa65:72727b02172ed3b43b7b659e18a91a4093e258a8d894366723c3ce333449a4d0"] {
+            let mut attestation = test_attestation();
+            attestation.attester.pubkey_ed25519 = bad_key.to_string();
+            let err = attestation.validate(&ChainSpec::testnet()).unwrap_err();
+            assert!(
+                matches!(err.downcast_ref::<SignatureError>(), Some(SignatureError::InvalidKeyEncoding { codec: "ed25519", .. })),
+                "{} should be rejected as a bad key encoding, got {}", bad_key, err
+            );
+        }
+    }
+
+    #[test]
+    fn test_ed25519_bad_signature_encoding() {
+        for bad_sig in ["00", "not-hex", ""] {
+            let mut attestation = test_attestation();
+            attestation.signature.sig_base16 = bad_sig.to_string();
+            let err = attestation.validate(&ChainSpec::testnet()).unwrap_err();
+            assert!(
+                matches!(err.downcast_ref::<SignatureError>(), Some(SignatureError::InvalidSignatureEncoding(_))),
+                "{:?} should be rejected as a bad signature encoding, got {}", bad_sig, err
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsupported_signature_algorithm() {
+        let mut attestation = test_attestation();
+        attestation.signature.alg = "mldsa65".to_string();
+
+        let result = attestation.validate(&ChainSpec::testnet());
+        assert!(result.unwrap_err().to_string().contains("Unsupported signature algorithm"));
+    }
+
     #[test]
     fn test_benchmarks_delta_mismatch() {
         let mut attestation = test_attestation();
@@ -613,7 +1119,7 @@ mod tests {
             delta: 0.5, // Wrong: should be 0.328
         });
 
-        let result = attestation.validate("bizra-testnet-001", "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f");
+        let result = attestation.validate(&ChainSpec::testnet());
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Benchmark delta mismatch"));
     }
@@ -1,12 +1,182 @@
 //! Validator registry and active set management
 //!
 //! Manages the active validator set with epoch-based rotation and churn limits
+//!
+//! ## Epoch Transition
+//!
+//! [`ValidatorRegistry::advance_epoch`] moves the registry to the next epoch in
+//! one step, in this order:
+//!
+//! 1. Complete exits whose unbonding delay has passed (Exiting → Exited);
+//!    switch to announced keys whose rotation delay has passed
+//! 2. Release slashed validators whose quarantine has ended (Slashed → Exited),
+//!    then release matured unbonding entries (held while a validator is slashed)
+//! 3. Decay the reputation of validators that served in the closing epoch, never
+//!    below `min_reputation_for_activation`
+//! 4. Demote the lowest-weight Active validators if the set exceeds `max_active_validators`
+//! 5. Activate queued Pending validators, up to `churn_limit(N)` and the free capacity
+//!
+//! [`ValidatorRegistry::close_epoch`] additionally recomputes PoI weights and
+//! distributes the closing epoch's rewards first, all in the same change; its
+//! reward manifest is stored with the new epoch's snapshot.
+//!
+//! ## Slashing
+//!
+//! [`ValidatorRegistry::submit_slash_evidence`] verifies the proof against the
+//! validator's key, re-executes `FraudulentPoI` claims with the configured
+//! benchmark verifier and rejects censorship claims as unverifiable. It then
+//! drops duplicates, applies the [`SlashingPolicy`] for the reason and
+//! quarantines the validator until `current_epoch + quarantine_epochs`, after
+//! which `advance_epoch` releases it.
+//!
+//! ## Key Rotation
+//!
+//! [`ValidatorRegistry::submit_rotate_key_tx`] schedules a new key signed off by
+//! the current one; it takes over `key_rotation_delay_epochs` later and the old
+//! key moves to the record's `key_history`, where it stays for
+//! `key_history_epochs` after retiring. A consensus message signed by a
+//! rotated-out key for a view at or after its retirement is key theft
+//! ([`ValidatorRegistry::retired_key_evidence`]).
+//!
+//! ## Liveness
+//!
+//! [`ValidatorRegistry::record_liveness`] ingests "validator signed at slot S"
+//! events; [`ValidatorRegistry::process_liveness`] applies graduated reputation
+//! penalties and moves long-silent validators Offline (see [`crate::liveness`]).
+//!
+//! ## Events
+//!
+//! [`ValidatorRegistry::subscribe`] streams every lifecycle change, slash and
+//! weight update as a [`RegistryEvent`] (see [`crate::events`]).
+//!
+//! ## Persistence
+//!
+//! A registry opened with [`ValidatorRegistry::with_store`] restores the latest
+//! snapshot and writes a new one after every lifecycle change, so it survives
+//! restarts and can answer point-in-time queries (see [`crate::registry_store`]).
+//! Snapshots include reputation history, liveness windows and applied slashes,
+//! so a restart neither accepts the same evidence again nor re-charges missed
+//! windows. Changes are applied to a copy of the state and only take effect
+//! once their snapshot is stored; a storage error leaves the registry as it was.
+//! Snapshots are written without holding the state locks, so reads go on
+//! while one is stored.
 
-use crate::types::{ValidatorId, ValidatorRecord};
+use crate::benchmark::{BenchmarkVerifier, FraudulentPoIProof};
+use crate::bond::BondReceipt;
+use crate::chain_spec::ChainSpec;
+use crate::consensus_message::SignedConsensusMessage;
+use crate::events::{RegistryEvent, EVENT_CHANNEL_CAPACITY};
+use crate::evidence::EvidencePack;
+use crate::liveness::{AtRiskValidator, LivenessReport, LivenessTracker};
+use crate::poi::PoIWeightCalculator;
+use crate::registry_store::{RegistrySnapshot, RegistryStore, RegistryStoreError};
+use crate::reputation::{ReputationChange, ReputationReason, ReputationTracker};
+use crate::rewards::{RewardCalculator, RewardInput, RewardManifest};
+use crate::slashing::{KeyTheftProof, SlashError, SlashEvent, SlashEvidence, SlashReason, SlashingPolicy};
+use crate::tx::{BondAction, BondTx, ExitTx, JoinTx, RotateKeyTx, TxError};
+use crate::types::{validator_id_for, Ed25519PublicKey, ValidatorId, ValidatorRecord, ValidatorStatus};
 use anyhow::Result;
-use std::collections::HashMap;
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tracing::warn;
+
+/// Order in which queued Pending validators are activated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActivationOrder {
+    /// Registration order
+    #[default]
+    Fifo,
+
+    /// Highest stake bond first, then highest reputation, then registration order
+    Priority,
+}
+
+/// Report of one epoch transition
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochTransition {
+    /// Epoch that was closed
+    pub from_epoch: u64,
+
+    /// Epoch the registry is now in
+    pub to_epoch: u64,
+
+    /// Activation budget for this transition
+    pub churn_limit: usize,
+
+    /// Pending → Active
+    pub activated: Vec<ValidatorId>,
+
+    /// Exiting → Exited (unbonding complete)
+    pub exited: Vec<ValidatorId>,
+
+    /// Slashed → Exited (quarantine over)
+    pub released: Vec<ValidatorId>,
+
+    /// Active → Pending to enforce the active set cap
+    pub deactivated: Vec<ValidatorId>,
+
+    /// Pending key rotations that took effect
+    pub keys_rotated: Vec<ValidatorId>,
+
+    /// Unbonding funds that became withdrawable
+    pub bonds_released: Vec<(ValidatorId, u128)>,
+
+    /// Validators whose reputation was decayed
+    pub reputation_decayed: usize,
+
+    /// Validators left in the activation queue
+    pub pending_remaining: usize,
+
+    /// Active set size after the transition
+    pub active_count: usize,
+}
+
+/// Report of [`ValidatorRegistry::close_epoch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochClose {
+    /// Weight-counting validators whose `poi_weight` was recomputed
+    pub weights_updated: usize,
+
+    /// Rewards of the closed epoch
+    pub rewards: RewardManifest,
+
+    pub transition: EpochTransition,
+}
+
+/// Accepted slash evidence and the resulting events
+#[derive(Debug, Clone, Default)]
+struct SlashingLog {
+    /// Dedup keys of applied evidence
+    seen: HashSet<[u8; 32]>,
+
+    /// Applied slashes, oldest first
+    events: Vec<SlashEvent>,
+}
+
+impl SlashingLog {
+    fn from_events(events: Vec<SlashEvent>) -> Self {
+        Self { seen: events.iter().map(SlashEvent::dedup_key).collect(), events }
+    }
+}
+
+/// Copy of the registry state a change is applied to
+///
+/// See [`ValidatorRegistry::transact`].
+struct RegistryState {
+    validators: HashMap<ValidatorId, ValidatorRecord>,
+    queue: VecDeque<ValidatorId>,
+    epoch: u64,
+    reputation: ReputationTracker,
+    slashing: SlashingLog,
+    liveness: LivenessTracker,
+    rewards: Option<RewardManifest>,
+
+    /// Set by a change that turned out to be a no-op: nothing is stored
+    unchanged: bool,
+}
 
 /// Validator registry managing active set
 pub struct ValidatorRegistry {
@@ -15,28 +185,454 @@ pub struct ValidatorRegistry {
 
     /// Current epoch
     current_epoch: Arc<RwLock<u64>>,
+
+    /// Pending validators awaiting activation, in registration order
+    activation_queue: Arc<RwLock<VecDeque<ValidatorId>>>,
+
+    /// Order in which the activation queue is drained
+    activation_order: ActivationOrder,
+
+    /// Reputation changes and their history
+    reputation: Arc<RwLock<ReputationTracker>>,
+
+    /// Applied slashes
+    slashing: Arc<RwLock<SlashingLog>>,
+
+    /// Missed-window bookkeeping
+    liveness: Arc<RwLock<LivenessTracker>>,
+
+    /// Reward manifest of the last epoch closed with rewards
+    rewards: Arc<RwLock<Option<RewardManifest>>>,
+
+    /// Snapshot storage (in-memory only if None)
+    store: Option<Arc<Mutex<Box<dyn RegistryStore>>>>,
+
+    /// Held by every change, so a copy being stored is never overtaken
+    commit: Arc<Mutex<()>>,
+
+    /// Re-executes benchmark claims of `FraudulentPoI` evidence (rejected if None)
+    benchmarks: Option<Arc<BenchmarkVerifier>>,
+
+    /// Chain parameters this registry runs under
+    chain_spec: Arc<ChainSpec>,
+
+    /// Change notifications
+    events: broadcast::Sender<RegistryEvent>,
 }
 
 impl ValidatorRegistry {
-    /// Create new validator registry
+    /// Create new validator registry for the alpha testnet
     pub fn new() -> Self {
+        Self::with_chain_spec(ChainSpec::testnet())
+    }
+
+    /// Create new validator registry for a chain
+    pub fn with_chain_spec(chain_spec: ChainSpec) -> Self {
         Self {
             validators: Arc::new(RwLock::new(HashMap::new())),
             current_epoch: Arc::new(RwLock::new(0)),
+            activation_queue: Arc::new(RwLock::new(VecDeque::new())),
+            activation_order: ActivationOrder::default(),
+            reputation: Arc::new(RwLock::new(ReputationTracker::new())),
+            slashing: Arc::new(RwLock::new(SlashingLog::default())),
+            liveness: Arc::new(RwLock::new(LivenessTracker::from_chain_spec(&chain_spec))),
+            rewards: Arc::new(RwLock::new(None)),
+            store: None,
+            commit: Arc::new(Mutex::new(())),
+            benchmarks: None,
+            chain_spec: Arc::new(chain_spec),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
         }
     }
 
-    /// Register new validator (Pending state)
-    pub async fn register(&self, validator: ValidatorRecord) -> Result<()> {
+    /// Create a persistent registry, restoring the latest snapshot in `store`
+    pub fn with_store(chain_spec: ChainSpec, store: Box<dyn RegistryStore>) -> Result<Self> {
+        let latest = store.latest()?;
+        let mut registry = Self::with_chain_spec(chain_spec);
+
+        if let Some(snapshot) = latest {
+            if snapshot.chain_id != registry.chain_spec.chain_id {
+                return Err(RegistryStoreError::ChainMismatch {
+                    expected: registry.chain_spec.chain_id.clone(),
+                    found: snapshot.chain_id,
+                }
+                .into());
+            }
+            registry.current_epoch = Arc::new(RwLock::new(snapshot.epoch));
+            registry.activation_queue = Arc::new(RwLock::new(snapshot.activation_queue.into()));
+            registry.validators = Arc::new(RwLock::new(
+                snapshot.validators.into_iter().map(|v| (v.validator_id, v)).collect(),
+            ));
+            let mut reputation = ReputationTracker::new();
+            reputation.restore(snapshot.reputation_history);
+            registry.reputation = Arc::new(RwLock::new(reputation));
+            let mut liveness = LivenessTracker::from_chain_spec(&registry.chain_spec);
+            liveness.restore(snapshot.liveness);
+            registry.liveness = Arc::new(RwLock::new(liveness));
+            registry.slashing = Arc::new(RwLock::new(SlashingLog::from_events(snapshot.slash_events)));
+            registry.rewards = Arc::new(RwLock::new(snapshot.rewards));
+        }
+
+        registry.store = Some(Arc::new(Mutex::new(store)));
+        Ok(registry)
+    }
+
+    /// Set the order in which Pending validators are activated
+    pub fn with_activation_order(mut self, order: ActivationOrder) -> Self {
+        self.activation_order = order;
+        self
+    }
+
+    /// Accept `FraudulentPoI` evidence after re-executing its claim with `verifier`
+    pub fn with_benchmark_verifier(mut self, verifier: BenchmarkVerifier) -> Self {
+        self.benchmarks = Some(Arc::new(verifier));
+        self
+    }
+
+    /// Chain parameters
+    pub fn chain_spec(&self) -> &ChainSpec {
+        &self.chain_spec
+    }
+
+    /// Receive every change applied from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<RegistryEvent> {
+        self.events.subscribe()
+    }
+
+    /// Notify subscribers (no-op without any)
+    fn emit(&self, events: impl IntoIterator<Item = RegistryEvent>) {
+        for event in events {
+            let _ = self.events.send(event);
+        }
+    }
+
+    /// Register a validator record as is (tests only)
+    ///
+    /// Takes no proof that the caller holds the key, so outside tests
+    /// validators join through [`Self::submit_join_tx`]. The same checks apply:
+    /// `validator_id` must be blake3 of `pk_ed25519` ([`TxError::IdKeyMismatch`])
+    /// and the key must not be held by another validator (current, pending or
+    /// retired). A Pending registration with the current key of an Exited
+    /// validator rejoins that validator ([`ValidatorRecord::rejoin`]).
+    #[cfg(test)]
+    pub(crate) async fn register(&self, validator: ValidatorRecord) -> Result<()> {
+        self.admit(validator).await.map(|_| ())
+    }
+
+    /// Validate a signed join request and register the validator (Pending state)
+    ///
+    /// The signature proves the joiner holds `pk_ed25519`, so nobody can claim
+    /// someone else's key first. `validator_id` must be blake3 of the key and
+    /// the key must not be held by another validator (current, pending or
+    /// retired); signing with the current key of an Exited validator rejoins
+    /// it ([`ValidatorRecord::rejoin`]).
+    ///
+    /// The validator starts without a bond whatever `tx.bond` declares; stake
+    /// is only credited by [`Self::deposit_bond`]. Returns the registered
+    /// record, which for a rejoin is the existing one.
+    pub async fn submit_join_tx(&self, tx: &JoinTx) -> Result<ValidatorRecord> {
+        let epoch = self.get_epoch().await;
+        let (validator_id, pk) = tx.validate(&self.chain_spec.chain_id, epoch)?;
+
+        self.admit(ValidatorRecord::new_pending(validator_id, pk, tx.network_address.clone(), epoch)).await
+    }
+
+    /// Register or rejoin a validator; returns the stored record
+    async fn admit(&self, validator: ValidatorRecord) -> Result<ValidatorRecord> {
+        if validator.validator_id != validator_id_for(&validator.pk_ed25519) {
+            return Err(TxError::IdKeyMismatch { validator_id: hex::encode(validator.validator_id) }.into());
+        }
+        let pk = validator.pk_ed25519;
+
+        let (registered, epoch) = self
+            .transact(|state| {
+                let rejoining = state
+                    .validators
+                    .values()
+                    .find(|v| v.status == ValidatorStatus::Exited && v.pk_ed25519 == pk)
+                    .filter(|_| validator.status == ValidatorStatus::Pending)
+                    .map(|v| v.validator_id);
+                if rejoining.is_none() && state.validators.contains_key(&validator.validator_id) {
+                    anyhow::bail!("Validator already registered: {:?}", validator.validator_id);
+                }
+                if state.validators.values().any(|v| Some(v.validator_id) != rejoining && v.holds_key(&pk)) {
+                    anyhow::bail!("Key {} is registered to another validator", hex::encode(pk));
+                }
+
+                let registered = match rejoining {
+                    Some(id) => {
+                        let existing = state.validators.get_mut(&id).expect("found above");
+                        existing.rejoin(validator.network_address, state.epoch).map_err(anyhow::Error::msg)?;
+                        existing.clone()
+                    }
+                    None => {
+                        state.validators.insert(validator.validator_id, validator.clone());
+                        validator
+                    }
+                };
+                if registered.status == ValidatorStatus::Pending {
+                    state.queue.push_back(registered.validator_id);
+                }
+                Ok((registered, state.epoch))
+            })
+            .await?;
+        self.emit([RegistryEvent::Registered { validator_id: registered.validator_id, epoch }]);
+        Ok(registered)
+    }
+
+    /// Validate a signed exit request and start unbonding (Active or Offline → Exiting)
+    ///
+    /// The validator leaves after the chain's unbonding delay; its whole bond
+    /// unbonds with the same release epoch.
+    pub async fn submit_exit_tx(&self, tx: &ExitTx) -> Result<ValidatorRecord> {
+        let validator_id = tx.validator_id()?;
+        let delay = self.chain_spec.epoch.unbonding_delay_epochs;
+
+        let (exiting, exit_epoch) = self
+            .transact(|state| {
+                let epoch = state.epoch;
+                let validator = state
+                    .validators
+                    .get_mut(&validator_id)
+                    .ok_or_else(|| anyhow::anyhow!("Validator not found: {}", tx.validator_id))?;
+                tx.validate(&self.chain_spec.chain_id, epoch, &validator.pk_ed25519)?;
+
+                let exit_epoch = epoch + delay;
+                validator.initiate_exit(epoch, delay).map_err(|e| anyhow::anyhow!(e))?;
+                if validator.stake_bond > 0 {
+                    validator.unbond(validator.stake_bond, exit_epoch)?;
+                }
+                Ok((validator.clone(), exit_epoch))
+            })
+            .await?;
+        self.emit([RegistryEvent::ExitInitiated { validator_id, exit_epoch }]);
+        Ok(exiting)
+    }
+
+    /// Validate a signed key rotation and schedule the new key
+    ///
+    /// The new key takes over at the first epoch transition reaching
+    /// `epoch + key_rotation_delay_epochs`; until then the current key keeps
+    /// signing. `validator_id`, reputation and weight are unaffected.
+    pub async fn submit_rotate_key_tx(&self, tx: &RotateKeyTx) -> Result<ValidatorRecord> {
+        let validator_id = tx.validator_id()?;
+
+        let (scheduled, new_pk, activation_epoch) = self
+            .transact(|state| {
+                let epoch = state.epoch;
+                let current_pk = state
+                    .validators
+                    .get(&validator_id)
+                    .ok_or_else(|| anyhow::anyhow!("Validator not found: {}", tx.validator_id))?
+                    .pk_ed25519;
+                let new_pk = tx.validate(&self.chain_spec.chain_id, epoch, &current_pk)?;
+
+                if state.validators.values().any(|v| v.validator_id != validator_id && v.holds_key(&new_pk)) {
+                    anyhow::bail!("Key {} is registered to another validator", tx.new_pk_ed25519);
+                }
+
+                let validator = state.validators.get_mut(&validator_id).expect("checked above");
+                let activation_epoch = epoch + self.chain_spec.epoch.key_rotation_delay_epochs;
+                validator
+                    .schedule_key_rotation(new_pk, epoch, activation_epoch)
+                    .map_err(anyhow::Error::msg)?;
+                Ok((validator.clone(), new_pk, activation_epoch))
+            })
+            .await?;
+        self.emit([RegistryEvent::KeyRotationScheduled { validator_id, pk_ed25519: new_pk, activation_epoch }]);
+        Ok(scheduled)
+    }
+
+    /// Validate a signed bond transaction and apply it to the validator's ledger
+    ///
+    /// The transaction must be signed by the validator's current key and carry
+    /// the next bond nonce, so each signed request applies at most once.
+    pub async fn submit_bond_tx(&self, tx: &BondTx) -> Result<BondReceipt> {
+        let validator_id = tx.validator_id()?;
+        let delay = self.chain_spec.epoch.unbonding_delay_epochs;
+
+        self.update_bond(&validator_id, |validator, epoch| {
+            let amount = tx.validate(&self.chain_spec.chain_id, epoch, &validator.pk_ed25519)?;
+            let expected = validator.bond.nonce + 1;
+            if tx.nonce != expected {
+                return Err(TxError::NonceMismatch { expected, found: tx.nonce }.into());
+            }
+
+            let (amount, release_epoch) = match tx.action {
+                BondAction::Deposit => {
+                    validator.deposit_bond(amount)?;
+                    (amount, None)
+                }
+                BondAction::Unbond => {
+                    validator.unbond(amount, epoch + delay)?;
+                    (amount, Some(epoch + delay))
+                }
+                BondAction::Withdraw => (validator.withdraw_bond()?, None),
+            };
+            validator.bond.nonce = expected;
+            Ok(BondReceipt { action: tx.action, amount, stake_bond: validator.stake_bond, release_epoch })
+        })
+        .await
+    }
+
+    /// Deposit or top up a validator's bond; returns the new bonded amount
+    ///
+    /// Unauthenticated: network requests go through [`Self::submit_bond_tx`].
+    /// The weight's stake term follows at the next `apply_poi_carry`.
+    pub async fn deposit_bond(&self, validator_id: &ValidatorId, amount: u128) -> Result<u128> {
+        self.update_bond(validator_id, |validator, _| {
+            validator.deposit_bond(amount)?;
+            Ok(validator.stake_bond)
+        })
+        .await
+    }
+
+    /// Start unbonding part of a validator's bond; returns the release epoch
+    pub async fn unbond(&self, validator_id: &ValidatorId, amount: u128) -> Result<u64> {
+        let delay = self.chain_spec.epoch.unbonding_delay_epochs;
+        self.update_bond(validator_id, |validator, epoch| {
+            validator.unbond(amount, epoch + delay)?;
+            Ok(epoch + delay)
+        })
+        .await
+    }
+
+    /// Withdraw a validator's released bond funds; returns the amount paid out
+    pub async fn withdraw_bond(&self, validator_id: &ValidatorId) -> Result<u128> {
+        self.update_bond(validator_id, |validator, _| Ok(validator.withdraw_bond()?)).await
+    }
+
+    /// Total bond burned by slashing across all validators
+    pub async fn total_burned(&self) -> u128 {
+        self.validators.read().await.values().map(|v| v.bond.burned).sum()
+    }
+
+    async fn update_bond<T>(
+        &self,
+        validator_id: &ValidatorId,
+        op: impl FnOnce(&mut ValidatorRecord, u64) -> Result<T>,
+    ) -> Result<T> {
+        self.transact(|state| {
+            let validator = state
+                .validators
+                .get_mut(validator_id)
+                .ok_or_else(|| anyhow::anyhow!("Validator not found: {}", hex::encode(validator_id)))?;
+            op(validator, state.epoch)
+        })
+        .await
+    }
+
+    /// Key-theft evidence for a consensus message signed by a rotated-out key
+    ///
+    /// For messages whose signature does not verify against the signer's
+    /// current key; the network's gossip validation passes those here through
+    /// its validator key lookup. Returns None unless one of the signer's keys
+    /// rotated out at or before the epoch of the message's view made the
+    /// signature, for this registry's chain.
+    pub async fn retired_key_evidence(&self, signed: &SignedConsensusMessage) -> Option<SlashEvidence> {
+        let validator_id: ValidatorId = signed.message.signer_id().try_into().ok()?;
+        let slot = signed.message.view();
+        let slot_epoch = self.chain_spec.slot_to_epoch(slot);
+
+        let validators = self.validators.read().await;
+        let validator = validators.get(&validator_id)?;
+        let retired = validator
+            .key_history
+            .iter()
+            .find(|k| k.retired_at <= slot_epoch && signed.verify_signature(&k.pk_ed25519))?;
+
+        let proof = KeyTheftProof::retired_key(&retired.pk_ed25519, signed.clone());
+        let evidence = SlashEvidence {
+            validator_id,
+            reason: SlashReason::KeyTheft,
+            slot,
+            proof: serde_json::to_vec(&proof).ok()?,
+        };
+        evidence.verify_for(validator, slot_epoch, &self.chain_spec.chain_id).ok()?;
+        Some(evidence)
+    }
+
+    /// Apply a change to a copy of the registry state and keep it once stored
+    ///
+    /// Changes are serialized by the commit lock. The state is copied under
+    /// every read lock in order, so `op` sees one consistent state, and the
+    /// snapshot is written on a blocking thread with no state lock held, so
+    /// readers are never stalled by disk I/O. The copy replaces the live state
+    /// only after its snapshot was written: if `op` or the store fails, the
+    /// registry is unchanged.
+    async fn transact<T>(&self, op: impl FnOnce(&mut RegistryState) -> Result<T>) -> Result<T> {
+        let _commit = self.commit.lock().await;
+        let mut state = RegistryState {
+            validators: self.validators.read().await.clone(),
+            queue: self.activation_queue.read().await.clone(),
+            epoch: *self.current_epoch.read().await,
+            reputation: self.reputation.read().await.clone(),
+            slashing: self.slashing.read().await.clone(),
+            liveness: self.liveness.read().await.clone(),
+            rewards: self.rewards.read().await.clone(),
+            unchanged: false,
+        };
+        let result = op(&mut state)?;
+        if !state.unchanged {
+            self.persist(&state).await?;
+        }
+
         let mut validators = self.validators.write().await;
-        let validator_id = validator.validator_id;
+        let mut queue = self.activation_queue.write().await;
+        let mut epoch = self.current_epoch.write().await;
+        let mut reputation = self.reputation.write().await;
+        let mut slashing = self.slashing.write().await;
+        let mut liveness = self.liveness.write().await;
+        let mut rewards = self.rewards.write().await;
+        *validators = state.validators;
+        *queue = state.queue;
+        *epoch = state.epoch;
+        *reputation = state.reputation;
+        *slashing = state.slashing;
+        *liveness = state.liveness;
+        *rewards = state.rewards;
+        Ok(result)
+    }
+
+    /// Write a snapshot of the given state, if the registry is persistent
+    async fn persist(&self, state: &RegistryState) -> Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let mut snapshot = RegistrySnapshot::new(
+            self.chain_spec.chain_id.clone(),
+            state.epoch,
+            state.validators.values().cloned().collect(),
+            state.queue.iter().copied().collect(),
+        );
+        snapshot.reputation_history = state.reputation.histories();
+        snapshot.liveness = state.liveness.states();
+        snapshot.slash_events = state.slashing.events.clone();
+        snapshot.rewards = state.rewards.clone();
+
+        let store = store.clone();
+        tokio::task::spawn_blocking(move || store.blocking_lock().save(&snapshot))
+            .await
+            .map_err(|e| anyhow::anyhow!("Registry snapshot write did not complete: {}", e))?
+    }
 
-        if validators.contains_key(&validator_id) {
-            anyhow::bail!("Validator already registered: {:?}", validator_id);
+    /// Validator record as of the end of `epoch`
+    ///
+    /// Past epochs are served from the store; the current epoch from memory.
+    pub async fn validator_at(&self, validator_id: &ValidatorId, epoch: u64) -> Result<Option<ValidatorRecord>> {
+        if epoch >= self.get_epoch().await {
+            return Ok(self.get(validator_id).await);
         }
+        let Some(store) = &self.store else {
+            anyhow::bail!("No registry store: history before the current epoch is not kept");
+        };
+        let snapshot = store.lock().await.snapshot_at(epoch)?;
+        Ok(snapshot.and_then(|s| s.validator(validator_id).cloned()))
+    }
 
-        validators.insert(validator_id, validator);
-        Ok(())
+    /// Validator's `poi_weight` as of the end of `epoch`
+    pub async fn weight_at(&self, validator_id: &ValidatorId, epoch: u64) -> Result<Option<u128>> {
+        Ok(self.validator_at(validator_id, epoch).await?.map(|v| v.poi_weight))
     }
 
     /// Get validator by ID
@@ -45,6 +641,54 @@ impl ValidatorRegistry {
         validators.get(validator_id).cloned()
     }
 
+    /// Find validator by Ed25519 public key
+    pub async fn find_by_pubkey(&self, pk_ed25519: &Ed25519PublicKey) -> Option<ValidatorRecord> {
+        let validators = self.validators.read().await;
+        validators.values().find(|v| &v.pk_ed25519 == pk_ed25519).cloned()
+    }
+
+    /// Recompute `poi_weight` of weight-counting validators at an epoch boundary
+    ///
+    /// `poi_carry` holds each validator's aggregated PoI carry in bps (missing
+    /// entries count as zero). Returns the number of validators updated.
+    /// Weights that changed are announced in validator id order.
+    pub async fn apply_poi_carry(
+        &self,
+        poi_carry: &HashMap<ValidatorId, u128>,
+        calculator: &PoIWeightCalculator,
+    ) -> Result<usize> {
+        let (updated, mut changed) = self
+            .transact(|state| {
+                let (updated, changed) = Self::update_weights(state, poi_carry, calculator);
+                state.unchanged = changed.is_empty();
+                Ok((updated, changed))
+            })
+            .await?;
+        changed.sort_by_key(|e| e.validator_id().copied());
+        self.emit(changed);
+        Ok(updated)
+    }
+
+    /// Recompute `poi_weight` in `state`, with the events for weights that changed
+    fn update_weights(
+        state: &mut RegistryState,
+        poi_carry: &HashMap<ValidatorId, u128>,
+        calculator: &PoIWeightCalculator,
+    ) -> (usize, Vec<RegistryEvent>) {
+        let mut updated = 0;
+        let mut changed = Vec::new();
+        for validator in state.validators.values_mut().filter(|v| v.status.weight_counts()) {
+            let carry = poi_carry.get(&validator.validator_id).copied().unwrap_or(0);
+            let poi_weight = calculator.calculate_weight(carry, validator.rep_score, validator.stake_bond);
+            if poi_weight != validator.poi_weight {
+                changed.push(RegistryEvent::WeightUpdated { validator_id: validator.validator_id, poi_weight });
+            }
+            validator.poi_weight = poi_weight;
+            updated += 1;
+        }
+        (updated, changed)
+    }
+
     /// Get active validator count
     pub async fn active_count(&self) -> usize {
         let validators = self.validators.read().await;
@@ -66,6 +710,7 @@ impl ValidatorRegistry {
 
     /// Update current epoch
     pub async fn set_epoch(&self, epoch: u64) {
+        let _commit = self.commit.lock().await;
         let mut current_epoch = self.current_epoch.write().await;
         *current_epoch = epoch;
     }
@@ -75,6 +720,484 @@ impl ValidatorRegistry {
         *self.current_epoch.read().await
     }
 
+    /// Verify and apply slash evidence
+    ///
+    /// Rejections are [`SlashError`]s. Evidence is deduplicated per validator,
+    /// reason and slot; a validator already in quarantine cannot be slashed again.
+    pub async fn submit_slash_evidence(&self, evidence: SlashEvidence) -> Result<SlashEvent> {
+        self.reexecute_claim(&evidence).await?;
+        let id_hex = hex::encode(evidence.validator_id);
+
+        let event = self
+            .transact(|state| {
+                let epoch = state.epoch;
+                let dedup_key = evidence.dedup_key();
+                if state.slashing.seen.contains(&dedup_key) {
+                    return Err(
+                        SlashError::Duplicate { validator: id_hex.clone(), reason: evidence.reason, slot: evidence.slot }
+                            .into(),
+                    );
+                }
+
+                let slot_epoch = self.chain_spec.slot_to_epoch(evidence.slot);
+                if slot_epoch > epoch {
+                    return Err(SlashError::FutureSlot { slot: evidence.slot, current_epoch: epoch }.into());
+                }
+
+                let validator = state
+                    .validators
+                    .get_mut(&evidence.validator_id)
+                    .ok_or_else(|| SlashError::UnknownValidator(id_hex.clone()))?;
+                if validator.status == ValidatorStatus::Slashed {
+                    return Err(SlashError::AlreadySlashed(id_hex.clone()).into());
+                }
+                evidence.verify_for(validator, slot_epoch, &self.chain_spec.chain_id)?;
+
+                let policy = SlashingPolicy::for_reason(evidence.reason);
+                let previous_status = validator.status;
+                let (weight, burned, rep) = (validator.poi_weight, validator.bond.burned, validator.rep_score);
+                validator
+                    .slash(policy.weight_factor, policy.bond_factor, 0)
+                    .map_err(anyhow::Error::msg)?;
+                validator.rep_score = state.reputation.apply_delta(
+                    evidence.validator_id,
+                    rep,
+                    -(policy.rep_penalty as i64),
+                    ReputationReason::Slashed(evidence.reason),
+                    epoch,
+                );
+                validator.quarantine_until = Some(epoch + policy.quarantine_epochs);
+
+                let event = SlashEvent {
+                    validator_id: evidence.validator_id,
+                    reason: evidence.reason,
+                    slot: evidence.slot,
+                    epoch,
+                    previous_status,
+                    weight_slashed: weight - validator.poi_weight,
+                    bond_burned: validator.bond.burned - burned,
+                    rep_penalty: rep - validator.rep_score,
+                    quarantine_until: epoch + policy.quarantine_epochs,
+                };
+
+                if previous_status == ValidatorStatus::Pending {
+                    state.queue.retain(|id| id != &evidence.validator_id);
+                }
+                state.slashing.seen.insert(dedup_key);
+                state.slashing.events.push(event.clone());
+                Ok(event)
+            })
+            .await?;
+        self.emit([RegistryEvent::Slashed(event.clone())]);
+
+        warn!(
+            validator = %id_hex,
+            reason = ?event.reason,
+            slot = event.slot,
+            quarantine_until = event.quarantine_until,
+            "Validator slashed"
+        );
+
+        Ok(event)
+    }
+
+    /// Check claims that signatures alone cannot prove
+    ///
+    /// Censorship is rejected outright. A `FraudulentPoI` claim is re-run on
+    /// the proof's evidence pack and must fail again; the signature is checked
+    /// first so forged proofs never reach a benchmark run.
+    async fn reexecute_claim(&self, evidence: &SlashEvidence) -> Result<(), SlashError> {
+        let invalid = |detail: String| SlashError::InvalidProof { reason: evidence.reason, detail };
+        match evidence.reason {
+            SlashReason::Censorship => return Err(SlashError::Unverifiable(evidence.reason)),
+            SlashReason::FraudulentPoI => {}
+            SlashReason::Equivocation | SlashReason::KeyTheft => return Ok(()),
+        }
+        let Some(verifier) = self.benchmarks.clone() else {
+            return Err(SlashError::Unverifiable(evidence.reason));
+        };
+
+        let validator = self
+            .get(&evidence.validator_id)
+            .await
+            .ok_or_else(|| SlashError::UnknownValidator(hex::encode(evidence.validator_id)))?;
+        evidence.verify_for(&validator, self.chain_spec.slot_to_epoch(evidence.slot), &self.chain_spec.chain_id)?;
+
+        let proof: FraudulentPoIProof = serde_json::from_slice(&evidence.proof).map_err(|e| invalid(e.to_string()))?;
+        let pack_bytes = hex::decode(&proof.evidence_pack).map_err(|e| invalid(format!("evidence_pack: {}", e)))?;
+        let pack = EvidencePack::from_bytes(pack_bytes).map_err(|e| invalid(e.to_string()))?;
+        // A pack that is not the attested one proves nothing about the claim
+        pack.verify_against(&proof.attestation.evidence).map_err(|e| invalid(e.to_string()))?;
+
+        let verdict = tokio::task::spawn_blocking(move || verifier.verify(&proof.attestation, &pack))
+            .await
+            .map_err(|e| invalid(format!("benchmark run failed: {}", e)))?
+            .map_err(|e| invalid(format!("re-execution inconclusive: {}", e)))?;
+        if !verdict.is_unreproduced() {
+            return Err(invalid("claim reproduced on re-execution".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Applied slashes, oldest first
+    pub async fn slash_events(&self) -> Vec<SlashEvent> {
+        self.slashing.read().await.events.clone()
+    }
+
+    /// Record that a validator signed at `slot` (from consensus or gossip)
+    ///
+    /// Returns true if the validator was Offline and is now Active again. Only
+    /// a revival writes a snapshot: other seen slots change memory under the
+    /// commit lock and are stored with the next snapshot. Slots seen since the
+    /// last snapshot are lost on restart; liveness then resumes from the stored
+    /// slots and is rebuilt as validators sign again.
+    pub async fn record_liveness(&self, validator_id: &ValidatorId, slot: u64) -> Result<bool> {
+        let unknown = || anyhow::anyhow!("Unknown validator: {}", hex::encode(validator_id));
+        {
+            let _commit = self.commit.lock().await;
+            let mut validators = self.validators.write().await;
+            let validator = validators.get_mut(validator_id).ok_or_else(unknown)?;
+            if validator.status != ValidatorStatus::Offline {
+                validator.mark_seen(slot);
+                self.liveness.write().await.record_seen(validator_id);
+                return Ok(false);
+            }
+        }
+
+        let revived = self
+            .transact(|state| {
+                let validator = state.validators.get_mut(validator_id).ok_or_else(unknown)?;
+                validator.mark_seen(slot);
+                state.liveness.record_seen(validator_id);
+                Ok(validator.revive().is_ok())
+            })
+            .await?;
+        if revived {
+            self.emit([RegistryEvent::Revived { validator_id: *validator_id }]);
+        }
+        Ok(revived)
+    }
+
+    /// Check every serving validator at `current_slot`
+    ///
+    /// Deducts newly due liveness penalties from reputation and moves Active
+    /// validators past the offline threshold to Offline.
+    pub async fn process_liveness(&self, current_slot: u64) -> Result<LivenessReport> {
+        let report = self
+            .transact(|state| {
+                let epoch = state.epoch;
+                let mut report = LivenessReport { slot: current_slot, ..Default::default() };
+
+                let mut ids: Vec<ValidatorId> = state.validators.keys().copied().collect();
+                ids.sort_unstable();
+
+                for id in ids {
+                    let validator = state.validators.get_mut(&id).expect("id from key set");
+                    match validator.status {
+                        ValidatorStatus::Active | ValidatorStatus::Exiting => {}
+                        ValidatorStatus::Offline => continue,
+                        _ => {
+                            state.liveness.forget(&id);
+                            continue;
+                        }
+                    }
+
+                    let check = state.liveness.check(validator, current_slot);
+                    if check.penalty > 0 {
+                        validator.rep_score = state.reputation.apply_delta(
+                            id,
+                            validator.rep_score,
+                            -(check.penalty as i64),
+                            ReputationReason::LivenessMiss,
+                            epoch,
+                        );
+                        report.penalized.push((id, check.penalty));
+                    }
+                    if check.offline && validator.mark_offline().is_ok() {
+                        warn!(validator = %hex::encode(id), missed_slots = check.missed_slots, "Validator offline");
+                        report.went_offline.push(id);
+                    }
+                }
+
+                state.unchanged = report.penalized.is_empty() && report.went_offline.is_empty();
+                Ok(report)
+            })
+            .await?;
+        self.emit(report.went_offline.iter().map(|&validator_id| RegistryEvent::WentOffline { validator_id }));
+        Ok(report)
+    }
+
+    /// Serving validators that missed liveness but are not offline yet, most missed first
+    pub async fn at_risk_validators(&self, current_slot: u64) -> Vec<AtRiskValidator> {
+        let validators = self.validators.read().await;
+        let liveness = self.liveness.read().await;
+
+        let mut at_risk: Vec<AtRiskValidator> = validators
+            .values()
+            .filter(|v| v.status.weight_counts())
+            .filter_map(|v| liveness.at_risk(v, current_slot))
+            .collect();
+        at_risk.sort_by(|a, b| {
+            b.missed_slots
+                .cmp(&a.missed_slots)
+                .then_with(|| a.validator_id.cmp(&b.validator_id))
+        });
+        at_risk
+    }
+
+    /// Apply the configured reputation reward or penalty for `reason`
+    ///
+    /// Only validators in the active set earn or lose reputation this way.
+    /// The NAPI layer records [`ReputationReason::TimelyAttestation`] for the
+    /// first attestation a validator gets credited in an epoch. Returns the
+    /// validator's new score.
+    pub async fn record_reputation(&self, validator_id: &ValidatorId, reason: ReputationReason) -> Result<u64> {
+        let rep_score = self
+            .transact(|state| {
+                let validator = state
+                    .validators
+                    .get_mut(validator_id)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown validator: {}", hex::encode(validator_id)))?;
+                if !validator.status.is_active_set() {
+                    anyhow::bail!("Validator {} is {}, not in the active set", hex::encode(validator_id), validator.status);
+                }
+
+                validator.rep_score = state.reputation.apply(*validator_id, validator.rep_score, reason, state.epoch);
+                Ok(validator.rep_score)
+            })
+            .await?;
+        self.emit([RegistryEvent::ReputationUpdated { validator_id: *validator_id, reason, rep_score }]);
+        Ok(rep_score)
+    }
+
+    /// Recent reputation changes of a validator, oldest first
+    pub async fn reputation_history(&self, validator_id: &ValidatorId) -> Vec<ReputationChange> {
+        self.reputation.read().await.history(validator_id)
+    }
+
+    /// Unsigned reward manifest for the current (closing) epoch
+    ///
+    /// Rewards go to the active set, by `poi_carry` (bps, as applied by
+    /// `apply_poi_carry`) and weight; `blocks_proposed` comes from consensus.
+    /// Liveness windows missed in the epoch (as counted by the liveness
+    /// tracker) reduce a validator's share. Call before `advance_epoch`.
+    pub async fn compute_rewards(
+        &self,
+        poi_carry: &HashMap<ValidatorId, u128>,
+        blocks_proposed: &HashMap<ValidatorId, u64>,
+    ) -> Result<RewardManifest> {
+        let validators = self.validators.read().await;
+        let epoch = *self.current_epoch.read().await;
+        let liveness = self.liveness.read().await;
+        self.distribute(&validators, epoch, &liveness, poi_carry, blocks_proposed)
+    }
+
+    /// Reward manifest of `epoch` for the given validators and liveness ledger
+    fn distribute(
+        &self,
+        validators: &HashMap<ValidatorId, ValidatorRecord>,
+        epoch: u64,
+        liveness: &LivenessTracker,
+        poi_carry: &HashMap<ValidatorId, u128>,
+        blocks_proposed: &HashMap<ValidatorId, u64>,
+    ) -> Result<RewardManifest> {
+        let inputs: Vec<RewardInput> = validators
+            .values()
+            .filter(|v| v.status.is_active_set())
+            .map(|v| RewardInput {
+                validator_id: v.validator_id,
+                weight: v.poi_weight,
+                poi_carry_bps: poi_carry.get(&v.validator_id).copied().unwrap_or(0),
+                blocks_proposed: blocks_proposed.get(&v.validator_id).copied().unwrap_or(0),
+                liveness_misses: liveness.epoch_misses(&v.validator_id),
+            })
+            .collect();
+
+        Ok(RewardCalculator::from_chain_spec(&self.chain_spec).distribute(&self.chain_spec.chain_id, epoch, &inputs)?)
+    }
+
+    /// Number of Pending validators awaiting activation
+    pub async fn pending_count(&self) -> usize {
+        self.activation_queue.read().await.len()
+    }
+
+    /// Close the current epoch and apply all lifecycle transitions for the next
+    ///
+    /// See the module docs for the order of operations. A persistent registry
+    /// writes the new epoch's snapshot; on a storage error the registry stays
+    /// in the closing epoch and nothing is announced.
+    pub async fn advance_epoch(&self) -> Result<EpochTransition> {
+        let (report, events) = self.transact(|state| self.transition(state)).await?;
+        self.emit(events);
+        Ok(report)
+    }
+
+    /// Close the current epoch in one change: recompute weights, distribute rewards and advance
+    ///
+    /// Applies `poi_carry` as [`Self::apply_poi_carry`] does, distributes the
+    /// closing epoch's rewards as [`Self::compute_rewards`] does (signed with
+    /// `signing_key` if given) and then runs the epoch transition. The
+    /// manifest is kept with the new epoch's snapshot ([`Self::reward_manifest`]).
+    /// If any step or the store fails, the registry stays in the closing epoch
+    /// and nothing is announced.
+    pub async fn close_epoch(
+        &self,
+        poi_carry: &HashMap<ValidatorId, u128>,
+        blocks_proposed: &HashMap<ValidatorId, u64>,
+        calculator: &PoIWeightCalculator,
+        signing_key: Option<&SigningKey>,
+    ) -> Result<EpochClose> {
+        let (close, events) = self
+            .transact(|state| {
+                let (weights_updated, mut events) = Self::update_weights(state, poi_carry, calculator);
+                events.sort_by_key(|e| e.validator_id().copied());
+
+                let mut rewards = self.distribute(&state.validators, state.epoch, &state.liveness, poi_carry, blocks_proposed)?;
+                if let Some(key) = signing_key {
+                    rewards.sign(key)?;
+                }
+                state.rewards = Some(rewards.clone());
+
+                let (transition, transition_events) = self.transition(state)?;
+                events.extend(transition_events);
+                Ok((EpochClose { weights_updated, rewards, transition }, events))
+            })
+            .await?;
+        self.emit(events);
+        Ok(close)
+    }
+
+    /// Reward manifest of an epoch closed with [`Self::close_epoch`]
+    ///
+    /// The last one is kept in memory; earlier ones are read from the store.
+    /// None for epochs not closed yet or closed without rewards.
+    pub async fn reward_manifest(&self, epoch: u64) -> Result<Option<RewardManifest>> {
+        if let Some(manifest) = self.rewards.read().await.as_ref().filter(|m| m.epoch == epoch) {
+            return Ok(Some(manifest.clone()));
+        }
+        if epoch >= self.get_epoch().await {
+            return Ok(None);
+        }
+        let Some(store) = &self.store else {
+            anyhow::bail!("No registry store: history before the current epoch is not kept");
+        };
+        let snapshot = store.lock().await.snapshot_at(epoch + 1)?;
+        Ok(snapshot.and_then(|s| s.rewards).filter(|m| m.epoch == epoch))
+    }
+
+    /// Apply one epoch transition to `state`, with the events announcing it
+    fn transition(&self, state: &mut RegistryState) -> Result<(EpochTransition, Vec<RegistryEvent>)> {
+        let validators = &mut state.validators;
+        let queue = &mut state.queue;
+        let reputation = &mut state.reputation;
+
+        let from_epoch = state.epoch;
+        let epoch = from_epoch + 1;
+        let thresholds = &self.chain_spec.thresholds;
+        let mut report = EpochTransition { from_epoch, to_epoch: epoch, ..Default::default() };
+        state.liveness.start_epoch();
+
+        // Iterate in id order so the report is deterministic
+        let mut ids: Vec<ValidatorId> = validators.keys().copied().collect();
+        ids.sort_unstable();
+
+        for id in &ids {
+            let validator = validators.get_mut(id).expect("id from key set");
+            if validator.apply_key_rotation(epoch) {
+                report.keys_rotated.push(*id);
+            }
+            validator.prune_key_history(epoch, self.chain_spec.epoch.key_history_epochs);
+            let status = validator.status;
+            if status == ValidatorStatus::Exiting && validator.complete_exit(epoch).is_ok() {
+                report.exited.push(*id);
+            } else if status == ValidatorStatus::Slashed && validator.release_quarantine(epoch).is_ok() {
+                report.released.push(*id);
+            } else if status.is_active_set() {
+                // Not below the activation floor, so a validator demoted by the cap can be reactivated
+                validator.rep_score = reputation.decay_to_floor(
+                    *id,
+                    validator.rep_score,
+                    1,
+                    thresholds.min_reputation_for_activation,
+                    from_epoch,
+                );
+                report.reputation_decayed += 1;
+            }
+
+            // After the status step, so funds of a validator leaving quarantine are released too
+            let released = validator.release_unbonded(epoch);
+            if released > 0 {
+                report.bonds_released.push((*id, released));
+            }
+        }
+
+        // Enforce the cap: demote the lowest-weight Active validators back to the queue head
+        let mut active: Vec<&ValidatorRecord> = validators.values().filter(|v| v.status.is_active_set()).collect();
+        if active.len() > thresholds.max_active_validators {
+            active.sort_by(|a, b| {
+                a.poi_weight
+                    .cmp(&b.poi_weight)
+                    .then_with(|| a.rep_score.cmp(&b.rep_score))
+                    .then_with(|| b.validator_id.cmp(&a.validator_id))
+            });
+            let excess = active.len() - thresholds.max_active_validators;
+            let demote: Vec<ValidatorId> = active
+                .iter()
+                .filter(|v| v.status == ValidatorStatus::Active)
+                .take(excess)
+                .map(|v| v.validator_id)
+                .collect();
+            for id in demote.iter().rev() {
+                let validator = validators.get_mut(id).expect("id from key set");
+                validator.status = ValidatorStatus::Pending;
+                validator.poi_weight = 0;
+                queue.push_front(*id);
+            }
+            report.deactivated = demote;
+        }
+
+        let active_count = validators.values().filter(|v| v.status.is_active_set()).count();
+        report.churn_limit = crate::churn_limit(active_count);
+        let budget = report
+            .churn_limit
+            .min(thresholds.max_active_validators.saturating_sub(active_count));
+
+        // Drop entries that are no longer Pending (e.g. slashed while queued)
+        queue.retain(|id| validators.get(id).is_some_and(|v| v.status == ValidatorStatus::Pending));
+
+        let mut candidates: Vec<(usize, ValidatorId)> = queue.iter().copied().enumerate().collect();
+        if self.activation_order == ActivationOrder::Priority {
+            candidates.sort_by(|(ia, a), (ib, b)| {
+                let (a, b) = (&validators[a], &validators[b]);
+                b.stake_bond
+                    .cmp(&a.stake_bond)
+                    .then_with(|| b.rep_score.cmp(&a.rep_score))
+                    .then_with(|| ia.cmp(ib))
+            });
+        }
+
+        for (_, id) in candidates {
+            if report.activated.len() >= budget {
+                break;
+            }
+            let validator = validators.get_mut(&id).expect("queued id is registered");
+            if validator.rep_score < thresholds.min_reputation_for_activation {
+                continue;
+            }
+            if validator.activate(epoch).is_ok() {
+                report.activated.push(id);
+            }
+        }
+
+        queue.retain(|id| !report.activated.contains(id));
+        report.pending_remaining = queue.len();
+        report.active_count = active_count + report.activated.len();
+        state.epoch = epoch;
+
+        let events = transition_events(&report, validators);
+        Ok((report, events))
+    }
+
     /// Get total effective weight across active validators
     pub async fn total_active_weight(&self) -> u128 {
         let validators = self.validators.read().await;
@@ -86,6 +1209,29 @@ impl ValidatorRegistry {
     }
 }
 
+/// Events of one epoch transition, in the order it applied them
+fn transition_events(
+    report: &EpochTransition,
+    validators: &HashMap<ValidatorId, ValidatorRecord>,
+) -> Vec<RegistryEvent> {
+    let epoch = report.to_epoch;
+    let mut events: Vec<RegistryEvent> = report
+        .keys_rotated
+        .iter()
+        .map(|&validator_id| RegistryEvent::KeyRotated { validator_id, pk_ed25519: validators[&validator_id].pk_ed25519 })
+        .collect();
+    events.extend(report.exited.iter().map(|&validator_id| RegistryEvent::Exited { validator_id, epoch }));
+    events.extend(report.released.iter().map(|&validator_id| RegistryEvent::Released { validator_id, epoch }));
+    events.extend(report.deactivated.iter().map(|&validator_id| RegistryEvent::Deactivated { validator_id, epoch }));
+    events.extend(report.activated.iter().map(|&validator_id| RegistryEvent::Activated { validator_id, epoch }));
+    events.push(RegistryEvent::EpochAdvanced {
+        from_epoch: report.from_epoch,
+        to_epoch: epoch,
+        active_count: report.active_count,
+    });
+    events
+}
+
 impl Default for ValidatorRegistry {
     fn default() -> Self {
         Self::new()
@@ -109,7 +1255,7 @@ mod tests {
         let registry = ValidatorRegistry::new();
 
         let validator = ValidatorRecord::new_pending(
-            [1u8; 32],
+            id(2),
             [2u8; 32],
             "/ip4/127.0.0.1/tcp/9944".to_string(),
             0,
@@ -122,16 +1268,317 @@ mod tests {
         assert_eq!(retrieved.unwrap().validator_id, validator.validator_id);
     }
 
+    #[tokio::test]
+    async fn test_signed_lifecycle_transactions() {
+        use crate::tx::{ExitTx, JoinTx, RotateKeyTx};
+        use ed25519_dalek::SigningKey;
+
+        let registry = ValidatorRegistry::new();
+        let chain = registry.chain_spec().chain_id.clone();
+        let (key, new_key) = (SigningKey::from_bytes(&[9u8; 32]), SigningKey::from_bytes(&[8u8; 32]));
+
+        let mut join = JoinTx::new(chain.clone(), &key, "/ip4/127.0.0.1/tcp/9944", Some(5_000), 0);
+        join.sign(&key).unwrap();
+        let validator = registry.submit_join_tx(&join).await.unwrap();
+        assert_eq!(validator.validator_id, validator_id_for(&key.verifying_key().to_bytes()));
+        // The declared bond is not credited
+        assert_eq!((validator.status, validator.stake_bond), (ValidatorStatus::Pending, 0));
+        assert!(registry.submit_join_tx(&join).await.is_err()); // Already registered
+        assert_eq!(registry.deposit_bond(&validator.validator_id, 5_000).await.unwrap(), 5_000);
+
+        // Mismatched id/key pairs are rejected at registration too
+        let err = registry.register(ValidatorRecord::new_pending(id(1), [2u8; 32], String::new(), 0)).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<TxError>(), Some(TxError::IdKeyMismatch { .. })));
+
+        registry.advance_epoch().await.unwrap();
+        let vid = validator.validator_id;
+
+        let mut rotate = RotateKeyTx::new(chain.clone(), &vid, &new_key.verifying_key().to_bytes(), 1);
+        rotate.sign(&key, &new_key).unwrap();
+        let scheduled = registry.submit_rotate_key_tx(&rotate).await.unwrap();
+        assert_eq!(scheduled.pending_key.unwrap().activation_epoch, 3);
+        assert_eq!(scheduled.pk_ed25519, key.verifying_key().to_bytes()); // Old key serves until then
+
+        registry.advance_epoch().await.unwrap();
+        assert_eq!(registry.advance_epoch().await.unwrap().keys_rotated, vec![vid]);
+        let rotated = registry.get(&vid).await.unwrap();
+        assert_eq!(rotated.pk_ed25519, new_key.verifying_key().to_bytes());
+        assert_eq!(rotated.key_history[0].retired_at, 3);
+
+        // The old key no longer authorizes an exit
+        let mut exit = ExitTx::new(chain.clone(), &vid, 3);
+        exit.sign(&key).unwrap();
+        let err = registry.submit_exit_tx(&exit).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<TxError>(), Some(TxError::BadSignature { .. })));
+
+        exit.sign(&new_key).unwrap();
+        let exiting = registry.submit_exit_tx(&exit).await.unwrap();
+        assert_eq!(exiting.status, ValidatorStatus::Exiting);
+        assert_eq!(exiting.epoch_exit, Some(3 + registry.chain_spec().epoch.unbonding_delay_epochs));
+        assert_eq!((exiting.stake_bond, exiting.bond.unbonding[0].release_epoch), (0, exiting.epoch_exit.unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_keys_in_use_and_allows_rejoin() {
+        use crate::tx::{JoinTx, RotateKeyTx};
+        use ed25519_dalek::SigningKey;
+
+        let registry = ValidatorRegistry::new();
+        let chain = registry.chain_spec().chain_id.clone();
+        let (key, new_key) = (SigningKey::from_bytes(&[9u8; 32]), SigningKey::from_bytes(&[8u8; 32]));
+        let mut join = JoinTx::new(chain.clone(), &key, "/ip4/127.0.0.1/tcp/9944", None, 0);
+        join.sign(&key).unwrap();
+        let vid = registry.submit_join_tx(&join).await.unwrap().validator_id;
+
+        // A key announced for rotation cannot be registered by anyone else
+        let mut rotate = RotateKeyTx::new(chain.clone(), &vid, &new_key.verifying_key().to_bytes(), 0);
+        rotate.sign(&key, &new_key).unwrap();
+        registry.submit_rotate_key_tx(&rotate).await.unwrap();
+        let new_pk = new_key.verifying_key().to_bytes();
+        let err = registry.register(ValidatorRecord::new_pending(validator_id_for(&new_pk), new_pk, String::new(), 0)).await;
+        assert!(err.unwrap_err().to_string().contains("registered to another validator"));
+
+        // Leaves, then rejoins under its (rotated) current key as the same validator
+        registry.advance_epoch().await.unwrap();
+        registry.advance_epoch().await.unwrap(); // Key rotated
+        registry.validators.write().await.get_mut(&vid).unwrap().status = ValidatorStatus::Exited;
+        assert!(registry.submit_join_tx(&join).await.is_err()); // Under its old key
+
+        let mut rejoin = JoinTx::new(chain, &new_key, "/ip4/127.0.0.1/tcp/9945", None, 2);
+        rejoin.sign(&new_key).unwrap();
+        let rejoined = registry.submit_join_tx(&rejoin).await.unwrap();
+        assert_eq!((rejoined.validator_id, rejoined.status), (vid, ValidatorStatus::Pending));
+        assert_eq!((rejoined.epoch_join, rejoined.key_history.len()), (2, 1));
+        assert_eq!(registry.pending_count().await, 1);
+        assert!(registry.get(&validator_id_for(&new_pk)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retired_key_signature_is_key_theft() {
+        use crate::consensus_message::ConsensusMessage;
+        use crate::tx::RotateKeyTx;
+        use ed25519_dalek::SigningKey;
+
+        let registry = ValidatorRegistry::new();
+        let (key, new_key) = (SigningKey::from_bytes(&[9u8; 32]), SigningKey::from_bytes(&[8u8; 32]));
+        let (old_pk, new_pk) = (key.verifying_key().to_bytes(), new_key.verifying_key().to_bytes());
+        let vid = validator_id_for(&old_pk);
+        let mut validator = ValidatorRecord::new_pending(vid, old_pk, String::new(), 0);
+        validator.activate(0).unwrap();
+        registry.register(validator).await.unwrap();
+
+        let mut rotate = RotateKeyTx::new(registry.chain_spec().chain_id.clone(), &vid, &new_pk, 0);
+        rotate.sign(&key, &new_key).unwrap();
+        registry.submit_rotate_key_tx(&rotate).await.unwrap();
+        assert!(registry.submit_rotate_key_tx(&rotate).await.is_err()); // Already pending
+        registry.advance_epoch().await.unwrap();
+        registry.advance_epoch().await.unwrap(); // Rotated at epoch 2
+
+        let slots = registry.chain_spec().epoch.epoch_duration_slots;
+        let chain_id = registry.chain_spec().chain_id.clone();
+        let timeout = |view: u64, key: &SigningKey| {
+            let message = ConsensusMessage::Timeout { view, validator_id: vid.to_vec(), signature: Vec::new() };
+            SignedConsensusMessage::new(message, key, &chain_id, 1_000)
+        };
+
+        // Signed while the old key was still valid: not evidence
+        assert!(registry.retired_key_evidence(&timeout(slots, &key)).await.is_none());
+        // Signed by the current key: not evidence
+        assert!(registry.retired_key_evidence(&timeout(2 * slots, &new_key)).await.is_none());
+
+        // Signed for another chain: not evidence here
+        let mut foreign = timeout(2 * slots, &key);
+        foreign.message.sign(&key, "bizra-other", 1_000);
+        foreign.chain_id = "bizra-other".to_string();
+        assert!(registry.retired_key_evidence(&foreign).await.is_none());
+
+        let evidence = registry.retired_key_evidence(&timeout(2 * slots, &key)).await.unwrap();
+        assert_eq!((evidence.validator_id, evidence.reason), (vid, crate::slashing::SlashReason::KeyTheft));
+
+        let event = registry.submit_slash_evidence(evidence).await.unwrap();
+        assert_eq!(event.previous_status, ValidatorStatus::Active);
+        let slashed = registry.get(&vid).await.unwrap();
+        assert_eq!(slashed.status, ValidatorStatus::Slashed);
+        assert_eq!(slashed.pk_ed25519, new_pk);
+    }
+
+    #[tokio::test]
+    async fn test_first_key_theft_slashable_after_many_rotations() {
+        use crate::consensus_message::ConsensusMessage;
+        use crate::tx::RotateKeyTx;
+        use ed25519_dalek::SigningKey;
+
+        let registry = ValidatorRegistry::new();
+        let first = SigningKey::from_bytes(&[9u8; 32]);
+        let vid = validator_id_for(&first.verifying_key().to_bytes());
+        let mut validator = ValidatorRecord::new_pending(vid, first.verifying_key().to_bytes(), String::new(), 0);
+        validator.activate(0).unwrap();
+        registry.register(validator).await.unwrap();
+
+        let mut key = first.clone();
+        for seed in 100..117u8 {
+            let new_key = SigningKey::from_bytes(&[seed; 32]);
+            let epoch = registry.get_epoch().await;
+            let mut rotate = RotateKeyTx::new(registry.chain_spec().chain_id.clone(), &vid, &new_key.verifying_key().to_bytes(), epoch);
+            rotate.sign(&key, &new_key).unwrap();
+            registry.submit_rotate_key_tx(&rotate).await.unwrap();
+            for _ in 0..registry.chain_spec().epoch.key_rotation_delay_epochs {
+                registry.advance_epoch().await.unwrap();
+            }
+            key = new_key;
+        }
+        let rotated = registry.get(&vid).await.unwrap();
+        assert_eq!((rotated.pk_ed25519, rotated.key_history.len()), (key.verifying_key().to_bytes(), 17));
+
+        let view = registry.get_epoch().await * registry.chain_spec().epoch.epoch_duration_slots;
+        let message = ConsensusMessage::Timeout { view, validator_id: vid.to_vec(), signature: Vec::new() };
+        let stolen = SignedConsensusMessage::new(message, &first, &registry.chain_spec().chain_id, 1_000);
+        let evidence = registry.retired_key_evidence(&stolen).await.unwrap();
+        registry.submit_slash_evidence(evidence).await.unwrap();
+        assert_eq!(registry.get(&vid).await.unwrap().status, ValidatorStatus::Slashed);
+    }
+
+    #[tokio::test]
+    async fn test_bond_ledger_unbonding_and_slashing() {
+        let (registry, key, vid) = registry_with_signer(ValidatorStatus::Active).await; // 10,000 bonded, epoch 3
+        let calculator = PoIWeightCalculator::new();
+        let weight = |stake| calculator.calculate_weight(0, 500, stake);
+
+        assert_eq!(registry.deposit_bond(&vid, 6_000).await.unwrap(), 16_000);
+        registry.apply_poi_carry(&HashMap::new(), &calculator).await.unwrap();
+        assert_eq!(registry.get(&vid).await.unwrap().poi_weight, weight(16_000));
+
+        assert_eq!(registry.unbond(&vid, 6_000).await.unwrap(), 5);
+        assert!(registry.unbond(&vid, 20_000).await.is_err());
+        registry.apply_poi_carry(&HashMap::new(), &calculator).await.unwrap();
+        assert_eq!(registry.get(&vid).await.unwrap().poi_weight, weight(10_000));
+
+        // KeyTheft burns 10% of the bonded and the unbonding amounts
+        let event = registry.submit_slash_evidence(key_theft_evidence(&key, vid, 100)).await.unwrap();
+        assert_eq!(event.bond_burned, 1_600);
+        assert_eq!(registry.total_burned().await, 1_600);
+
+        // Release epoch 5 passes while quarantined (until 4) → released with the quarantine
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!(report.released, vec![vid]);
+        assert!(report.bonds_released.is_empty());
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!(report.bonds_released, vec![(vid, 5_400)]);
+
+        assert_eq!(registry.withdraw_bond(&vid).await.unwrap(), 5_400);
+        assert!(registry.withdraw_bond(&vid).await.is_err());
+        let validator = registry.get(&vid).await.unwrap();
+        assert_eq!(validator.stake_bond + validator.bond.withdrawn + validator.bond.burned, 16_000);
+    }
+
+    #[tokio::test]
+    async fn test_signed_bond_transactions() {
+        let (registry, key, vid) = registry_with_signer(ValidatorStatus::Active).await; // 10,000 bonded, epoch 3
+        let chain = registry.chain_spec().chain_id.clone();
+        let signed = |action, amount, nonce| {
+            let mut tx = BondTx::new(chain.clone(), &vid, action, amount, nonce, 3);
+            tx.sign(&key).unwrap();
+            tx
+        };
+
+        let deposit = signed(BondAction::Deposit, Some(500), 1);
+        assert_eq!(registry.submit_bond_tx(&deposit).await.unwrap().stake_bond, 10_500);
+        // Replaying the same signed deposit is rejected
+        let err = registry.submit_bond_tx(&deposit).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<TxError>(), Some(&TxError::NonceMismatch { expected: 2, found: 1 }));
+
+        // Someone else's key cannot move the bond
+        let mut forged = signed(BondAction::Unbond, Some(10_500), 2);
+        forged.sign(&ed25519_dalek::SigningKey::from_bytes(&[7u8; 32])).unwrap();
+        assert!(matches!(registry.submit_bond_tx(&forged).await.unwrap_err().downcast_ref::<TxError>(), Some(TxError::BadSignature { .. })));
+
+        let receipt = registry.submit_bond_tx(&signed(BondAction::Unbond, Some(500), 2)).await.unwrap();
+        assert_eq!((receipt.stake_bond, receipt.release_epoch), (10_000, Some(5)));
+
+        // A rejected operation does not use up the nonce
+        assert!(registry.submit_bond_tx(&signed(BondAction::Withdraw, None, 3)).await.is_err());
+        registry.advance_epoch().await.unwrap();
+        registry.advance_epoch().await.unwrap();
+        let mut withdraw = signed(BondAction::Withdraw, None, 3);
+        withdraw.epoch = 5;
+        withdraw.sign(&key).unwrap();
+        assert_eq!(registry.submit_bond_tx(&withdraw).await.unwrap().amount, 500);
+        assert_eq!(registry.get(&vid).await.unwrap().bond.nonce, 3);
+    }
+
+    #[tokio::test]
+    async fn test_compute_rewards_for_active_set() {
+        let registry = ValidatorRegistry::new();
+        for i in 1..=3 {
+            let mut validator = pending(i);
+            if i < 3 {
+                validator.activate(0).unwrap();
+                validator.rep_score = 5_000;
+                validator.poi_weight = 100;
+            }
+            registry.register(validator).await.unwrap();
+            registry.record_liveness(&id(i), 10).await.unwrap();
+        }
+        registry.record_liveness(&id(2), 100).await.unwrap();
+        registry.process_liveness(100).await.unwrap(); // Validator 1 penalized once
+        registry.process_liveness(100).await.unwrap(); // Repeated sweep, same window
+
+        let carry = HashMap::from([(id(1), 10_000u128), (id(3), 10_000u128)]);
+        let manifest = registry.compute_rewards(&carry, &HashMap::new()).await.unwrap();
+        assert_eq!(manifest.epoch, 0);
+        assert_eq!(manifest.rewards.len(), 2); // Pending validator 3 earns nothing
+
+        let first = manifest.reward_for(&id(1)).unwrap();
+        assert_eq!(first.liveness_misses, 1);
+        assert_eq!((first.bloom, first.bloom_penalty), (9_000, 1_000));
+        assert_eq!((first.seed, first.seed_penalty), (2_250, 250));
+        assert_eq!(manifest.reward_for(&id(2)).unwrap().seed, 2_500);
+        manifest.check_conservation().unwrap();
+
+        // Every window missed counts, also when one sweep covers several
+        registry.record_liveness(&id(2), 250).await.unwrap();
+        registry.process_liveness(10 + 3 * 64 + 1).await.unwrap();
+        let manifest = registry.compute_rewards(&carry, &HashMap::new()).await.unwrap();
+        assert_eq!(manifest.reward_for(&id(1)).unwrap().liveness_misses, 3);
+
+        registry.advance_epoch().await.unwrap();
+        let manifest = registry.compute_rewards(&carry, &HashMap::new()).await.unwrap();
+        assert_eq!(manifest.reward_for(&id(1)).unwrap().liveness_misses, 0);
+    }
+
+    #[tokio::test]
+    async fn test_proposer_rewarded_from_aggregator_counts() {
+        use crate::epoch::EpochAggregator;
+
+        let registry = ValidatorRegistry::new();
+        for i in 1..=2 {
+            let mut validator = pending(i);
+            validator.activate(0).unwrap();
+            validator.poi_weight = 100;
+            registry.register(validator).await.unwrap();
+        }
+
+        let mut aggregator = EpochAggregator::new(0);
+        aggregator.record_proposal(id(1));
+        let manifest = registry.compute_rewards(aggregator.carry(), aggregator.blocks_proposed()).await.unwrap();
+
+        // Equal weight and carry: the proposer share is the only difference
+        let (proposer, other) = (manifest.reward_for(&id(1)).unwrap(), manifest.reward_for(&id(2)).unwrap());
+        assert_eq!((proposer.blocks_proposed, other.blocks_proposed), (1, 0));
+        let proposer_share = registry.chain_spec().rewards.bloom_per_epoch * registry.chain_spec().rewards.proposer_share_bps
+            / crate::poi::BPS_SCALE;
+        assert!(proposer_share > 0);
+        assert_eq!(proposer.bloom, other.bloom + proposer_share);
+    }
+
     #[tokio::test]
     async fn test_active_count() {
         let registry = ValidatorRegistry::new();
 
         // Register 3 validators
         for i in 0..3 {
-            let mut vid = [0u8; 32];
-            vid[0] = i;
             let mut validator = ValidatorRecord::new_pending(
-                vid,
+                id(i),
                 [i; 32],
                 format!("/ip4/127.0.0.{}/tcp/9944", i),
                 0,
@@ -147,4 +1594,668 @@ mod tests {
 
         assert_eq!(registry.active_count().await, 2);
     }
+
+    /// Id bound to the key `[i; 32]`
+    fn id(i: u8) -> ValidatorId {
+        validator_id_for(&[i; 32])
+    }
+
+    fn pending(i: u8) -> ValidatorRecord {
+        ValidatorRecord::new_pending(id(i), [i; 32], String::new(), 0)
+    }
+
+    #[tokio::test]
+    async fn test_advance_epoch_churn_limited_fifo() {
+        let registry = ValidatorRegistry::new();
+        for i in 1..=5 {
+            registry.register(pending(i)).await.unwrap();
+        }
+
+        // churn_limit(0) = 2 per epoch, in registration order
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!((report.from_epoch, report.to_epoch), (0, 1));
+        assert_eq!(report.churn_limit, 2);
+        assert_eq!(report.activated, vec![id(1), id(2)]);
+        assert_eq!(report.pending_remaining, 3);
+        assert_eq!(report.active_count, 2);
+
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!(report.activated, vec![id(3), id(4)]);
+        assert_eq!(report.reputation_decayed, 2);
+        // Decay stops at the activation floor
+        assert_eq!(registry.get(&id(1)).await.unwrap().rep_score, 500);
+        assert_eq!(registry.record_reputation(&id(1), ReputationReason::TimelyAttestation).await.unwrap(), 505);
+        assert!(registry.record_reputation(&id(9), ReputationReason::TimelyAttestation).await.is_err());
+        // Still queued
+        assert!(registry.record_reputation(&id(5), ReputationReason::TimelyAttestation).await.is_err());
+        assert_eq!(registry.get(&id(3)).await.unwrap().epoch_join, 2);
+
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!(report.activated, vec![id(5)]);
+        assert_eq!(registry.pending_count().await, 0);
+        assert_eq!(registry.active_count().await, 5);
+        assert_eq!(registry.get_epoch().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_advance_epoch_priority_order() {
+        let registry = ValidatorRegistry::new().with_activation_order(ActivationOrder::Priority);
+        for i in 1..=4 {
+            let mut validator = pending(i);
+            validator.stake_bond = if i == 3 { 1_000 } else { 0 };
+            validator.rep_score = 500 + i as u64;
+            registry.register(validator).await.unwrap();
+        }
+        let mut low_rep = pending(9);
+        low_rep.rep_score = 100;
+        registry.register(low_rep).await.unwrap();
+
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!(report.activated, vec![id(3), id(4)]);
+
+        // Below the activation threshold: stays queued
+        registry.advance_epoch().await.unwrap();
+        let report = registry.advance_epoch().await.unwrap();
+        assert!(report.activated.is_empty());
+        assert_eq!(report.pending_remaining, 1);
+        assert_eq!(registry.get(&id(9)).await.unwrap().status, ValidatorStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_advance_epoch_exits_and_quarantine() {
+        let registry = ValidatorRegistry::new();
+
+        let mut exiting = pending(1);
+        exiting.activate(0).unwrap();
+        exiting.initiate_exit(0, crate::UNBONDING_DELAY_EPOCHS).unwrap(); // exits at epoch 2
+        registry.register(exiting).await.unwrap();
+
+        let mut slashed = pending(2);
+        slashed.activate(0).unwrap();
+        slashed.slash(0.5, 0.5, 0).unwrap();
+        slashed.quarantine_until = Some(1);
+        registry.register(slashed).await.unwrap();
+
+        let report = registry.advance_epoch().await.unwrap();
+        assert!(report.exited.is_empty());
+        assert_eq!(report.released, vec![id(2)]);
+        assert_eq!(report.reputation_decayed, 1); // Exiting still serves
+        assert_eq!(registry.get(&id(2)).await.unwrap().status, ValidatorStatus::Exited);
+
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!(report.exited, vec![id(1)]);
+        assert_eq!(report.active_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_advance_epoch_caps_active_set() {
+        let mut spec = ChainSpec::testnet();
+        spec.thresholds.max_active_validators = 3;
+        let registry = ValidatorRegistry::with_chain_spec(spec);
+
+        for i in 1..=4 {
+            let mut validator = pending(i);
+            validator.activate(0).unwrap();
+            validator.poi_weight = 100 * i as u128;
+            registry.register(validator).await.unwrap();
+        }
+        registry.register(pending(5)).await.unwrap();
+
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!(report.deactivated, vec![id(1)]);
+        assert!(report.activated.is_empty());
+        assert_eq!(report.active_count, 3);
+
+        // Demoted validator is first in line once capacity frees up
+        let mut queue: Vec<ValidatorId> = registry.activation_queue.read().await.iter().copied().collect();
+        assert_eq!(queue.remove(0), id(1));
+        assert_eq!(registry.get(&id(1)).await.unwrap().status, ValidatorStatus::Pending);
+
+        // Reactivated ahead of the queue once a seat frees up
+        registry.validators.write().await.get_mut(&id(4)).unwrap().status = ValidatorStatus::Exited;
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!(report.activated, vec![id(1)]);
+        assert_eq!(registry.get(&id(1)).await.unwrap().status, ValidatorStatus::Active);
+    }
+
+    fn key_theft_evidence(key: &ed25519_dalek::SigningKey, validator_id: ValidatorId, slot: u64) -> SlashEvidence {
+        use crate::slashing::{key_theft_message, KeyTheftProof, SlashReason};
+        use ed25519_dalek::Signer;
+
+        let proof = KeyTheftProof::statement(&key.sign(&key_theft_message(&validator_id)).to_bytes());
+        SlashEvidence {
+            validator_id,
+            reason: SlashReason::KeyTheft,
+            slot,
+            proof: serde_json::to_vec(&proof).unwrap(),
+        }
+    }
+
+    async fn registry_with_signer(status: ValidatorStatus) -> (ValidatorRegistry, ed25519_dalek::SigningKey, ValidatorId) {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let pk = key.verifying_key().to_bytes();
+        let mut validator = ValidatorRecord::new_pending(validator_id_for(&pk), pk, String::new(), 0);
+        validator.activate(0).unwrap();
+        validator.poi_weight = 1_000;
+        validator.stake_bond = 10_000;
+        if status == ValidatorStatus::Exiting {
+            validator.initiate_exit(0, crate::UNBONDING_DELAY_EPOCHS).unwrap();
+        }
+
+        let registry = ValidatorRegistry::new();
+        registry.register(validator).await.unwrap();
+        registry.set_epoch(3).await;
+        (registry, key, validator_id_for(&pk))
+    }
+
+    #[tokio::test]
+    async fn test_offline_validator_can_exit() {
+        let (registry, key, vid) = registry_with_signer(ValidatorStatus::Active).await;
+        registry.validators.write().await.get_mut(&vid).unwrap().status = ValidatorStatus::Offline;
+
+        let mut exit = ExitTx::new(registry.chain_spec().chain_id.clone(), &vid, 3);
+        exit.sign(&key).unwrap();
+        let exiting = registry.submit_exit_tx(&exit).await.unwrap();
+        assert_eq!(exiting.status, ValidatorStatus::Exiting);
+        assert_eq!(exiting.epoch_exit, Some(3 + registry.chain_spec().epoch.unbonding_delay_epochs));
+        assert_eq!(exiting.poi_weight, 0);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_streams_changes_in_order() {
+        use tokio::sync::broadcast::error::TryRecvError;
+
+        let (registry, key, vid) = registry_with_signer(ValidatorStatus::Active).await;
+        let mut events = registry.subscribe();
+
+        registry.register(pending(1)).await.unwrap();
+        let carry = HashMap::from([(vid, 50_000u128)]);
+        registry.apply_poi_carry(&carry, &PoIWeightCalculator::new()).await.unwrap();
+        let poi_weight = registry.get(&vid).await.unwrap().poi_weight;
+        let rep_score = registry.record_reputation(&vid, ReputationReason::TimelyAttestation).await.unwrap();
+        let slash = registry.submit_slash_evidence(key_theft_evidence(&key, vid, 100)).await.unwrap();
+        registry.advance_epoch().await.unwrap();
+
+        let received: Vec<RegistryEvent> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+        assert_eq!(
+            received,
+            vec![
+                RegistryEvent::Registered { validator_id: id(1), epoch: 3 },
+                RegistryEvent::WeightUpdated { validator_id: vid, poi_weight },
+                RegistryEvent::ReputationUpdated {
+                    validator_id: vid,
+                    reason: ReputationReason::TimelyAttestation,
+                    rep_score,
+                },
+                RegistryEvent::Slashed(slash),
+                RegistryEvent::Released { validator_id: vid, epoch: 4 },
+                RegistryEvent::Activated { validator_id: id(1), epoch: 4 },
+                RegistryEvent::EpochAdvanced { from_epoch: 3, to_epoch: 4, active_count: 1 },
+            ]
+        );
+        assert_eq!(events.try_recv(), Err(TryRecvError::Empty));
+
+        // Events are JSON-tagged by kind
+        let json = serde_json::to_value(&received[1]).unwrap();
+        assert_eq!(json["type"], received[1].kind());
+        assert_eq!(json["poi_weight"], poi_weight as u64);
+    }
+
+    #[tokio::test]
+    async fn test_submit_slash_evidence_applies_policy() {
+        let (registry, key, vid) = registry_with_signer(ValidatorStatus::Active).await;
+
+        let event = registry.submit_slash_evidence(key_theft_evidence(&key, vid, 100)).await.unwrap();
+        assert_eq!(event.previous_status, ValidatorStatus::Active);
+        assert_eq!((event.weight_slashed, event.bond_burned, event.rep_penalty), (950, 1_000, 500));
+        assert_eq!(event.quarantine_until, 4);
+
+        let validator = registry.get(&vid).await.unwrap();
+        assert_eq!(validator.status, ValidatorStatus::Slashed);
+        let history = registry.reputation_history(&vid).await;
+        assert_eq!(history[0].reason, ReputationReason::Slashed(crate::slashing::SlashReason::KeyTheft));
+        assert_eq!(history[0].delta, -500);
+        assert_eq!(validator.quarantine_until, Some(4));
+        assert_eq!(registry.active_count().await, 0);
+        assert_eq!(registry.slash_events().await, vec![event]);
+
+        let report = registry.advance_epoch().await.unwrap();
+        assert_eq!(report.released, vec![vid]);
+    }
+
+    #[tokio::test]
+    async fn test_double_slash_rejected() {
+        let (registry, key, vid) = registry_with_signer(ValidatorStatus::Active).await;
+        let evidence = key_theft_evidence(&key, vid, 100);
+        registry.submit_slash_evidence(evidence.clone()).await.unwrap();
+
+        let err = registry.submit_slash_evidence(evidence).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<SlashError>(), Some(SlashError::Duplicate { slot: 100, .. })));
+
+        // Different offense while quarantined: no second penalty, quarantine unchanged
+        let err = registry.submit_slash_evidence(key_theft_evidence(&key, vid, 101)).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<SlashError>(), Some(SlashError::AlreadySlashed(_))));
+        assert_eq!(registry.slash_events().await.len(), 1);
+        assert_eq!(registry.get(&vid).await.unwrap().rep_score, 0);
+    }
+
+    #[tokio::test]
+    async fn test_slash_exiting_validator() {
+        let (registry, key, vid) = registry_with_signer(ValidatorStatus::Exiting).await;
+        registry.set_epoch(0).await;
+
+        let event = registry.submit_slash_evidence(key_theft_evidence(&key, vid, 10)).await.unwrap();
+        assert_eq!(event.previous_status, ValidatorStatus::Exiting);
+        assert_eq!(event.bond_burned, 1_000);
+
+        // Unbonding would have completed at epoch 2: quarantine takes over instead
+        let report = registry.advance_epoch().await.unwrap();
+        assert!(report.exited.is_empty());
+        assert_eq!(report.released, vec![vid]);
+        assert_eq!(registry.get(&vid).await.unwrap().status, ValidatorStatus::Exited);
+    }
+
+    #[tokio::test]
+    async fn test_slash_evidence_rejections() {
+        let (registry, key, vid) = registry_with_signer(ValidatorStatus::Active).await;
+
+        let unknown = key_theft_evidence(&key, id(2), 10);
+        let err = registry.submit_slash_evidence(unknown).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<SlashError>(), Some(SlashError::UnknownValidator(_))));
+
+        let future = key_theft_evidence(&key, vid, 4 * crate::EPOCH_DURATION_SLOTS);
+        let err = registry.submit_slash_evidence(future).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<SlashError>(), Some(SlashError::FutureSlot { .. })));
+
+        let other_key = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]);
+        let forged = key_theft_evidence(&other_key, vid, 10);
+        let err = registry.submit_slash_evidence(forged).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<SlashError>(), Some(SlashError::InvalidProof { .. })));
+        assert_eq!(registry.get(&vid).await.unwrap().status, ValidatorStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_fraudulent_poi_is_reexecuted_and_censorship_rejected() {
+        use crate::benchmark::{BenchmarkRegistry, BenchmarkRunner, Metrics};
+        use crate::evidence::EvidencePackBuilder;
+        use crate::poi::Benchmarks;
+        use crate::slashing::{CensorshipProof, SlashReason};
+
+        const METHODOLOGY: &str = "doi:10.1234/bizra.bench.filecount.v1";
+
+        /// performance = files / 10
+        struct FileCount;
+
+        impl BenchmarkRunner for FileCount {
+            fn run(&self, evidence_dir: &std::path::Path) -> Result<Metrics> {
+                let files = std::fs::read_dir(evidence_dir)?.count();
+                Ok(Metrics::from([("performance".to_string(), files as f64 / 10.0)]))
+            }
+        }
+
+        let (registry, key, vid) = registry_with_signer(ValidatorStatus::Active).await;
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+        let pack = EvidencePackBuilder::new(dir.path()).build().unwrap();
+        let claim = |post: f64, key: &ed25519_dalek::SigningKey| {
+            let mut attestation = crate::poi::tests::test_attestation();
+            attestation.evidence = pack.to_evidence(Some(METHODOLOGY.to_string()));
+            attestation.benchmarks = Some(Benchmarks {
+                pre: [("performance".to_string(), 0.1)].into(),
+                post: [("performance".to_string(), post)].into(),
+                delta: post - 0.1,
+            });
+            attestation.sign(key).unwrap();
+            attestation
+        };
+        let evidence_for = |attestation: &crate::PoIAttestation| {
+            let proof = FraudulentPoIProof {
+                attestation: attestation.clone(),
+                evidence_pack: hex::encode(pack.as_bytes()),
+                methodology_ref: METHODOLOGY.to_string(),
+                reason: "not reproduced".to_string(),
+                mismatches: Vec::new(),
+            };
+            SlashEvidence { validator_id: vid, reason: SlashReason::FraudulentPoI, slot: 10, proof: serde_json::to_vec(&proof).unwrap() }
+        };
+        let slash_error = |result: Result<SlashEvent>| result.unwrap_err().downcast::<SlashError>().unwrap();
+
+        // Nothing the validator signed shows censorship
+        let censorship = SlashEvidence {
+            validator_id: vid,
+            reason: SlashReason::Censorship,
+            slot: 100,
+            proof: serde_json::to_vec(&CensorshipProof { slots: (10..20).collect() }).unwrap(),
+        };
+        let err = slash_error(registry.submit_slash_evidence(censorship).await);
+        assert_eq!(err, SlashError::Unverifiable(SlashReason::Censorship));
+
+        // No benchmark verifier: the claim cannot be checked
+        let fraud = evidence_for(&claim(0.9, &key));
+        let err = slash_error(registry.submit_slash_evidence(fraud.clone()).await);
+        assert_eq!(err, SlashError::Unverifiable(SlashReason::FraudulentPoI));
+
+        let mut benchmarks = BenchmarkRegistry::new();
+        benchmarks.register(METHODOLOGY, Arc::new(FileCount));
+        let registry = registry.with_benchmark_verifier(BenchmarkVerifier::new(benchmarks));
+
+        // Forged: attestation not signed by the validator
+        let forged = evidence_for(&claim(0.9, &ed25519_dalek::SigningKey::from_bytes(&[3u8; 32])));
+        let err = slash_error(registry.submit_slash_evidence(forged).await);
+        assert!(matches!(err, SlashError::InvalidProof { .. }), "{err}");
+
+        // Honest claim reported as fraudulent
+        let err = slash_error(registry.submit_slash_evidence(evidence_for(&claim(0.3, &key))).await);
+        assert!(err.to_string().contains("reproduced on re-execution"), "{err}");
+        assert_eq!(registry.get(&vid).await.unwrap().status, ValidatorStatus::Active);
+
+        let event = registry.submit_slash_evidence(fraud).await.unwrap();
+        assert_eq!(event.reason, SlashReason::FraudulentPoI);
+        assert_eq!(registry.get(&vid).await.unwrap().status, ValidatorStatus::Slashed);
+    }
+
+    #[tokio::test]
+    async fn test_liveness_penalties_and_offline() {
+        let registry = ValidatorRegistry::new();
+        for i in 1..=2 {
+            let mut validator = pending(i);
+            validator.activate(0).unwrap();
+            validator.rep_score = 5_000;
+            validator.poi_weight = 100;
+            registry.register(validator).await.unwrap();
+            registry.record_liveness(&id(i), 10).await.unwrap();
+        }
+        assert!(registry.record_liveness(&id(9), 10).await.is_err());
+
+        // Validator 2 keeps signing, validator 1 goes silent
+        registry.record_liveness(&id(2), 100).await.unwrap();
+        let report = registry.process_liveness(100).await.unwrap();
+        assert_eq!(report.penalized, vec![(id(1), 100)]);
+
+        let at_risk = registry.at_risk_validators(100).await;
+        assert_eq!(at_risk.len(), 1);
+        assert_eq!((at_risk[0].validator_id, at_risk[0].missed_slots), (id(1), 90));
+
+        registry.record_liveness(&id(2), 1_100).await.unwrap();
+        let report = registry.process_liveness(1_100).await.unwrap();
+        assert_eq!(report.went_offline, vec![id(1)]);
+        assert_eq!(registry.get(&id(1)).await.unwrap().status, ValidatorStatus::Offline);
+        assert_eq!(registry.total_active_weight().await, 100);
+        assert!(registry.at_risk_validators(1_100).await.is_empty());
+
+        let history = registry.reputation_history(&id(1)).await;
+        assert_eq!(history.len(), 2);
+        assert!(history.iter().all(|c| c.reason == ReputationReason::LivenessMiss));
+
+        // Seen again: back to Active with a clean slate
+        assert!(registry.record_liveness(&id(1), 1_101).await.unwrap());
+        assert_eq!(registry.total_active_weight().await, 200);
+        assert!(registry.process_liveness(1_102).await.unwrap().penalized.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_persistent_registry_restores_and_answers_history() {
+        use crate::registry_store::FileRegistryStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let open = || ValidatorRegistry::with_store(ChainSpec::testnet(), Box::new(FileRegistryStore::open(dir.path()).unwrap()));
+
+        let registry = open().unwrap();
+        for i in 1..=3 {
+            registry.register(pending(i)).await.unwrap();
+        }
+        registry.advance_epoch().await.unwrap(); // activates 1, 2
+        registry.apply_poi_carry(&HashMap::from([(id(1), 10_000u128)]), &PoIWeightCalculator::new()).await.unwrap();
+        registry.advance_epoch().await.unwrap(); // activates 3
+        let weight_epoch_2 = registry.get(&id(1)).await.unwrap().poi_weight;
+        drop(registry);
+
+        let restored = open().unwrap();
+        assert_eq!(restored.get_epoch().await, 2);
+        assert_eq!(restored.active_count().await, 3);
+        assert_eq!(restored.pending_count().await, 0);
+
+        assert_eq!(restored.weight_at(&id(1), 0).await.unwrap(), Some(0));
+        // Weights applied during epoch 1 are part of its snapshot
+        assert_eq!(restored.weight_at(&id(1), 1).await.unwrap(), Some(weight_epoch_2));
+        assert_eq!(restored.weight_at(&id(1), 2).await.unwrap(), Some(weight_epoch_2));
+        assert_eq!(restored.validator_at(&id(3), 1).await.unwrap().unwrap().status, ValidatorStatus::Pending);
+        assert!(restored.validator_at(&id(9), 1).await.unwrap().is_none());
+
+        // Registration keeps FIFO order across restarts
+        restored.register(pending(4)).await.unwrap();
+        restored.register(pending(5)).await.unwrap();
+        drop(restored);
+        let restored = open().unwrap();
+        assert_eq!(restored.advance_epoch().await.unwrap().activated, vec![id(4), id(5)]);
+
+        let mut devnet = ChainSpec::testnet();
+        devnet.chain_id = "bizra-devnet".to_string();
+        let err = ValidatorRegistry::with_store(devnet, Box::new(FileRegistryStore::open(dir.path()).unwrap())).err().unwrap();
+        assert!(matches!(err.downcast_ref::<RegistryStoreError>(), Some(RegistryStoreError::ChainMismatch { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_close_epoch_keeps_reward_manifests_across_restarts() {
+        use crate::registry_store::FileRegistryStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let open = || ValidatorRegistry::with_store(ChainSpec::testnet(), Box::new(FileRegistryStore::open(dir.path()).unwrap()));
+        let calculator = PoIWeightCalculator::new();
+        let key = ed25519_dalek::SigningKey::from_bytes(&[5u8; 32]);
+
+        let registry = open().unwrap();
+        for i in 1..=2 {
+            let mut validator = pending(i);
+            validator.activate(0).unwrap();
+            registry.register(validator).await.unwrap();
+        }
+        let carry = HashMap::from([(id(1), 10_000u128)]);
+        let close = registry.close_epoch(&carry, &HashMap::from([(id(2), 3)]), &calculator, Some(&key)).await.unwrap();
+        assert_eq!((close.weights_updated, close.transition.to_epoch, close.rewards.epoch), (2, 1, 0));
+        assert_eq!(close.rewards.reward_for(&id(2)).unwrap().blocks_proposed, 3);
+        assert!(close.rewards.verify().is_ok());
+        assert!(registry.get(&id(1)).await.unwrap().poi_weight > registry.get(&id(2)).await.unwrap().poi_weight);
+        drop(registry);
+
+        let restored = open().unwrap();
+        assert_eq!(restored.reward_manifest(0).await.unwrap(), Some(close.rewards.clone()));
+        assert!(restored.reward_manifest(1).await.unwrap().is_none()); // Still open
+
+        // Earlier manifests come from the store
+        let next = restored.close_epoch(&carry, &HashMap::new(), &calculator, None).await.unwrap();
+        restored.register(pending(3)).await.unwrap();
+        assert_eq!(restored.reward_manifest(0).await.unwrap(), Some(close.rewards));
+        assert_eq!(restored.reward_manifest(1).await.unwrap(), Some(next.rewards));
+    }
+
+    #[tokio::test]
+    async fn test_restart_keeps_slash_and_liveness_ledgers() {
+        use crate::registry_store::FileRegistryStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let open = || {
+            ValidatorRegistry::with_store(ChainSpec::testnet(), Box::new(FileRegistryStore::open(dir.path()).unwrap()))
+                .unwrap()
+        };
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let pk = key.verifying_key().to_bytes();
+        let vid = validator_id_for(&pk);
+
+        let registry = open();
+        let mut signer = ValidatorRecord::new_pending(vid, pk, String::new(), 0);
+        signer.activate(0).unwrap();
+        registry.register(signer).await.unwrap();
+        let mut silent = pending(1);
+        silent.activate(0).unwrap();
+        silent.rep_score = 5_000;
+        registry.register(silent).await.unwrap();
+
+        let evidence = key_theft_evidence(&key, vid, 5);
+        registry.submit_slash_evidence(evidence.clone()).await.unwrap();
+        registry.record_liveness(&id(1), 10).await.unwrap();
+        assert_eq!(registry.process_liveness(100).await.unwrap().penalized, vec![(id(1), 100)]);
+        let history = registry.reputation_history(&id(1)).await;
+        drop(registry);
+
+        let restored = open();
+        assert_eq!(restored.reputation_history(&id(1)).await, history);
+        assert_eq!(restored.slash_events().await.len(), 1);
+        let err = restored.submit_slash_evidence(evidence).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<SlashError>(), Some(SlashError::Duplicate { slot: 5, .. })));
+        // The missed window was already charged before the restart
+        assert!(restored.process_liveness(100).await.unwrap().penalized.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reads_proceed_while_snapshot_is_written() {
+        use crate::registry_store::MemoryRegistryStore;
+        use std::sync::mpsc;
+
+        /// Store whose writes each wait for a token
+        struct GatedStore {
+            inner: MemoryRegistryStore,
+            tokens: std::sync::Mutex<mpsc::Receiver<()>>,
+        }
+
+        impl RegistryStore for GatedStore {
+            fn save(&mut self, snapshot: &RegistrySnapshot) -> Result<()> {
+                self.tokens.lock().unwrap().recv()?;
+                self.inner.save(snapshot)
+            }
+
+            fn latest(&self) -> Result<Option<RegistrySnapshot>> {
+                self.inner.latest()
+            }
+
+            fn snapshot_at(&self, epoch: u64) -> Result<Option<RegistrySnapshot>> {
+                self.inner.snapshot_at(epoch)
+            }
+
+            fn epochs(&self) -> Vec<u64> {
+                self.inner.epochs()
+            }
+        }
+
+        let (tokens, rx) = mpsc::channel();
+        let store = GatedStore { inner: MemoryRegistryStore::new(), tokens: std::sync::Mutex::new(rx) };
+        let registry = Arc::new(ValidatorRegistry::with_store(ChainSpec::testnet(), Box::new(store)).unwrap());
+        tokens.send(()).unwrap();
+        registry.register(pending(1)).await.unwrap();
+
+        let advance = tokio::spawn({
+            let registry = registry.clone();
+            async move { registry.advance_epoch().await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        // The snapshot is being written: reads answer from the unchanged state
+        let read = async { (registry.get_epoch().await, registry.get(&id(1)).await.map(|v| v.status)) };
+        let (epoch, status) = tokio::time::timeout(std::time::Duration::from_secs(5), read).await.unwrap();
+        assert_eq!((epoch, status), (0, Some(ValidatorStatus::Pending)));
+        assert!(!advance.is_finished());
+
+        tokens.send(()).unwrap();
+        advance.await.unwrap().unwrap();
+        assert_eq!(registry.get_epoch().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_persist_leaves_registry_unchanged() {
+        use crate::registry_store::MemoryRegistryStore;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        /// Store whose writes fail while `failing` is set
+        struct FlakyStore {
+            inner: MemoryRegistryStore,
+            failing: Arc<AtomicBool>,
+        }
+
+        impl RegistryStore for FlakyStore {
+            fn save(&mut self, snapshot: &RegistrySnapshot) -> Result<()> {
+                if self.failing.load(Ordering::SeqCst) {
+                    anyhow::bail!("disk full");
+                }
+                self.inner.save(snapshot)
+            }
+
+            fn latest(&self) -> Result<Option<RegistrySnapshot>> {
+                self.inner.latest()
+            }
+
+            fn snapshot_at(&self, epoch: u64) -> Result<Option<RegistrySnapshot>> {
+                self.inner.snapshot_at(epoch)
+            }
+
+            fn epochs(&self) -> Vec<u64> {
+                self.inner.epochs()
+            }
+        }
+
+        let failing = Arc::new(AtomicBool::new(false));
+        let store = FlakyStore { inner: MemoryRegistryStore::new(), failing: failing.clone() };
+        let registry = ValidatorRegistry::with_store(ChainSpec::testnet(), Box::new(store)).unwrap();
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let pk = key.verifying_key().to_bytes();
+        let vid = validator_id_for(&pk);
+        let mut validator = ValidatorRecord::new_pending(vid, pk, String::new(), 0);
+        validator.activate(0).unwrap();
+        registry.register(validator).await.unwrap();
+        registry.deposit_bond(&vid, 1_000).await.unwrap();
+        let mut events = registry.subscribe();
+
+        failing.store(true, Ordering::SeqCst);
+        let before = registry.get(&vid).await.unwrap();
+        assert!(registry.unbond(&vid, 1_000).await.is_err());
+        assert!(registry.register(pending(2)).await.is_err());
+        let evidence = key_theft_evidence(&key, vid, 5);
+        assert!(registry.submit_slash_evidence(evidence.clone()).await.is_err());
+        assert!(registry.advance_epoch().await.is_err());
+        let carry = HashMap::from([(vid, 10_000u128)]);
+        assert!(registry.close_epoch(&carry, &HashMap::new(), &PoIWeightCalculator::new(), None).await.is_err());
+
+        let after = registry.get(&vid).await.unwrap();
+        assert_eq!((after.status, after.stake_bond, after.bond.unbonding.len()), (before.status, before.stake_bond, 0));
+        assert!(registry.get(&id(2)).await.is_none());
+        assert!(registry.slash_events().await.is_empty());
+        assert!(registry.reputation_history(&vid).await.is_empty());
+        assert_eq!(registry.get_epoch().await, 0);
+        assert_eq!(after.poi_weight, before.poi_weight);
+        assert!(registry.reward_manifest(0).await.unwrap().is_none());
+        assert!(events.try_recv().is_err());
+
+        // Nothing was half-applied, so the same evidence is accepted once the store recovers
+        failing.store(false, Ordering::SeqCst);
+        registry.submit_slash_evidence(evidence).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_history_requires_store() {
+        let registry = ValidatorRegistry::new();
+        registry.register(pending(1)).await.unwrap();
+        registry.advance_epoch().await.unwrap();
+        assert!(registry.weight_at(&id(1), 1).await.unwrap().is_some());
+        assert!(registry.weight_at(&id(1), 0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_poi_carry() {
+        let registry = ValidatorRegistry::new();
+
+        let mut active = ValidatorRecord::new_pending(id(11), [11u8; 32], String::new(), 0);
+        active.activate(0).unwrap();
+        registry.register(active).await.unwrap();
+        registry.register(ValidatorRecord::new_pending(id(12), [12u8; 32], String::new(), 0)).await.unwrap();
+
+        let carry = HashMap::from([(id(11), 50_000u128), (id(12), 50_000u128)]);
+        let updated = registry.apply_poi_carry(&carry, &PoIWeightCalculator::new()).await.unwrap();
+        assert_eq!(updated, 1);
+
+        // BASE + 10*5.0 + 0.05*rep
+        let active = registry.find_by_pubkey(&[11u8; 32]).await.unwrap();
+        assert_eq!(active.poi_weight, 100 + 50 + active.rep_score as u128 / 20);
+        assert_eq!(registry.get(&id(12)).await.unwrap().poi_weight, 0);
+    }
 }
@@ -0,0 +1,379 @@
+//! Persistent validator registry storage
+//!
+//! The registry writes a [`RegistrySnapshot`] of every validator record, the
+//! activation queue, the current epoch, the reputation, liveness and
+//! slashing ledgers and the reward manifest of the epoch before on every
+//! change it applies, keyed by epoch; the last write of an epoch is
+//! that epoch's boundary snapshot. Older snapshots stay available for
+//! point-in-time lookups ("what was validator X's weight at epoch N").
+//!
+//! - [`MemoryRegistryStore`] keeps snapshots in process (tests, ephemeral nodes)
+//! - [`FileRegistryStore`] writes one JSON file per epoch, replaced atomically
+//!
+//! ## Schema Versions
+//!
+//! Snapshots carry `schema_version`; snapshots from a newer schema are rejected.
+//! Every version so far only added fields whose serde defaults are the migrated
+//! values, so older snapshots decode straight into the current types. Decoding
+//! is typed end to end: a `serde_json::Value` detour would turn amounts above
+//! `u64::MAX` into floats.
+//!
+//! | Version | Change                                                     |
+//! |---------|------------------------------------------------------------|
+//! | 1       | Initial layout, no `schema_version` field                  |
+//! | 2       | `ValidatorRecord::quarantine_until`, `activation_queue`    |
+//! | 3       | `ValidatorRecord::pending_key`, `key_history`              |
+//! | 4       | `ValidatorRecord::bond` (unbonding queue, burned amount)   |
+//! | 5       | `reputation_history`, `liveness`, `slash_events`           |
+//! | 6       | `BondAccount::nonce`                                       |
+//! | 7       | `LivenessState::epoch_misses`                              |
+//! | 8       | `rewards`                                                  |
+//!
+//! Snapshots before version 5 restore with empty ledgers.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::liveness::LivenessState;
+use crate::reputation::ReputationChange;
+use crate::rewards::RewardManifest;
+use crate::slashing::SlashEvent;
+use crate::types::{ValidatorId, ValidatorRecord};
+
+/// Current snapshot schema version
+pub const REGISTRY_SCHEMA_VERSION: u32 = 8;
+
+/// Subdirectory of a data directory holding registry snapshots
+pub const REGISTRY_SNAPSHOT_DIR: &str = "registry";
+
+/// Registry store failures
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RegistryStoreError {
+    #[error("Registry snapshot schema {found} is newer than supported schema {supported}")]
+    UnsupportedSchema { found: u32, supported: u32 },
+
+    #[error("Corrupt registry snapshot for epoch {epoch}: {reason}")]
+    CorruptSnapshot { epoch: u64, reason: String },
+
+    #[error("Registry snapshot belongs to chain {found}, expected {expected}")]
+    ChainMismatch { expected: String, found: String },
+}
+
+/// Registry state at one epoch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrySnapshot {
+    /// Missing before schema 2
+    #[serde(default = "initial_schema_version")]
+    pub schema_version: u32,
+
+    pub chain_id: String,
+    pub epoch: u64,
+
+    /// All validators, ordered by id
+    pub validators: Vec<ValidatorRecord>,
+
+    /// Pending validators in activation order
+    #[serde(default)]
+    pub activation_queue: Vec<ValidatorId>,
+
+    /// Recent reputation changes per validator, ordered by id
+    #[serde(default)]
+    pub reputation_history: Vec<(ValidatorId, Vec<ReputationChange>)>,
+
+    /// Missed-window state per tracked validator, ordered by id
+    #[serde(default)]
+    pub liveness: Vec<(ValidatorId, LivenessState)>,
+
+    /// Applied slashes, oldest first (their evidence is not accepted again)
+    #[serde(default)]
+    pub slash_events: Vec<SlashEvent>,
+
+    /// Reward manifest of the last epoch closed with rewards
+    #[serde(default)]
+    pub rewards: Option<RewardManifest>,
+}
+
+fn initial_schema_version() -> u32 {
+    1
+}
+
+impl RegistrySnapshot {
+    /// Snapshot at the current schema version
+    pub fn new(
+        chain_id: impl Into<String>,
+        epoch: u64,
+        mut validators: Vec<ValidatorRecord>,
+        activation_queue: Vec<ValidatorId>,
+    ) -> Self {
+        validators.sort_by_key(|v| v.validator_id);
+        Self {
+            schema_version: REGISTRY_SCHEMA_VERSION,
+            chain_id: chain_id.into(),
+            epoch,
+            validators,
+            activation_queue,
+            reputation_history: Vec::new(),
+            liveness: Vec::new(),
+            slash_events: Vec::new(),
+            rewards: None,
+        }
+    }
+
+    /// Decode a snapshot of any supported schema version, migrating it to the current one
+    pub fn from_json(bytes: &[u8]) -> Result<Self> {
+        let mut snapshot: RegistrySnapshot = match serde_json::from_slice(bytes) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                // Report a newer schema rather than the field it fails on
+                let header: SnapshotHeader = serde_json::from_slice(bytes).context("Registry snapshot is not JSON")?;
+                header.check_version()?;
+                return Err(RegistryStoreError::CorruptSnapshot { epoch: header.epoch, reason: e.to_string() }.into());
+            }
+        };
+        SnapshotHeader { schema_version: snapshot.schema_version, epoch: snapshot.epoch }.check_version()?;
+
+        snapshot.schema_version = REGISTRY_SCHEMA_VERSION;
+        Ok(snapshot)
+    }
+
+    /// Record of a validator in this snapshot
+    pub fn validator(&self, validator_id: &ValidatorId) -> Option<&ValidatorRecord> {
+        self.validators
+            .binary_search_by(|v| v.validator_id.cmp(validator_id))
+            .ok()
+            .map(|i| &self.validators[i])
+    }
+}
+
+/// Version and epoch of a snapshot that may not decode
+#[derive(Deserialize)]
+struct SnapshotHeader {
+    #[serde(default = "initial_schema_version")]
+    schema_version: u32,
+    #[serde(default)]
+    epoch: u64,
+}
+
+impl SnapshotHeader {
+    fn check_version(&self) -> Result<(), RegistryStoreError> {
+        if self.schema_version > REGISTRY_SCHEMA_VERSION {
+            return Err(RegistryStoreError::UnsupportedSchema {
+                found: self.schema_version,
+                supported: REGISTRY_SCHEMA_VERSION,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Snapshot storage keyed by epoch
+pub trait RegistryStore: Send + Sync {
+    /// Store the snapshot for `snapshot.epoch`, replacing any earlier one for that epoch
+    fn save(&mut self, snapshot: &RegistrySnapshot) -> Result<()>;
+
+    /// Most recent snapshot
+    fn latest(&self) -> Result<Option<RegistrySnapshot>>;
+
+    /// Latest snapshot at or before `epoch`
+    fn snapshot_at(&self, epoch: u64) -> Result<Option<RegistrySnapshot>>;
+
+    /// Epochs with a stored snapshot, ascending
+    fn epochs(&self) -> Vec<u64>;
+}
+
+/// In-memory snapshot store
+#[derive(Debug, Clone, Default)]
+pub struct MemoryRegistryStore {
+    snapshots: BTreeMap<u64, RegistrySnapshot>,
+}
+
+impl MemoryRegistryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RegistryStore for MemoryRegistryStore {
+    fn save(&mut self, snapshot: &RegistrySnapshot) -> Result<()> {
+        self.snapshots.insert(snapshot.epoch, snapshot.clone());
+        Ok(())
+    }
+
+    fn latest(&self) -> Result<Option<RegistrySnapshot>> {
+        Ok(self.snapshots.values().next_back().cloned())
+    }
+
+    fn snapshot_at(&self, epoch: u64) -> Result<Option<RegistrySnapshot>> {
+        Ok(self.snapshots.range(..=epoch).next_back().map(|(_, s)| s.clone()))
+    }
+
+    fn epochs(&self) -> Vec<u64> {
+        self.snapshots.keys().copied().collect()
+    }
+}
+
+/// Embedded on-disk store: `snapshot-<epoch>.json` per epoch
+///
+/// Each snapshot is written to a temporary file, fsync'd and renamed over the
+/// previous one, so a crash leaves either the old or the new snapshot.
+#[derive(Debug)]
+pub struct FileRegistryStore {
+    dir: PathBuf,
+    epochs: BTreeMap<u64, PathBuf>,
+}
+
+impl FileRegistryStore {
+    /// Open (or create) a store in `dir`
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create registry store {}", dir.display()))?;
+
+        let mut epochs = BTreeMap::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let epoch = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix("snapshot-")?.strip_suffix(".json")?.parse::<u64>().ok());
+            if let Some(epoch) = epoch {
+                epochs.insert(epoch, path);
+            }
+        }
+
+        Ok(Self { dir, epochs })
+    }
+
+    /// Directory holding the snapshots
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    fn load(&self, epoch: u64, path: &Path) -> Result<RegistrySnapshot> {
+        let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        RegistrySnapshot::from_json(&bytes).with_context(|| format!("Registry snapshot for epoch {}", epoch))
+    }
+}
+
+impl RegistryStore for FileRegistryStore {
+    fn save(&mut self, snapshot: &RegistrySnapshot) -> Result<()> {
+        let path = self.dir.join(format!("snapshot-{:020}.json", snapshot.epoch));
+        let tmp = self.dir.join(format!("snapshot-{:020}.json.tmp", snapshot.epoch));
+
+        let mut file = File::create(&tmp).with_context(|| format!("Failed to create {}", tmp.display()))?;
+        file.write_all(&serde_json::to_vec(snapshot)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))?;
+
+        self.epochs.insert(snapshot.epoch, path);
+        Ok(())
+    }
+
+    fn latest(&self) -> Result<Option<RegistrySnapshot>> {
+        self.epochs
+            .iter()
+            .next_back()
+            .map(|(&epoch, path)| self.load(epoch, path))
+            .transpose()
+    }
+
+    fn snapshot_at(&self, epoch: u64) -> Result<Option<RegistrySnapshot>> {
+        self.epochs
+            .range(..=epoch)
+            .next_back()
+            .map(|(&epoch, path)| self.load(epoch, path))
+            .transpose()
+    }
+
+    fn epochs(&self) -> Vec<u64> {
+        self.epochs.keys().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(epoch: u64, weight: u128) -> RegistrySnapshot {
+        let mut validator = ValidatorRecord::new_pending([1u8; 32], [1u8; 32], String::new(), 0);
+        validator.poi_weight = weight;
+        RegistrySnapshot::new("bizra-testnet-001", epoch, vec![validator], vec![[1u8; 32]])
+    }
+
+    #[test]
+    fn test_point_in_time_lookup() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut stores: Vec<Box<dyn RegistryStore>> =
+            vec![Box::new(MemoryRegistryStore::new()), Box::new(FileRegistryStore::open(dir.path()).unwrap())];
+
+        for store in stores.iter_mut() {
+            store.save(&snapshot(0, 100)).unwrap();
+            store.save(&snapshot(2, 200)).unwrap();
+            store.save(&snapshot(2, 250)).unwrap(); // Same epoch: replaced
+
+            assert_eq!(store.epochs(), vec![0, 2]);
+            assert_eq!(store.latest().unwrap().unwrap().epoch, 2);
+
+            let weight_at = |epoch| store.snapshot_at(epoch).unwrap().map(|s| s.validator(&[1u8; 32]).unwrap().poi_weight);
+            assert_eq!(weight_at(0), Some(100));
+            assert_eq!(weight_at(1), Some(100));
+            assert_eq!(weight_at(7), Some(250));
+        }
+
+        // Survives reopen
+        let reopened = FileRegistryStore::open(dir.path()).unwrap();
+        assert_eq!(reopened.epochs(), vec![0, 2]);
+        assert_eq!(reopened.latest().unwrap().unwrap().activation_queue, vec![[1u8; 32]]);
+    }
+
+    #[test]
+    fn test_migrates_v1_snapshot() {
+        let mut v1 = serde_json::to_value(snapshot(3, 42)).unwrap();
+        let obj = v1.as_object_mut().unwrap();
+        for field in ["schema_version", "activation_queue", "reputation_history", "liveness", "slash_events", "rewards"] {
+            obj.remove(field);
+        }
+        let record = v1["validators"][0].as_object_mut().unwrap();
+        for field in ["quarantine_until", "pending_key", "key_history", "bond"] {
+            record.remove(field);
+        }
+
+        let migrated = RegistrySnapshot::from_json(&serde_json::to_vec(&v1).unwrap()).unwrap();
+        assert_eq!(migrated.schema_version, REGISTRY_SCHEMA_VERSION);
+        assert_eq!(migrated.validators[0].quarantine_until, None);
+        assert!(migrated.validators[0].key_history.is_empty());
+        assert_eq!(migrated.validators[0].poi_weight, 42);
+        assert!(migrated.activation_queue.is_empty());
+        assert!(migrated.slash_events.is_empty());
+        assert!(migrated.rewards.is_none());
+    }
+
+    #[test]
+    fn test_amounts_above_u64_round_trip() {
+        let big = u64::MAX as u128 * 3;
+        let mut snapshot = snapshot(5, big);
+        snapshot.validators[0].stake_bond = big + 1;
+        snapshot.validators[0].bond.burned = big + 2;
+
+        let dir = tempfile::tempdir().unwrap();
+        FileRegistryStore::open(dir.path()).unwrap().save(&snapshot).unwrap();
+        let loaded = FileRegistryStore::open(dir.path()).unwrap().latest().unwrap().unwrap();
+        let validator = loaded.validator(&[1u8; 32]).unwrap();
+        assert_eq!((validator.poi_weight, validator.stake_bond, validator.bond.burned), (big, big + 1, big + 2));
+    }
+
+    #[test]
+    fn test_rejects_newer_schema() {
+        let mut future = serde_json::to_value(snapshot(3, 42)).unwrap();
+        future["schema_version"] = (REGISTRY_SCHEMA_VERSION + 1).into();
+
+        let err = RegistrySnapshot::from_json(&serde_json::to_vec(&future).unwrap()).unwrap_err();
+        assert!(matches!(err.downcast_ref::<RegistryStoreError>(), Some(RegistryStoreError::UnsupportedSchema { .. })));
+    }
+}
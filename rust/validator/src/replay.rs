@@ -0,0 +1,462 @@
+//! Replay protection for PoI attestations
+//!
+//! Keeps a bounded history per attester so that a resubmitted nonce, a
+//! resubmitted attestation digest, or a second claim over an overlapping time
+//! window (which would double-count impact) is rejected before the attestation
+//! is stored or weighted.
+//!
+//! Attesters are identified by their Ed25519 public key, which the attestation
+//! signature has been verified against; the free-form `attester.id` is not
+//! trusted. Callers only record attestations from registered validator keys,
+//! so one attester cannot crowd out another by inventing keys.
+//!
+//! Memory is bounded per attester (oldest entries first) and in the number of
+//! attesters whose history is kept (the one with the oldest activity first).
+//! Forgetting an entry raises that attester's floor below which its time
+//! windows are rejected, so an evicted attestation cannot be replayed; a
+//! forgotten attester keeps only its floor. Floors are never shared between
+//! attesters.
+//!
+//! Spec reference: BIZRA_PoI_Cryptographic_Attestation_Spec_v1.0.md section 5
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::poi::PoIAttestation;
+use crate::store::{for_each_record, AttestationStore};
+
+/// Default number of attestations remembered per attester
+pub const DEFAULT_REPLAY_CACHE_PER_ATTESTER: usize = 1024;
+
+/// Default number of attesters remembered
+pub const DEFAULT_REPLAY_CACHE_ATTESTERS: usize = 10_000;
+
+/// Replay check failures
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ReplayError {
+    #[error("Replayed nonce {nonce} from attester {attester}")]
+    DuplicateNonce { attester: String, nonce: String },
+
+    #[error("Replayed attestation {digest} from attester {attester}")]
+    DuplicateDigest { attester: String, digest: String },
+
+    #[error("Time window [{start}, {end}) from attester {attester} overlaps attestation {existing_digest}")]
+    OverlappingWindow {
+        attester: String,
+        start: String,
+        end: String,
+        existing_digest: String,
+    },
+
+    #[error("Time window starting {start} from attester {attester} is before the replay horizon {floor}")]
+    StaleWindow { attester: String, start: String, floor: String },
+}
+
+/// One remembered attestation
+#[derive(Debug, Clone)]
+struct SeenAttestation {
+    nonce: String,
+    digest: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+/// Bounded per-attester history (FIFO eviction)
+#[derive(Debug, Default)]
+struct AttesterHistory {
+    entries: VecDeque<SeenAttestation>,
+    nonces: HashSet<String>,
+    digests: HashSet<String>,
+
+    /// Latest window end among evicted entries
+    floor: Option<DateTime<Utc>>,
+
+    /// Latest window end seen
+    latest: Option<DateTime<Utc>>,
+}
+
+/// Per-attester nonce, digest and time-window replay cache
+#[derive(Debug)]
+pub struct ReplayGuard {
+    capacity: usize,
+    max_attesters: usize,
+    attesters: HashMap<[u8; 32], AttesterHistory>,
+
+    /// Floor of each attester whose history was forgotten
+    forgotten: HashMap<[u8; 32], DateTime<Utc>>,
+}
+
+impl ReplayGuard {
+    /// Create a guard with the default per-attester and attester limits
+    pub fn new() -> Self {
+        Self::with_limits(DEFAULT_REPLAY_CACHE_PER_ATTESTER, DEFAULT_REPLAY_CACHE_ATTESTERS)
+    }
+
+    /// Create a guard remembering at most `capacity` attestations per attester
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_limits(capacity, DEFAULT_REPLAY_CACHE_ATTESTERS)
+    }
+
+    /// Create a guard remembering at most `capacity` attestations for each of
+    /// at most `max_attesters` attesters
+    pub fn with_limits(capacity: usize, max_attesters: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            max_attesters: max_attesters.max(1),
+            attesters: HashMap::new(),
+            forgotten: HashMap::new(),
+        }
+    }
+
+    /// Default guard remembering every attestation in `store` credited to a
+    /// registered validator, in insertion order
+    pub fn from_store(store: &dyn AttestationStore) -> Result<Self> {
+        let mut guard = Self::new();
+        for_each_record(store, |record| {
+            if record.validator_id.is_none() {
+                return Ok(());
+            }
+            // Accepted once already; a replay error means an earlier record covers it
+            if let Err(e) = guard.check_and_record(&record.attestation) {
+                e.downcast::<ReplayError>()?;
+            }
+            Ok(())
+        })?;
+        Ok(guard)
+    }
+
+    /// Check an attestation against the cache without recording it
+    pub fn check(&self, attestation: &PoIAttestation) -> Result<()> {
+        let (attester, seen) = Self::entry(attestation)?;
+        self.check_entry(&attester, &seen)?;
+        Ok(())
+    }
+
+    /// Check an attestation and record it if it is not a replay
+    ///
+    /// Only attestations signed by a registered validator key should be recorded.
+    pub fn check_and_record(&mut self, attestation: &PoIAttestation) -> Result<()> {
+        let (attester, seen) = Self::entry(attestation)?;
+        self.check_entry(&attester, &seen)?;
+
+        if !self.attesters.contains_key(&attester) && self.attesters.len() >= self.max_attesters {
+            self.forget_oldest_attester();
+        }
+
+        // A returning attester starts at its own floor: its forgotten entries are below it
+        let history = match self.attesters.entry(attester) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let floor = self.forgotten.remove(&attester);
+                entry.insert(AttesterHistory { floor, latest: floor, ..Default::default() })
+            }
+        };
+        if history.entries.len() >= self.capacity {
+            if let Some(evicted) = history.entries.pop_front() {
+                history.nonces.remove(&evicted.nonce);
+                history.digests.remove(&evicted.digest);
+                history.floor = history.floor.max(Some(evicted.end));
+            }
+        }
+        history.nonces.insert(seen.nonce.clone());
+        history.digests.insert(seen.digest.clone());
+        history.latest = history.latest.max(Some(seen.end));
+        history.entries.push_back(seen);
+
+        Ok(())
+    }
+
+    /// Undo [`Self::check_and_record`] for an attestation that was not stored
+    ///
+    /// Frees its nonce, digest and window. An older entry it evicted stays
+    /// evicted, so the raised floor keeps rejecting that entry's replays.
+    pub fn forget(&mut self, attestation: &PoIAttestation) -> Result<()> {
+        let (attester, seen) = Self::entry(attestation)?;
+        let Some(history) = self.attesters.get_mut(&attester) else {
+            return Ok(());
+        };
+        let Some(index) = history.entries.iter().position(|e| e.digest == seen.digest) else {
+            return Ok(());
+        };
+        history.entries.remove(index);
+        history.nonces.remove(&seen.nonce);
+        history.digests.remove(&seen.digest);
+        history.latest = history.entries.iter().map(|e| e.end).max().max(history.floor);
+        if history.entries.is_empty() {
+            if let Some(floor) = history.floor {
+                self.forgotten.insert(attester, floor);
+            }
+            self.attesters.remove(&attester);
+        }
+        Ok(())
+    }
+
+    /// Number of attestations remembered for an attester key
+    pub fn len_for(&self, attester: &[u8; 32]) -> usize {
+        self.attesters.get(attester).map_or(0, |h| h.entries.len())
+    }
+
+    /// Number of attesters remembered
+    pub fn attester_count(&self) -> usize {
+        self.attesters.len()
+    }
+
+    fn entry(attestation: &PoIAttestation) -> Result<([u8; 32], SeenAttestation)> {
+        let attester = attestation.attester_ed25519_key()?;
+        let digest = attestation.compute_digest()?;
+        let (start, end) = attestation.time_window_bounds()?;
+        Ok((attester, SeenAttestation { nonce: attestation.nonce.clone(), digest, start, end }))
+    }
+
+    /// Drop the history of the attester whose latest window ended first,
+    /// keeping its latest window end as its floor
+    fn forget_oldest_attester(&mut self) {
+        let oldest = self
+            .attesters
+            .iter()
+            .min_by_key(|(key, history)| (history.latest, **key))
+            .map(|(key, _)| *key);
+        if let Some((key, history)) = oldest.and_then(|key| self.attesters.remove_entry(&key)) {
+            if let Some(floor) = history.latest.max(history.floor) {
+                self.forgotten.insert(key, floor);
+            }
+        }
+    }
+
+    fn check_entry(&self, attester: &[u8; 32], seen: &SeenAttestation) -> std::result::Result<(), ReplayError> {
+        let history = self.attesters.get(attester);
+        let floor = match history {
+            Some(history) => history.floor,
+            None => self.forgotten.get(attester).copied(),
+        };
+        if let Some(floor) = floor.filter(|floor| seen.start < *floor) {
+            return Err(ReplayError::StaleWindow {
+                attester: hex::encode(attester),
+                start: seen.start.to_rfc3339(),
+                floor: floor.to_rfc3339(),
+            });
+        }
+        let Some(history) = history else {
+            return Ok(());
+        };
+
+        if history.digests.contains(&seen.digest) {
+            return Err(ReplayError::DuplicateDigest {
+                attester: hex::encode(attester),
+                digest: seen.digest.clone(),
+            });
+        }
+        if history.nonces.contains(&seen.nonce) {
+            return Err(ReplayError::DuplicateNonce {
+                attester: hex::encode(attester),
+                nonce: seen.nonce.clone(),
+            });
+        }
+
+        // Half-open windows [start, end): back-to-back windows do not overlap
+        if let Some(existing) = history.entries.iter().find(|e| seen.start < e.end && e.start < seen.end) {
+            return Err(ReplayError::OverlappingWindow {
+                attester: hex::encode(attester),
+                start: seen.start.to_rfc3339(),
+                end: seen.end.to_rfc3339(),
+                existing_digest: existing.digest.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ReplayGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poi::tests::test_attestation;
+    use ed25519_dalek::SigningKey;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn attestation(signer: &SigningKey, nonce: &str, window: [&str; 2]) -> PoIAttestation {
+        let mut attestation = test_attestation();
+        attestation.nonce = nonce.to_string();
+        attestation.time_window = [window[0].to_string(), window[1].to_string()];
+        attestation.sign(signer).unwrap();
+        attestation
+    }
+
+    fn replay_error(result: Result<()>) -> ReplayError {
+        result.unwrap_err().downcast::<ReplayError>().unwrap()
+    }
+
+    const NONCE_A: &str = "aab5c7d3c6b9421a8c7c3f0f0a4d33aa";
+    const NONCE_B: &str = "bbb5c7d3c6b9421a8c7c3f0f0a4d33bb";
+    const NONCE_C: &str = "ccb5c7d3c6b9421a8c7c3f0f0a4d33cc";
+
+    #[test]
+    fn test_duplicate_digest_and_nonce() {
+        let mut guard = ReplayGuard::new();
+        let first = attestation(&key(1), NONCE_A, ["2025-09-15T10:00:00Z", "2025-09-15T14:00:00Z"]);
+        guard.check_and_record(&first).unwrap();
+
+        assert!(matches!(replay_error(guard.check(&first)), ReplayError::DuplicateDigest { .. }));
+
+        let same_nonce = attestation(&key(1), NONCE_A, ["2025-09-16T10:00:00Z", "2025-09-16T14:00:00Z"]);
+        assert!(matches!(replay_error(guard.check(&same_nonce)), ReplayError::DuplicateNonce { .. }));
+
+        // Nonces are scoped per attester key
+        let other = attestation(&key(2), NONCE_A, ["2025-09-15T10:00:00Z", "2025-09-15T14:00:00Z"]);
+        guard.check_and_record(&other).unwrap();
+    }
+
+    #[test]
+    fn test_attester_id_does_not_scope_replays() {
+        let mut guard = ReplayGuard::new();
+        let first = attestation(&key(1), NONCE_A, ["2025-09-15T10:00:00Z", "2025-09-15T14:00:00Z"]);
+        guard.check_and_record(&first).unwrap();
+
+        // Same key under a new id: still the same attester
+        let mut renamed = first.clone();
+        renamed.attester.id = "someone-else".to_string();
+        renamed.sign(&key(1)).unwrap();
+        assert!(matches!(replay_error(guard.check(&renamed)), ReplayError::DuplicateNonce { .. }));
+        assert_eq!(guard.len_for(&key(1).verifying_key().to_bytes()), 1);
+    }
+
+    #[test]
+    fn test_overlapping_windows() {
+        let mut guard = ReplayGuard::new();
+        guard
+            .check_and_record(&attestation(&key(1), NONCE_A, ["2025-09-15T10:00:00Z", "2025-09-15T14:00:00Z"]))
+            .unwrap();
+
+        let overlapping = attestation(&key(1), NONCE_B, ["2025-09-15T13:00:00Z", "2025-09-15T18:00:00Z"]);
+        assert!(matches!(replay_error(guard.check(&overlapping)), ReplayError::OverlappingWindow { .. }));
+
+        let adjacent = attestation(&key(1), NONCE_C, ["2025-09-15T14:00:00Z", "2025-09-15T18:00:00Z"]);
+        guard.check_and_record(&adjacent).unwrap();
+        assert_eq!(guard.len_for(&key(1).verifying_key().to_bytes()), 2);
+    }
+
+    #[test]
+    fn test_bounded_eviction() {
+        let mut guard = ReplayGuard::with_capacity(2);
+        let windows = [
+            ["2025-09-15T00:00:00Z", "2025-09-15T01:00:00Z"],
+            ["2025-09-15T01:00:00Z", "2025-09-15T02:00:00Z"],
+            ["2025-09-15T02:00:00Z", "2025-09-15T03:00:00Z"],
+        ];
+        let nonces = [NONCE_A, NONCE_B, NONCE_C];
+        let attestations: Vec<_> = nonces
+            .iter()
+            .zip(windows)
+            .map(|(nonce, window)| attestation(&key(1), nonce, window))
+            .collect();
+
+        for a in &attestations {
+            guard.check_and_record(a).unwrap();
+        }
+        assert_eq!(guard.len_for(&key(1).verifying_key().to_bytes()), 2);
+
+        // The oldest entry was evicted but stays unreplayable below the floor
+        assert!(matches!(replay_error(guard.check(&attestations[0])), ReplayError::StaleWindow { .. }));
+        assert!(guard.check(&attestations[2]).is_err());
+    }
+
+    #[test]
+    fn test_attester_limit() {
+        let mut guard = ReplayGuard::with_limits(4, 2);
+        let old = attestation(&key(1), NONCE_A, ["2025-09-15T00:00:00Z", "2025-09-15T01:00:00Z"]);
+        guard.check_and_record(&old).unwrap();
+        guard
+            .check_and_record(&attestation(&key(2), NONCE_A, ["2025-09-15T05:00:00Z", "2025-09-15T06:00:00Z"]))
+            .unwrap();
+        guard
+            .check_and_record(&attestation(&key(3), NONCE_A, ["2025-09-15T07:00:00Z", "2025-09-15T08:00:00Z"]))
+            .unwrap();
+
+        // Key 1 (oldest activity) was forgotten; its attestations cannot come back
+        assert_eq!(guard.attester_count(), 2);
+        assert_eq!(guard.len_for(&key(1).verifying_key().to_bytes()), 0);
+        assert!(matches!(replay_error(guard.check(&old)), ReplayError::StaleWindow { .. }));
+        guard
+            .check_and_record(&attestation(&key(1), NONCE_B, ["2025-09-15T09:00:00Z", "2025-09-15T10:00:00Z"]))
+            .unwrap();
+        assert!(matches!(replay_error(guard.check(&old)), ReplayError::StaleWindow { .. }));
+    }
+
+    #[test]
+    fn test_rebuild_from_store() {
+        use crate::store::{AttestationStore, MemoryAttestationStore, StoredAttestation};
+
+        let first = attestation(&key(1), NONCE_A, ["2025-09-15T10:00:00Z", "2025-09-15T14:00:00Z"]);
+        let second = attestation(&key(2), NONCE_A, ["2025-09-15T10:00:00Z", "2025-09-15T14:00:00Z"]);
+        let unregistered = attestation(&key(3), NONCE_A, ["2025-09-15T10:00:00Z", "2025-09-15T14:00:00Z"]);
+        let mut store = MemoryAttestationStore::new();
+        for (n, a) in [&first, &second].into_iter().enumerate() {
+            store.insert(StoredAttestation::new(a.clone(), 0, Some([n as u8; 32])).unwrap()).unwrap();
+        }
+        store.insert(StoredAttestation::new(unregistered, 0, None).unwrap()).unwrap();
+
+        // Records not credited to a registered validator stay out of the guard
+        let guard = ReplayGuard::from_store(&store).unwrap();
+        assert_eq!(guard.attester_count(), 2);
+        assert!(matches!(replay_error(guard.check(&first)), ReplayError::DuplicateDigest { .. }));
+        guard
+            .check(&attestation(&key(1), NONCE_B, ["2025-09-15T14:00:00Z", "2025-09-15T18:00:00Z"]))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_forget_frees_an_unstored_attestation() {
+        let mut guard = ReplayGuard::new();
+        let first = attestation(&key(1), NONCE_A, ["2025-09-15T10:00:00Z", "2025-09-15T14:00:00Z"]);
+        guard.check_and_record(&first).unwrap();
+        guard.forget(&first).unwrap();
+        assert_eq!(guard.attester_count(), 0);
+        guard.check_and_record(&first).unwrap();
+
+        // An evicted entry stays below the floor after the evicting one is forgotten
+        let mut guard = ReplayGuard::with_capacity(1);
+        let second = attestation(&key(1), NONCE_B, ["2025-09-15T14:00:00Z", "2025-09-15T18:00:00Z"]);
+        guard.check_and_record(&first).unwrap();
+        guard.check_and_record(&second).unwrap();
+        guard.forget(&second).unwrap();
+        assert!(matches!(replay_error(guard.check(&first)), ReplayError::StaleWindow { .. }));
+        guard.check_and_record(&second).unwrap();
+    }
+
+    #[test]
+    fn test_other_attesters_cannot_raise_a_floor() {
+        let mut guard = ReplayGuard::new();
+        let registered = key(1);
+        let old = attestation(&registered, NONCE_A, ["2025-09-15T10:00:00Z", "2025-09-15T14:00:00Z"]);
+        guard.check_and_record(&old).unwrap();
+
+        // More attesters than the guard keeps, as if unknown keys had slipped past
+        // the registry check, all active later than the registered one
+        for n in 0..=DEFAULT_REPLAY_CACHE_ATTESTERS as u32 {
+            let mut seed = [0xffu8; 32];
+            seed[..4].copy_from_slice(&n.to_le_bytes());
+            let flood = attestation(&SigningKey::from_bytes(&seed), NONCE_A, ["2025-09-20T00:00:00Z", "2025-09-20T01:00:00Z"]);
+            guard.check_and_record(&flood).unwrap();
+        }
+        assert_eq!(guard.len_for(&registered.verifying_key().to_bytes()), 0);
+
+        // Its next attestation is judged against its own floor only
+        guard
+            .check_and_record(&attestation(&registered, NONCE_B, ["2025-09-15T14:00:00Z", "2025-09-15T18:00:00Z"]))
+            .unwrap();
+        assert!(matches!(replay_error(guard.check(&old)), ReplayError::StaleWindow { .. }));
+    }
+}
@@ -1,28 +1,221 @@
 //! Reputation tracking with decay and historical performance
 //!
 //! Implements reputation scoring as specified in spec section 5.8
+//!
+//! Scores live on [`ValidatorRecord::rep_score`](crate::ValidatorRecord) on a
+//! 0..=100,000 scale. Every change goes through [`ReputationTracker`], which
+//! clamps the result and keeps a bounded per-validator history of deltas with
+//! their reason. Decay is fixed point (parts per 10^18) and uses exponentiation
+//! by squaring, so decaying over any number of epochs costs at most 64 steps.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::poi::BPS_SCALE;
+use crate::slashing::SlashReason;
+use crate::types::ValidatorId;
+
+/// Upper bound of the reputation scale
+pub const MAX_REPUTATION_SCORE: u64 = 100_000;
+
+/// Default share of reputation retained per epoch (spec: 0.95)
+pub const DEFAULT_REPUTATION_DECAY_BPS: u64 = 9_500;
+
+/// Default number of history entries kept per validator
+pub const DEFAULT_REPUTATION_HISTORY: usize = 64;
+
+/// Fixed-point scale for decay factors (10^18)
+const DECAY_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// Why a reputation score moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReputationReason {
+    /// First PoI attestation accepted in an epoch
+    TimelyAttestation,
+
+    /// Missed liveness window (graduated amount)
+    LivenessMiss,
+
+    /// Slashed for the given fault (policy amount)
+    Slashed(SlashReason),
+
+    /// Per-epoch decay
+    Decay,
+}
+
+/// Reward and penalty amounts (0..100,000 scale)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReputationParams {
+    /// Share of reputation retained per epoch, in bps
+    pub decay_bps: u64,
+
+    pub timely_attestation_reward: u64,
+
+    /// History entries kept per validator
+    pub history_len: usize,
+}
+
+impl Default for ReputationParams {
+    fn default() -> Self {
+        Self {
+            decay_bps: DEFAULT_REPUTATION_DECAY_BPS,
+            timely_attestation_reward: 5,
+            history_len: DEFAULT_REPUTATION_HISTORY,
+        }
+    }
+}
+
+/// One recorded reputation change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReputationChange {
+    pub epoch: u64,
+    pub reason: ReputationReason,
+
+    /// Applied delta after clamping
+    pub delta: i64,
+
+    pub score_after: u64,
+}
 
 /// Reputation tracker with decay
+#[derive(Debug, Clone)]
 pub struct ReputationTracker {
-    /// Decay factor per epoch (spec: 0.95)
-    decay_factor: f64,
+    params: ReputationParams,
+
+    /// Decay factor per epoch (parts per 10^18)
+    decay_factor: u128,
+
+    /// Most recent changes per validator, oldest first
+    history: HashMap<ValidatorId, VecDeque<ReputationChange>>,
 }
 
 impl ReputationTracker {
     /// Create new reputation tracker
     pub fn new() -> Self {
+        Self::with_params(ReputationParams::default())
+    }
+
+    /// Create tracker with explicit amounts
+    pub fn with_params(params: ReputationParams) -> Self {
+        let decay_bps = (params.decay_bps as u128).min(BPS_SCALE);
         Self {
-            decay_factor: 0.95,
+            params,
+            decay_factor: decay_bps * (DECAY_SCALE / BPS_SCALE),
+            history: HashMap::new(),
         }
     }
 
+    pub fn params(&self) -> &ReputationParams {
+        &self.params
+    }
+
     /// Calculate reputation decay for given epochs
     pub fn apply_decay(&self, current_score: u64, epochs_passed: u64) -> u64 {
-        let mut score = current_score as f64;
-        for _ in 0..epochs_passed {
-            score *= self.decay_factor;
+        let factor = pow_fixed(self.decay_factor, epochs_passed);
+        (current_score as u128 * factor / DECAY_SCALE) as u64
+    }
+
+    /// Fixed delta for a reason (None for reasons whose amount is supplied by the caller)
+    pub fn delta_for(&self, reason: ReputationReason) -> Option<i64> {
+        let p = &self.params;
+        match reason {
+            ReputationReason::TimelyAttestation => Some(p.timely_attestation_reward as i64),
+            ReputationReason::LivenessMiss | ReputationReason::Slashed(_) | ReputationReason::Decay => None,
+        }
+    }
+
+    /// Apply the configured reward or penalty for `reason`; returns the new score
+    ///
+    /// Reasons without a fixed amount leave the score unchanged.
+    pub fn apply(&mut self, validator_id: ValidatorId, current_score: u64, reason: ReputationReason, epoch: u64) -> u64 {
+        match self.delta_for(reason) {
+            Some(delta) => self.apply_delta(validator_id, current_score, delta, reason, epoch),
+            None => current_score,
+        }
+    }
+
+    /// Apply a caller-supplied delta, clamped to 0..=MAX_REPUTATION_SCORE; returns the new score
+    pub fn apply_delta(
+        &mut self,
+        validator_id: ValidatorId,
+        current_score: u64,
+        delta: i64,
+        reason: ReputationReason,
+        epoch: u64,
+    ) -> u64 {
+        let score_after = (current_score as i128 + delta as i128).clamp(0, MAX_REPUTATION_SCORE as i128) as u64;
+        let applied = score_after as i64 - current_score as i64;
+        if applied != 0 {
+            self.push_history(validator_id, ReputationChange { epoch, reason, delta: applied, score_after });
         }
-        score as u64
+        score_after
+    }
+
+    /// Decay a validator's score by `epochs_passed` epochs; returns the new score
+    pub fn decay(&mut self, validator_id: ValidatorId, current_score: u64, epochs_passed: u64, epoch: u64) -> u64 {
+        let decayed = self.apply_decay(current_score.min(MAX_REPUTATION_SCORE), epochs_passed);
+        self.apply_delta(validator_id, current_score, decayed as i64 - current_score as i64, ReputationReason::Decay, epoch)
+    }
+
+    /// Decay like [`decay`](Self::decay), but never below `floor`
+    ///
+    /// A score already under `floor` is left as it is: only penalties take a
+    /// score below the floor.
+    pub fn decay_to_floor(
+        &mut self,
+        validator_id: ValidatorId,
+        current_score: u64,
+        epochs_passed: u64,
+        floor: u64,
+        epoch: u64,
+    ) -> u64 {
+        let decayed = self.apply_decay(current_score.min(MAX_REPUTATION_SCORE), epochs_passed);
+        let floored = decayed.max(floor.min(current_score));
+        self.apply_delta(validator_id, current_score, floored as i64 - current_score as i64, ReputationReason::Decay, epoch)
+    }
+
+    /// Recorded changes for a validator, oldest first
+    pub fn history(&self, validator_id: &ValidatorId) -> Vec<ReputationChange> {
+        self.history
+            .get(validator_id)
+            .map(|h| h.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Recorded changes of every validator, ordered by id (for snapshots)
+    pub fn histories(&self) -> Vec<(ValidatorId, Vec<ReputationChange>)> {
+        let mut histories: Vec<_> = self
+            .history
+            .iter()
+            .map(|(id, h)| (*id, h.iter().copied().collect()))
+            .collect();
+        histories.sort_unstable_by_key(|(id, _)| *id);
+        histories
+    }
+
+    /// Replace the history with one taken by [`Self::histories`], keeping the
+    /// most recent `history_len` entries per validator
+    pub fn restore(&mut self, histories: impl IntoIterator<Item = (ValidatorId, Vec<ReputationChange>)>) {
+        self.history.clear();
+        for (validator_id, changes) in histories {
+            for change in changes {
+                self.push_history(validator_id, change);
+            }
+        }
+    }
+
+    fn push_history(&mut self, validator_id: ValidatorId, change: ReputationChange) {
+        let limit = self.params.history_len;
+        if limit == 0 {
+            return;
+        }
+        let history = self.history.entry(validator_id).or_default();
+        if history.len() == limit {
+            history.pop_front();
+        }
+        history.push_back(change);
     }
 }
 
@@ -32,10 +225,26 @@ impl Default for ReputationTracker {
     }
 }
 
+/// `base^exp` for a factor in parts per 10^18 (base ≤ 1.0), rounding down
+fn pow_fixed(base: u128, mut exp: u64) -> u128 {
+    let mut result = DECAY_SCALE;
+    let mut base = base;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base / DECAY_SCALE;
+        }
+        base = base * base / DECAY_SCALE;
+        exp >>= 1;
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const A: ValidatorId = [1u8; 32];
+
     #[test]
     fn test_reputation_decay() {
         let tracker = ReputationTracker::new();
@@ -50,4 +259,67 @@ mod tests {
         let decayed = tracker.apply_decay(10000, 10);
         assert!(decayed < 6000); // ~5987
     }
+
+    #[test]
+    fn test_decay_matches_iterated_and_handles_long_gaps() {
+        let tracker = ReputationTracker::new();
+        assert_eq!(tracker.apply_decay(10000, 10), 5987);
+        assert_eq!(tracker.apply_decay(MAX_REPUTATION_SCORE, 0), MAX_REPUTATION_SCORE);
+        assert_eq!(tracker.apply_decay(MAX_REPUTATION_SCORE, u64::MAX), 0);
+
+        let mut score = MAX_REPUTATION_SCORE as f64;
+        for _ in 0..37 {
+            score *= 0.95;
+        }
+        assert!((tracker.apply_decay(MAX_REPUTATION_SCORE, 37) as f64 - score).abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_decay_to_floor() {
+        let mut tracker = ReputationTracker::new();
+        assert_eq!(tracker.decay_to_floor(A, 10000, 1, 500, 0), 9500);
+        assert_eq!(tracker.decay_to_floor(A, 510, 1, 500, 1), 500);
+        assert_eq!(tracker.decay_to_floor(A, 500, 1, 500, 2), 500);
+
+        // Already below the floor: unchanged
+        assert_eq!(tracker.decay_to_floor(A, 300, 1, 500, 3), 300);
+        assert_eq!(tracker.history(&A).len(), 2);
+    }
+
+    #[test]
+    fn test_rewards_penalties_and_clamp() {
+        let mut tracker = ReputationTracker::new();
+
+        let score = tracker.apply(A, 1_000, ReputationReason::TimelyAttestation, 1);
+        assert_eq!(score, 1_005);
+        assert_eq!(tracker.apply_delta(A, score, -15, ReputationReason::LivenessMiss, 1), 990);
+        assert_eq!(tracker.apply(A, 990, ReputationReason::LivenessMiss, 1), 990);
+
+        assert_eq!(tracker.apply(A, 99_998, ReputationReason::TimelyAttestation, 2), MAX_REPUTATION_SCORE);
+        assert_eq!(tracker.apply_delta(A, 100, -5_000, ReputationReason::Slashed(SlashReason::Equivocation), 2), 0);
+
+        let history = tracker.history(&A);
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[2], ReputationChange {
+            epoch: 2,
+            reason: ReputationReason::TimelyAttestation,
+            delta: 2,
+            score_after: MAX_REPUTATION_SCORE,
+        });
+        assert_eq!(history[3].delta, -100);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut tracker = ReputationTracker::with_params(ReputationParams { history_len: 3, ..Default::default() });
+        let mut score = 10_000;
+        for epoch in 0..10 {
+            score = tracker.decay(A, score, 1, epoch);
+        }
+        let history = tracker.history(&A);
+        assert_eq!(history.iter().map(|c| c.epoch).collect::<Vec<_>>(), vec![7, 8, 9]);
+        assert!(history.iter().all(|c| c.reason == ReputationReason::Decay && c.delta < 0));
+        assert_eq!(history[2].score_after, score);
+        assert!(tracker.history(&[2u8; 32]).is_empty());
+    }
 }
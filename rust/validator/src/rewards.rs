@@ -0,0 +1,474 @@
+//! Epoch reward distribution (BLOOM / SEED)
+//!
+//! Each closed epoch issues a fixed amount of two tokens to the validators that
+//! served in it:
+//!
+//! - **BLOOM** (impact token) follows PoI contribution (`poi_carry_bps`)
+//! - **SEED** (resource token) follows effective consensus weight (`poi_weight`)
+//!
+//! Each pool is split the same way:
+//!
+//! ```text
+//!  issuance ─┬─ proposer share (proposer_share_bps) ─▶ by blocks proposed
+//!            └─ attester share (the rest)           ─▶ by PoI carry (BLOOM) / weight (SEED)
+//!
+//!  net = gross − gross × min(liveness_misses × liveness_penalty_bps, 100%)
+//! ```
+//!
+//! If nobody proposed a block, the proposer share joins the attester share.
+//! Liveness penalties and pools without any eligible recipient are withheld
+//! (not minted), so `distributed + withheld = issuance` holds exactly.
+//!
+//! ## Rounding
+//!
+//! Pools are split with the largest remainder method: every validator gets the
+//! floor of its exact share, and the few units left over go one each to the
+//! largest fractional remainders, ties broken by ascending validator id. The
+//! result depends only on the inputs, never on iteration order.
+//!
+//! ## Manifest
+//!
+//! The distribution is published as a [`RewardManifest`], signed over
+//! `REWARD_MANIFEST_DOMAIN || JCS(manifest without signature)`. Amounts are
+//! decimal strings (see [`u128_string`](crate::canonical::u128_string)).
+
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::canonical::{canonicalize, u128_string};
+use crate::chain_spec::ChainSpec;
+use crate::poi::BPS_SCALE;
+use crate::types::ValidatorId;
+
+/// Domain separator for reward manifest signatures
+pub const REWARD_MANIFEST_DOMAIN: &[u8] = b"BIZRA-REWARD-MANIFEST-v1";
+
+/// Default BLOOM issued per epoch (smallest units)
+pub const DEFAULT_BLOOM_PER_EPOCH: u128 = 10_000;
+
+/// Default SEED issued per epoch (smallest units)
+pub const DEFAULT_SEED_PER_EPOCH: u128 = 5_000;
+
+/// Default share of each pool paid to block proposers (20%)
+pub const DEFAULT_PROPOSER_SHARE_BPS: u128 = 2_000;
+
+/// Default reward withheld per missed liveness window (10%)
+pub const DEFAULT_LIVENESS_PENALTY_BPS: u128 = 1_000;
+
+/// Issuance and split parameters (chain parameters)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RewardParams {
+    pub bloom_per_epoch: u128,
+    pub seed_per_epoch: u128,
+
+    /// Share of each pool paid to proposers, in bps
+    pub proposer_share_bps: u128,
+
+    /// Share of a validator's reward withheld per missed liveness window, in bps
+    pub liveness_penalty_bps: u128,
+}
+
+impl Default for RewardParams {
+    fn default() -> Self {
+        Self {
+            bloom_per_epoch: DEFAULT_BLOOM_PER_EPOCH,
+            seed_per_epoch: DEFAULT_SEED_PER_EPOCH,
+            proposer_share_bps: DEFAULT_PROPOSER_SHARE_BPS,
+            liveness_penalty_bps: DEFAULT_LIVENESS_PENALTY_BPS,
+        }
+    }
+}
+
+/// Reward calculation and manifest failures
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RewardError {
+    #[error("Reward arithmetic overflow")]
+    Overflow,
+
+    #[error("Duplicate reward input for validator {0}")]
+    DuplicateValidator(String),
+
+    #[error("Reward manifest for epoch {0} is not signed")]
+    Unsigned(u64),
+
+    #[error("Reward manifest signature does not verify")]
+    BadSignature,
+
+    #[error("Reward manifest does not conserve issuance: {0}")]
+    NotConserved(String),
+
+    #[error("Invalid reward manifest encoding: {0}")]
+    InvalidEncoding(String),
+}
+
+/// One validator's participation in the closed epoch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RewardInput {
+    pub validator_id: ValidatorId,
+
+    /// Effective weight at the epoch boundary
+    pub weight: u128,
+
+    /// PoI carry after the epoch (bps)
+    pub poi_carry_bps: u128,
+
+    pub blocks_proposed: u64,
+
+    /// Liveness windows missed during the epoch
+    pub liveness_misses: u64,
+}
+
+/// One validator's line in a reward manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorReward {
+    /// Validator id (hex)
+    pub validator_id: String,
+
+    /// Net BLOOM paid
+    #[serde(with = "u128_string")]
+    pub bloom: u128,
+
+    /// Net SEED paid
+    #[serde(with = "u128_string")]
+    pub seed: u128,
+
+    /// BLOOM withheld for liveness misses
+    #[serde(with = "u128_string")]
+    pub bloom_penalty: u128,
+
+    /// SEED withheld for liveness misses
+    #[serde(with = "u128_string")]
+    pub seed_penalty: u128,
+
+    pub blocks_proposed: u64,
+    pub liveness_misses: u64,
+}
+
+/// Signed reward distribution of one epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardManifest {
+    pub chain_id: String,
+    pub epoch: u64,
+
+    #[serde(with = "u128_string")]
+    pub bloom_issued: u128,
+    #[serde(with = "u128_string")]
+    pub seed_issued: u128,
+
+    /// BLOOM paid out (sum of `rewards[].bloom`)
+    #[serde(with = "u128_string")]
+    pub bloom_minted: u128,
+    /// SEED paid out (sum of `rewards[].seed`)
+    #[serde(with = "u128_string")]
+    pub seed_distributed: u128,
+
+    #[serde(with = "u128_string")]
+    pub bloom_withheld: u128,
+    #[serde(with = "u128_string")]
+    pub seed_withheld: u128,
+
+    /// Per-validator rewards, ordered by validator id
+    pub rewards: Vec<ValidatorReward>,
+
+    /// Signer's Ed25519 public key (hex, empty if unsigned)
+    #[serde(default)]
+    pub signer: String,
+
+    /// Ed25519 signature (hex, empty if unsigned)
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl RewardManifest {
+    /// Message covered by the signature (includes the signer key)
+    pub fn signing_payload(&self) -> Result<Vec<u8>, RewardError> {
+        let mut value = serde_json::to_value(self).map_err(|e| RewardError::InvalidEncoding(e.to_string()))?;
+        if let Value::Object(map) = &mut value {
+            map.remove("signature");
+        }
+        let canonical = canonicalize(&value).map_err(|e| RewardError::InvalidEncoding(e.to_string()))?;
+        Ok([REWARD_MANIFEST_DOMAIN, canonical.as_bytes()].concat())
+    }
+
+    /// Sign as `key`
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), RewardError> {
+        self.signer = hex::encode(key.verifying_key().to_bytes());
+        self.signature = hex::encode(key.sign(&self.signing_payload()?).to_bytes());
+        Ok(())
+    }
+
+    /// Check conservation and the signature against the embedded signer key
+    pub fn verify(&self) -> Result<(), RewardError> {
+        self.check_conservation()?;
+        if self.signature.is_empty() {
+            return Err(RewardError::Unsigned(self.epoch));
+        }
+        let pk = hex::decode(&self.signer).map_err(|e| RewardError::InvalidEncoding(e.to_string()))?;
+        let signature = hex::decode(&self.signature).map_err(|e| RewardError::InvalidEncoding(e.to_string()))?;
+        if !poi::verify_attestation(&self.signing_payload()?, &pk, &signature) {
+            return Err(RewardError::BadSignature);
+        }
+        Ok(())
+    }
+
+    /// Totals match the per-validator lines and `paid + withheld = issued` per token
+    ///
+    /// Sums that overflow `u128` cannot balance and are reported as not conserved.
+    pub fn check_conservation(&self) -> Result<(), RewardError> {
+        let overflow = |what: &str| RewardError::NotConserved(format!("{} overflows", what));
+        let total = |amount: fn(&ValidatorReward) -> u128| {
+            self.rewards.iter().try_fold(0u128, |sum, r| sum.checked_add(amount(r)))
+        };
+
+        let bloom = total(|r| r.bloom).ok_or_else(|| overflow("BLOOM reward total"))?;
+        let seed = total(|r| r.seed).ok_or_else(|| overflow("SEED reward total"))?;
+        if bloom != self.bloom_minted || seed != self.seed_distributed {
+            return Err(RewardError::NotConserved("totals differ from the reward lines".to_string()));
+        }
+        let bloom_total = self.bloom_minted.checked_add(self.bloom_withheld).ok_or_else(|| overflow("BLOOM total"))?;
+        if bloom_total != self.bloom_issued {
+            return Err(RewardError::NotConserved(format!("BLOOM {} + {} != {}", self.bloom_minted, self.bloom_withheld, self.bloom_issued)));
+        }
+        let seed_total = self.seed_distributed.checked_add(self.seed_withheld).ok_or_else(|| overflow("SEED total"))?;
+        if seed_total != self.seed_issued {
+            return Err(RewardError::NotConserved(format!("SEED {} + {} != {}", self.seed_distributed, self.seed_withheld, self.seed_issued)));
+        }
+        Ok(())
+    }
+
+    /// Reward line of a validator
+    pub fn reward_for(&self, validator_id: &ValidatorId) -> Option<&ValidatorReward> {
+        let id = hex::encode(validator_id);
+        self.rewards.iter().find(|r| r.validator_id == id)
+    }
+}
+
+/// Split of one token's pool
+struct PoolSplit {
+    net: Vec<u128>,
+    penalty: Vec<u128>,
+    withheld: u128,
+}
+
+/// Deterministic reward calculator
+#[derive(Debug, Clone, Copy)]
+pub struct RewardCalculator {
+    params: RewardParams,
+}
+
+impl RewardCalculator {
+    pub fn new(params: RewardParams) -> Self {
+        Self { params }
+    }
+
+    /// Calculator with the chain's reward parameters
+    pub fn from_chain_spec(spec: &ChainSpec) -> Self {
+        Self::new(spec.rewards)
+    }
+
+    pub fn params(&self) -> &RewardParams {
+        &self.params
+    }
+
+    /// Distribute one epoch's issuance; the manifest is returned unsigned
+    pub fn distribute(&self, chain_id: &str, epoch: u64, inputs: &[RewardInput]) -> Result<RewardManifest, RewardError> {
+        let mut inputs = inputs.to_vec();
+        inputs.sort_unstable_by_key(|i| i.validator_id);
+        if let Some(w) = inputs.windows(2).find(|w| w[0].validator_id == w[1].validator_id) {
+            return Err(RewardError::DuplicateValidator(hex::encode(w[0].validator_id)));
+        }
+
+        let p = &self.params;
+        let bloom = self.split_pool(p.bloom_per_epoch, &inputs, |i| i.poi_carry_bps)?;
+        let seed = self.split_pool(p.seed_per_epoch, &inputs, |i| i.weight)?;
+
+        let rewards = inputs
+            .iter()
+            .enumerate()
+            .map(|(k, input)| ValidatorReward {
+                validator_id: hex::encode(input.validator_id),
+                bloom: bloom.net[k],
+                seed: seed.net[k],
+                bloom_penalty: bloom.penalty[k],
+                seed_penalty: seed.penalty[k],
+                blocks_proposed: input.blocks_proposed,
+                liveness_misses: input.liveness_misses,
+            })
+            .collect();
+
+        let manifest = RewardManifest {
+            chain_id: chain_id.to_string(),
+            epoch,
+            bloom_issued: p.bloom_per_epoch,
+            seed_issued: p.seed_per_epoch,
+            bloom_minted: bloom.net.iter().sum(),
+            seed_distributed: seed.net.iter().sum(),
+            bloom_withheld: bloom.withheld,
+            seed_withheld: seed.withheld,
+            rewards,
+            signer: String::new(),
+            signature: String::new(),
+        };
+        manifest.check_conservation()?;
+        Ok(manifest)
+    }
+
+    fn split_pool(
+        &self,
+        issuance: u128,
+        inputs: &[RewardInput],
+        attester_key: impl Fn(&RewardInput) -> u128,
+    ) -> Result<PoolSplit, RewardError> {
+        let proposals: Vec<u128> = inputs.iter().map(|i| i.blocks_proposed as u128).collect();
+        let proposer_pool = if proposals.iter().any(|&n| n > 0) {
+            mul_div(issuance, self.params.proposer_share_bps.min(BPS_SCALE), BPS_SCALE)?
+        } else {
+            0
+        };
+        let attester_pool = issuance - proposer_pool;
+
+        let keys: Vec<u128> = inputs.iter().map(attester_key).collect();
+        let from_proposals = largest_remainder(proposer_pool, &proposals)?;
+        let from_attesting = largest_remainder(attester_pool, &keys)?;
+
+        let mut split = PoolSplit { net: Vec::with_capacity(inputs.len()), penalty: Vec::with_capacity(inputs.len()), withheld: 0 };
+        let mut paid_gross = 0u128;
+        for (k, input) in inputs.iter().enumerate() {
+            let gross = from_proposals[k] + from_attesting[k];
+            let rate = (input.liveness_misses as u128).saturating_mul(self.params.liveness_penalty_bps).min(BPS_SCALE);
+            let penalty = mul_div(gross, rate, BPS_SCALE)?;
+            split.net.push(gross - penalty);
+            split.penalty.push(penalty);
+            paid_gross += gross;
+        }
+        // Unallocated pools (no eligible recipient) plus liveness penalties
+        split.withheld = issuance - paid_gross + split.penalty.iter().sum::<u128>();
+        Ok(split)
+    }
+}
+
+/// Split `amount` proportionally to `keys` (largest remainder, ties by position)
+///
+/// Positions follow ascending validator id. All-zero keys allocate nothing.
+fn largest_remainder(amount: u128, keys: &[u128]) -> Result<Vec<u128>, RewardError> {
+    let total = keys.iter().try_fold(0u128, |acc, &k| acc.checked_add(k)).ok_or(RewardError::Overflow)?;
+    if total == 0 {
+        return Ok(vec![0; keys.len()]);
+    }
+
+    let mut shares = Vec::with_capacity(keys.len());
+    let mut remainders = Vec::with_capacity(keys.len());
+    for (k, &key) in keys.iter().enumerate() {
+        let exact = amount.checked_mul(key).ok_or(RewardError::Overflow)?;
+        shares.push(exact / total);
+        remainders.push((exact % total, k));
+    }
+
+    let leftover = amount - shares.iter().sum::<u128>();
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, k) in remainders.iter().take(leftover as usize) {
+        shares[k] += 1;
+    }
+    Ok(shares)
+}
+
+fn mul_div(a: u128, b: u128, c: u128) -> Result<u128, RewardError> {
+    Ok(a.checked_mul(b).ok_or(RewardError::Overflow)? / c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(i: u8, weight: u128, poi_carry_bps: u128) -> RewardInput {
+        RewardInput { validator_id: [i; 32], weight, poi_carry_bps, ..Default::default() }
+    }
+
+    #[test]
+    fn test_largest_remainder_conserves_and_is_deterministic() {
+        // 10 / 3 each: floors 3,3,3; the leftover unit goes to the lowest position on a tie
+        assert_eq!(largest_remainder(10, &[1, 1, 1]).unwrap(), vec![4, 3, 3]);
+        assert_eq!(largest_remainder(7, &[0, 0]).unwrap(), vec![0, 0]);
+        assert_eq!(largest_remainder(100, &[1, 2, 3, 4]).unwrap(), vec![10, 20, 30, 40]);
+
+        let keys = [7u128, 13, 29, 31, 1];
+        let shares = largest_remainder(1_000_003, &keys).unwrap();
+        assert_eq!(shares.iter().sum::<u128>(), 1_000_003);
+        assert!(largest_remainder(u128::MAX, &[2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_distribution_by_weight_and_poi() {
+        let calc = RewardCalculator::new(RewardParams::default());
+        let inputs = [input(2, 300, 0), input(1, 100, 30_000), input(3, 100, 10_000)];
+
+        let manifest = calc.distribute("bizra-testnet-001", 7, &inputs).unwrap();
+        assert_eq!(manifest.rewards[0].validator_id, hex::encode([1u8; 32])); // Ordered by id
+
+        // No proposals: whole pools go to attesters
+        let bloom: Vec<u128> = manifest.rewards.iter().map(|r| r.bloom).collect();
+        let seed: Vec<u128> = manifest.rewards.iter().map(|r| r.seed).collect();
+        assert_eq!(bloom, vec![7_500, 0, 2_500]);
+        assert_eq!(seed, vec![1_000, 3_000, 1_000]);
+        assert_eq!((manifest.bloom_withheld, manifest.seed_withheld), (0, 0));
+    }
+
+    #[test]
+    fn test_proposer_share_and_liveness_penalty() {
+        let calc = RewardCalculator::new(RewardParams::default());
+        let mut proposer = input(1, 100, 0);
+        proposer.blocks_proposed = 4;
+        let mut flaky = input(2, 100, 0);
+        flaky.liveness_misses = 3;
+
+        let manifest = calc.distribute("bizra-testnet-001", 7, &[proposer, flaky]).unwrap();
+
+        // SEED 5,000: proposer pool 1,000 to validator 1; attester pool 4,000 split evenly
+        assert_eq!(manifest.rewards[0].seed, 3_000);
+        // Validator 2: gross 2,000, 30% withheld
+        assert_eq!((manifest.rewards[1].seed, manifest.rewards[1].seed_penalty), (1_400, 600));
+        assert_eq!(manifest.seed_withheld, 600);
+
+        // No PoI carry: the BLOOM attester pool is not minted
+        assert_eq!(manifest.rewards[0].bloom, 2_000);
+        assert_eq!(manifest.bloom_withheld, 8_000);
+        manifest.check_conservation().unwrap();
+    }
+
+    #[test]
+    fn test_manifest_signature() {
+        let calc = RewardCalculator::new(RewardParams::default());
+        let mut manifest = calc.distribute("bizra-testnet-001", 7, &[input(1, 100, 5_000)]).unwrap();
+        assert_eq!(manifest.verify(), Err(RewardError::Unsigned(7)));
+
+        manifest.sign(&SigningKey::from_bytes(&[9u8; 32])).unwrap();
+        manifest.verify().unwrap();
+
+        // Round trip through JSON keeps the signature valid
+        let decoded: RewardManifest = serde_json::from_str(&serde_json::to_string(&manifest).unwrap()).unwrap();
+        decoded.verify().unwrap();
+
+        let mut tampered = decoded.clone();
+        tampered.rewards[0].blocks_proposed = 1;
+        assert_eq!(tampered.verify(), Err(RewardError::BadSignature));
+
+        let mut inflated = decoded;
+        inflated.bloom_minted += 1;
+        assert!(matches!(inflated.verify(), Err(RewardError::NotConserved(_))));
+
+        // Lines that overflow when summed are rejected, not wrapped
+        let mut overflowing = manifest.clone();
+        overflowing.rewards.push(overflowing.rewards[0].clone());
+        overflowing.rewards[0].bloom = u128::MAX;
+        overflowing.rewards[1].bloom = u128::MAX;
+        assert!(matches!(overflowing.check_conservation(), Err(RewardError::NotConserved(_))));
+
+        assert!(matches!(
+            calc.distribute("bizra-testnet-001", 7, &[input(1, 1, 1), input(1, 2, 2)]),
+            Err(RewardError::DuplicateValidator(_))
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+//! Versioned PoI measurement schemas
+//!
+//! A measurement schema fixes which impact dimensions an attestation may
+//! report, the bounds of each dimension score, and the range its weight must
+//! fall in. Attestations name their schema in `measurement.schema`; when
+//! absent, the attestation `version` is used (so "poi-1.0" attestations are
+//! checked against the "poi-1.0" schema).
+//!
+//! Spec reference: BIZRA_Proof_of_Impact_Formal_Spec_v1.0.md section 3
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::poi::{Measurement, POI_VERSION};
+
+/// One registered impact dimension
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DimensionSpec {
+    /// Dimension name as it appears in `dimensions`/`weights`
+    pub name: String,
+
+    /// Inclusive score bounds
+    pub min: f64,
+    pub max: f64,
+
+    /// Inclusive weight bounds (equal bounds pin a required weight)
+    pub weight_min: f64,
+    pub weight_max: f64,
+}
+
+impl DimensionSpec {
+    pub fn new(name: &str, (min, max): (f64, f64), (weight_min, weight_max): (f64, f64)) -> Self {
+        Self {
+            name: name.to_string(),
+            min,
+            max,
+            weight_min,
+            weight_max,
+        }
+    }
+}
+
+/// Versioned set of allowed dimensions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeasurementSchema {
+    /// Schema identifier (e.g., "poi-1.0")
+    pub id: String,
+
+    /// Every dimension is required; nothing else is accepted
+    pub dimensions: Vec<DimensionSpec>,
+}
+
+impl MeasurementSchema {
+    /// The "poi-1.0" schema: six dimensions scored in [0,1]
+    pub fn poi_v1() -> Self {
+        Self {
+            id: POI_VERSION.to_string(),
+            dimensions: vec![
+                DimensionSpec::new("quality", (0.0, 1.0), (0.10, 0.40)),
+                DimensionSpec::new("utility", (0.0, 1.0), (0.10, 0.40)),
+                DimensionSpec::new("efficiency", (0.0, 1.0), (0.05, 0.30)),
+                DimensionSpec::new("trust", (0.0, 1.0), (0.05, 0.30)),
+                DimensionSpec::new("fairness", (0.0, 1.0), (0.05, 0.20)),
+                DimensionSpec::new("diversity", (0.0, 1.0), (0.05, 0.20)),
+            ],
+        }
+    }
+
+    /// Look up a dimension by name
+    pub fn dimension(&self, name: &str) -> Option<&DimensionSpec> {
+        self.dimensions.iter().find(|d| d.name == name)
+    }
+
+    /// Check a measurement against this schema
+    ///
+    /// Rejects unknown dimensions or weights, missing dimensions or weights,
+    /// scores outside their bounds and weights outside their ranges.
+    pub fn check(&self, measurement: &Measurement) -> Result<()> {
+        for name in measurement.dimensions.keys().chain(measurement.weights.keys()) {
+            if self.dimension(name).is_none() {
+                bail!("Unknown dimension '{}' for schema {}", name, self.id);
+            }
+        }
+
+        for spec in &self.dimensions {
+            let Some(score) = measurement.dimensions.get(&spec.name) else {
+                bail!("Missing dimension '{}' required by schema {}", spec.name, self.id);
+            };
+            let Some(weight) = measurement.weights.get(&spec.name) else {
+                bail!("Missing weight for dimension '{}' required by schema {}", spec.name, self.id);
+            };
+
+            if !(spec.min..=spec.max).contains(score) {
+                bail!("Dimension '{}' out of range [{},{}]: {:.6}",
+                    spec.name, spec.min, spec.max, score);
+            }
+            if !(spec.weight_min..=spec.weight_max).contains(weight) {
+                bail!("Weight for '{}' out of range [{},{}]: {:.6}",
+                    spec.name, spec.weight_min, spec.weight_max, weight);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Registry of measurement schemas by identifier
+#[derive(Debug, Clone)]
+pub struct MeasurementSchemaRegistry {
+    schemas: HashMap<String, MeasurementSchema>,
+}
+
+impl MeasurementSchemaRegistry {
+    /// Empty registry
+    pub fn empty() -> Self {
+        Self { schemas: HashMap::new() }
+    }
+
+    /// Register (or replace) a schema
+    pub fn register(&mut self, schema: MeasurementSchema) {
+        self.schemas.insert(schema.id.clone(), schema);
+    }
+
+    /// Get schema by identifier
+    pub fn get(&self, id: &str) -> Option<&MeasurementSchema> {
+        self.schemas.get(id)
+    }
+
+    /// Check a measurement against the named schema
+    pub fn check(&self, schema_id: &str, measurement: &Measurement) -> Result<()> {
+        match self.get(schema_id) {
+            Some(schema) => schema.check(measurement),
+            None => bail!("Unknown measurement schema: {}", schema_id),
+        }
+    }
+}
+
+impl Default for MeasurementSchemaRegistry {
+    /// Registry with the built-in "poi-1.0" schema
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(MeasurementSchema::poi_v1());
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn measurement(entries: &[(&str, f64, f64)]) -> Measurement {
+        Measurement {
+            schema: None,
+            dimensions: entries.iter().map(|(n, s, _)| (n.to_string(), *s)).collect::<BTreeMap<_, _>>(),
+            weights: entries.iter().map(|(n, _, w)| (n.to_string(), *w)).collect::<BTreeMap<_, _>>(),
+            impact_score: 0.0,
+        }
+    }
+
+    fn full() -> Vec<(&'static str, f64, f64)> {
+        vec![
+            ("quality", 0.9, 0.25),
+            ("utility", 0.8, 0.25),
+            ("efficiency", 0.7, 0.15),
+            ("trust", 0.6, 0.15),
+            ("fairness", 0.5, 0.10),
+            ("diversity", 0.4, 0.10),
+        ]
+    }
+
+    #[test]
+    fn test_poi_v1_accepts_full_measurement() {
+        let registry = MeasurementSchemaRegistry::default();
+        registry.check("poi-1.0", &measurement(&full())).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_and_missing_dimensions() {
+        let schema = MeasurementSchema::poi_v1();
+
+        let mut extra = full();
+        extra.push(("novelty", 0.5, 0.0));
+        assert!(schema.check(&measurement(&extra)).unwrap_err().to_string().contains("Unknown dimension 'novelty'"));
+
+        let missing: Vec<_> = full().into_iter().filter(|(n, _, _)| *n != "fairness").collect();
+        assert!(schema.check(&measurement(&missing)).unwrap_err().to_string().contains("Missing dimension 'fairness'"));
+
+        let mut no_weight = measurement(&full());
+        no_weight.weights.remove("trust");
+        assert!(schema.check(&no_weight).unwrap_err().to_string().contains("Missing weight"));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let schema = MeasurementSchema::poi_v1();
+
+        let mut m = measurement(&full());
+        m.dimensions.insert("quality".to_string(), 1.2);
+        assert!(schema.check(&m).unwrap_err().to_string().contains("out of range"));
+
+        let mut m = measurement(&full());
+        m.weights.insert("diversity".to_string(), 0.5);
+        assert!(schema.check(&m).unwrap_err().to_string().contains("Weight for 'diversity'"));
+    }
+
+    #[test]
+    fn test_custom_schema_registration() {
+        let mut registry = MeasurementSchemaRegistry::empty();
+        assert!(registry.check("poi-1.0", &measurement(&full())).is_err());
+
+        registry.register(MeasurementSchema {
+            id: "compute-1.0".to_string(),
+            dimensions: vec![DimensionSpec::new("throughput", (0.0, 1.0), (1.0, 1.0))],
+        });
+        registry.check("compute-1.0", &measurement(&[("throughput", 0.3, 1.0)])).unwrap();
+    }
+}
@@ -1,8 +1,41 @@
 //! Slashing logic for validator misbehavior
 //!
 //! Implements slashing as specified in spec section 7
+//!
+//! ## Proof Formats
+//!
+//! `SlashEvidence::proof` is the canonical JSON of a reason-specific proof:
+//!
+//! | Reason          | Proof                    | Verified by                                          |
+//! |-----------------|--------------------------|------------------------------------------------------|
+//! | `Equivocation`  | [`EquivocationProof`]    | two conflicting consensus messages signed for the    |
+//! |                 |                          | same view (the evidence slot) on this chain          |
+//! | `FraudulentPoI` | [`FraudulentPoIProof`]   | attestation signed by the validator key whose        |
+//! |                 |                          | claimed metrics failed benchmark re-execution        |
+//! | `Censorship`    | [`CensorshipProof`]      | at least [`MIN_CENSORSHIP_SLOTS`] distinct past slots |
+//! |                 |                          | (shape only: see below)                              |
+//! | `KeyTheft`      | [`KeyTheftProof`]        | published signature over the key-theft statement,    |
+//! |                 |                          | or a consensus message signed on this chain by a key |
+//! |                 |                          | rotated out before its view                          |
+//!
+//! Only the signature checks above tie a proof to the accused validator. A
+//! failed benchmark claim is trusted only once the registry has re-executed it
+//! ([`ValidatorRegistry::with_benchmark_verifier`](crate::ValidatorRegistry::with_benchmark_verifier)),
+//! and censorship claims are not accepted by the registry at all: nothing the
+//! validator signed shows a refusal.
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::benchmark::FraudulentPoIProof;
+use crate::consensus_message::SignedConsensusMessage;
+use crate::types::{Ed25519PublicKey, ValidatorId, ValidatorRecord, ValidatorStatus};
+
+/// Domain separator for key-theft statements
+pub const KEY_THEFT_DOMAIN: &[u8] = b"BIZRA-KEY-THEFT-v1";
+
+/// Minimum number of refused slots for censorship evidence
+pub const MIN_CENSORSHIP_SLOTS: usize = 8;
 
 /// Slashable fault types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -29,6 +62,259 @@ pub struct SlashEvidence {
     pub proof: Vec<u8>,
 }
 
+/// Slash evidence rejections
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SlashError {
+    #[error("Unknown validator {0}")]
+    UnknownValidator(String),
+
+    #[error("Invalid {reason:?} proof: {detail}")]
+    InvalidProof { reason: SlashReason, detail: String },
+
+    #[error("Duplicate slash evidence for validator {validator} ({reason:?} at slot {slot})")]
+    Duplicate {
+        validator: String,
+        reason: SlashReason,
+        slot: u64,
+    },
+
+    #[error("Validator {0} is already slashed")]
+    AlreadySlashed(String),
+
+    #[error("Evidence slot {slot} is after the current epoch {current_epoch}")]
+    FutureSlot { slot: u64, current_epoch: u64 },
+
+    #[error("{0:?} evidence cannot be verified by this registry")]
+    Unverifiable(SlashReason),
+}
+
+/// Two conflicting consensus messages signed by the validator for the same view
+///
+/// See [`ConsensusMessage::conflicts_with`](crate::consensus_message::ConsensusMessage::conflicts_with);
+/// the view is the evidence slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EquivocationProof {
+    pub message_a: SignedConsensusMessage,
+    pub message_b: SignedConsensusMessage,
+}
+
+/// Slots at which the validator refused to reference valid blocks
+///
+/// The referenced blocks are checked by the consensus layer before the
+/// evidence is submitted; the registry checks the shape of the claim.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CensorshipProof {
+    /// Refused slots, strictly ascending
+    pub slots: Vec<u64>,
+}
+
+/// Signature only a holder of a compromised key can produce
+///
+/// Either a signature by the current key over the key-theft statement, or a
+/// consensus message signed by a rotated-out key for a view at or after its
+/// rotation epoch (the honest operator no longer signs with that key).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyTheftProof {
+    /// Ed25519 signature over `key_theft_message(validator_id)` (hex)
+    Statement { signature: String },
+
+    /// Consensus message signed by a rotated-out key
+    RetiredKey {
+        /// Rotated-out key that made the signature (hex)
+        retired_key: String,
+        message: SignedConsensusMessage,
+    },
+}
+
+impl KeyTheftProof {
+    /// Proof from a published key-theft statement signature
+    pub fn statement(signature: &[u8]) -> Self {
+        Self::Statement { signature: hex::encode(signature) }
+    }
+
+    /// Proof from a consensus message signed by a rotated-out key
+    pub fn retired_key(pk_ed25519: &Ed25519PublicKey, message: SignedConsensusMessage) -> Self {
+        Self::RetiredKey { retired_key: hex::encode(pk_ed25519), message }
+    }
+}
+
+/// Statement whose signature proves the validator key has leaked
+pub fn key_theft_message(validator_id: &ValidatorId) -> Vec<u8> {
+    [KEY_THEFT_DOMAIN, validator_id.as_slice()].concat()
+}
+
+impl SlashEvidence {
+    /// Deduplication key: one slash per validator, reason and slot
+    pub fn dedup_key(&self) -> [u8; 32] {
+        dedup_key(&self.validator_id, self.reason, self.slot)
+    }
+
+    /// Verify the proof against the reason and the validator's public key
+    ///
+    /// Consensus messages in the proof must be signed for `chain_id`.
+    pub fn verify(&self, pk_ed25519: &Ed25519PublicKey, chain_id: &str) -> Result<(), SlashError> {
+        let invalid = |detail: String| SlashError::InvalidProof { reason: self.reason, detail };
+
+        match self.reason {
+            SlashReason::Equivocation => {
+                let proof: EquivocationProof = self.decode_proof()?;
+                for message in [&proof.message_a, &proof.message_b] {
+                    self.check_message(message, pk_ed25519, chain_id)?;
+                }
+                if !proof.message_a.message.conflicts_with(&proof.message_b.message) {
+                    return Err(invalid("messages do not conflict".to_string()));
+                }
+            }
+            SlashReason::FraudulentPoI => {
+                let proof: FraudulentPoIProof = self.decode_proof()?;
+                let attestation = &proof.attestation;
+                attestation.verify_signature().map_err(|e| invalid(format!("attestation signature: {}", e)))?;
+                if attestation.attester_ed25519_key().ok().as_ref() != Some(pk_ed25519) {
+                    return Err(invalid("attestation is not signed by the validator key".to_string()));
+                }
+                if attestation.evidence.methodology_ref.as_deref() != Some(proof.methodology_ref.as_str()) {
+                    return Err(invalid("methodology_ref does not match the attestation".to_string()));
+                }
+                if proof.mismatches.is_empty() && proof.reason.is_empty() {
+                    return Err(invalid("no failed claim".to_string()));
+                }
+                let claimed = attestation.benchmarks.as_ref().map(|b| &b.post);
+                for mismatch in &proof.mismatches {
+                    if claimed.and_then(|post| post.get(&mismatch.metric)) != Some(&mismatch.claimed) {
+                        return Err(invalid(format!("metric {} is not claimed by the attestation", mismatch.metric)));
+                    }
+                    if mismatch.reproduced == Some(mismatch.claimed) {
+                        return Err(invalid("mismatch reproduces the claimed value".to_string()));
+                    }
+                }
+            }
+            SlashReason::Censorship => {
+                let proof: CensorshipProof = self.decode_proof()?;
+                if proof.slots.len() < MIN_CENSORSHIP_SLOTS {
+                    return Err(invalid(format!("{} slots < {}", proof.slots.len(), MIN_CENSORSHIP_SLOTS)));
+                }
+                if !proof.slots.windows(2).all(|w| w[0] < w[1]) {
+                    return Err(invalid("slots must be strictly ascending".to_string()));
+                }
+                if proof.slots.last().is_some_and(|&last| last > self.slot) {
+                    return Err(invalid("slots must not exceed the evidence slot".to_string()));
+                }
+            }
+            SlashReason::KeyTheft => {
+                let KeyTheftProof::Statement { signature } = self.decode_proof()? else {
+                    return Err(invalid("retired-key proofs need the validator record".to_string()));
+                };
+                let signature = decode_hex(&signature).map_err(invalid)?;
+                if !poi::verify_attestation(&key_theft_message(&self.validator_id), pk_ed25519, &signature) {
+                    return Err(invalid("signature does not verify against the validator key".to_string()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify the proof against the validator's current and rotated-out keys
+    ///
+    /// `slot_epoch` is the epoch of `self.slot`. A retired-key signature only
+    /// counts for slots in or after the epoch the key was rotated out.
+    pub fn verify_for(&self, validator: &ValidatorRecord, slot_epoch: u64, chain_id: &str) -> Result<(), SlashError> {
+        if self.reason != SlashReason::KeyTheft {
+            return self.verify(&validator.pk_ed25519, chain_id);
+        }
+        let KeyTheftProof::RetiredKey { retired_key, message } = self.decode_proof()? else {
+            return self.verify(&validator.pk_ed25519, chain_id);
+        };
+
+        let invalid = |detail: String| SlashError::InvalidProof { reason: self.reason, detail };
+        let pk: Ed25519PublicKey = decode_hex(&retired_key)
+            .map_err(invalid)?
+            .try_into()
+            .map_err(|_| invalid("retired_key must be 32 bytes".to_string()))?;
+        let retired = validator
+            .retired_key(&pk)
+            .ok_or_else(|| invalid("key is not a rotated-out key of the validator".to_string()))?;
+        if slot_epoch < retired.retired_at {
+            return Err(invalid(format!("slot epoch {} is before the key was retired at epoch {}", slot_epoch, retired.retired_at)));
+        }
+
+        self.check_message(&message, &pk, chain_id)
+    }
+
+    /// Check that `signed` is by the accused validator, for the evidence
+    /// slot on `chain_id`, and signed by `pk_ed25519`
+    fn check_message(
+        &self,
+        signed: &SignedConsensusMessage,
+        pk_ed25519: &Ed25519PublicKey,
+        chain_id: &str,
+    ) -> Result<(), SlashError> {
+        let invalid = |detail: &str| SlashError::InvalidProof { reason: self.reason, detail: detail.to_string() };
+        let message = &signed.message;
+        if message.signer_id() != self.validator_id.as_slice() {
+            return Err(invalid("message is not signed by the accused validator"));
+        }
+        if signed.chain_id != chain_id {
+            return Err(invalid("message is signed for another chain"));
+        }
+        if message.view() != self.slot {
+            return Err(invalid("message view is not the evidence slot"));
+        }
+        if !signed.verify_signature(pk_ed25519) {
+            return Err(invalid("signature does not verify against the validator key"));
+        }
+        Ok(())
+    }
+
+    fn decode_proof<T: for<'de> Deserialize<'de>>(&self) -> Result<T, SlashError> {
+        serde_json::from_slice(&self.proof).map_err(|e| SlashError::InvalidProof {
+            reason: self.reason,
+            detail: e.to_string(),
+        })
+    }
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value).map_err(|e| format!("invalid hex: {}", e))
+}
+
+/// Applied slash, as recorded by the registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlashEvent {
+    pub validator_id: ValidatorId,
+    pub reason: SlashReason,
+    pub slot: u64,
+
+    /// Epoch in which the slash was applied
+    pub epoch: u64,
+
+    /// Status before the slash
+    pub previous_status: ValidatorStatus,
+
+    pub weight_slashed: u128,
+    pub bond_burned: u128,
+    pub rep_penalty: u64,
+
+    /// First epoch after the quarantine
+    pub quarantine_until: u64,
+}
+
+impl SlashEvent {
+    /// Deduplication key of the evidence this slash applied
+    pub fn dedup_key(&self) -> [u8; 32] {
+        dedup_key(&self.validator_id, self.reason, self.slot)
+    }
+}
+
+fn dedup_key(validator_id: &ValidatorId, reason: SlashReason, slot: u64) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(validator_id);
+    hasher.update(&[reason as u8]);
+    hasher.update(&slot.to_be_bytes());
+    *hasher.finalize().as_bytes()
+}
+
 /// Slashing parameters
 pub struct SlashingPolicy {
     /// Weight reduction factor (0.5-0.95)
@@ -89,6 +375,134 @@ mod tests {
         assert_eq!(policy.quarantine_epochs, 4);
     }
 
+    const CHAIN: &str = "bizra-test";
+
+    fn signing_key() -> ed25519_dalek::SigningKey {
+        ed25519_dalek::SigningKey::from_bytes(&[9u8; 32])
+    }
+
+    fn evidence<T: Serialize>(reason: SlashReason, slot: u64, proof: &T) -> SlashEvidence {
+        SlashEvidence { validator_id: [1u8; 32], reason, slot, proof: serde_json::to_vec(proof).unwrap() }
+    }
+
+    #[test]
+    fn test_verify_equivocation() {
+        use crate::consensus_message::{ConsensusMessage, VoteType};
+
+        let key = signing_key();
+        let pk = key.verifying_key().to_bytes();
+        let vote = |block: u8, voter: [u8; 32]| {
+            let message = ConsensusMessage::Vote {
+                block_hash: vec![block; 32],
+                voter_id: voter.to_vec(),
+                vote_type: VoteType::Prepare,
+                view: 42,
+                signature: Vec::new(),
+            };
+            SignedConsensusMessage::new(message, &key, CHAIN, 1_000)
+        };
+
+        let proof = EquivocationProof { message_a: vote(1, [1u8; 32]), message_b: vote(2, [1u8; 32]) };
+        assert!(evidence(SlashReason::Equivocation, 42, &proof).verify(&pk, CHAIN).is_ok());
+
+        // Messages bind the view
+        let err = evidence(SlashReason::Equivocation, 43, &proof).verify(&pk, CHAIN).unwrap_err();
+        assert!(err.to_string().contains("view"));
+
+        let same = EquivocationProof { message_b: proof.message_a.clone(), ..proof.clone() };
+        let err = evidence(SlashReason::Equivocation, 42, &same).verify(&pk, CHAIN).unwrap_err();
+        assert!(err.to_string().contains("do not conflict"));
+
+        // Signed by the key but naming another validator
+        let other = EquivocationProof { message_a: vote(1, [2u8; 32]), message_b: vote(2, [2u8; 32]) };
+        assert!(evidence(SlashReason::Equivocation, 42, &other).verify(&pk, CHAIN).is_err());
+
+        // Messages signed for another chain
+        let err = evidence(SlashReason::Equivocation, 42, &proof).verify(&pk, "bizra-other").unwrap_err();
+        assert!(err.to_string().contains("another chain"));
+
+        // Signature for another timestamp
+        let mut replayed = proof.clone();
+        replayed.message_b.timestamp += 1;
+        let err = evidence(SlashReason::Equivocation, 42, &replayed).verify(&pk, CHAIN).unwrap_err();
+        assert!(err.to_string().contains("does not verify"));
+
+        // Signatures by someone else
+        let forger = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]);
+        let mut forged = proof;
+        forged.message_b.message.sign(&forger, CHAIN, 1_000);
+        let err = evidence(SlashReason::Equivocation, 42, &forged).verify(&pk, CHAIN).unwrap_err();
+        assert!(err.to_string().contains("does not verify"));
+    }
+
+    #[test]
+    fn test_verify_fraudulent_poi() {
+        use crate::benchmark::MetricMismatch;
+        use crate::poi::tests::test_attestation;
+        use crate::poi::Benchmarks;
+
+        let pk = signing_key().verifying_key().to_bytes();
+        let mut attestation = test_attestation();
+        attestation.evidence.methodology_ref = Some("doi:10.1234/bench".to_string());
+        attestation.benchmarks = Some(Benchmarks {
+            pre: [("performance".to_string(), 0.1)].into(),
+            post: [("performance".to_string(), 0.9)].into(),
+            delta: 0.8,
+        });
+        attestation.sign(&signing_key()).unwrap();
+
+        let proof = FraudulentPoIProof {
+            attestation: attestation.clone(),
+            evidence_pack: String::new(),
+            methodology_ref: "doi:10.1234/bench".to_string(),
+            reason: "1 claimed metric(s) not reproduced".to_string(),
+            mismatches: vec![MetricMismatch { metric: "performance".to_string(), claimed: 0.9, reproduced: Some(0.3) }],
+        };
+        assert!(evidence(SlashReason::FraudulentPoI, 7, &proof).verify(&pk, CHAIN).is_ok());
+
+        // Attestation signed by someone else
+        let mut forged = proof.clone();
+        forged.attestation.sign(&ed25519_dalek::SigningKey::from_bytes(&[3u8; 32])).unwrap();
+        let err = evidence(SlashReason::FraudulentPoI, 7, &forged).verify(&pk, CHAIN).unwrap_err();
+        assert!(err.to_string().contains("not signed by the validator key"));
+
+        // Claim altered after signing
+        let mut tampered = proof.clone();
+        tampered.attestation.measurement.impact_score = 0.99;
+        assert!(evidence(SlashReason::FraudulentPoI, 7, &tampered).verify(&pk, CHAIN).is_err());
+
+        // Mismatch on a metric the attestation never claimed
+        let mut invented = proof;
+        invented.mismatches[0].claimed = 0.5;
+        let err = evidence(SlashReason::FraudulentPoI, 7, &invented).verify(&pk, CHAIN).unwrap_err();
+        assert!(err.to_string().contains("not claimed"));
+    }
+
+    #[test]
+    fn test_verify_censorship_and_malformed_proofs() {
+        let pk = signing_key().verifying_key().to_bytes();
+
+        let proof = CensorshipProof { slots: (10..10 + MIN_CENSORSHIP_SLOTS as u64).collect() };
+        assert!(evidence(SlashReason::Censorship, 100, &proof).verify(&pk, CHAIN).is_ok());
+        assert!(evidence(SlashReason::Censorship, 12, &proof).verify(&pk, CHAIN).is_err());
+
+        let short = CensorshipProof { slots: vec![1, 2, 3] };
+        assert!(evidence(SlashReason::Censorship, 100, &short).verify(&pk, CHAIN).is_err());
+
+        // Proof of the wrong kind for the reason
+        let err = evidence(SlashReason::KeyTheft, 100, &proof).verify(&pk, CHAIN).unwrap_err();
+        assert!(matches!(err, SlashError::InvalidProof { reason: SlashReason::KeyTheft, .. }));
+    }
+
+    #[test]
+    fn test_dedup_key_ignores_proof_bytes() {
+        let a = evidence(SlashReason::Censorship, 5, &CensorshipProof { slots: vec![1] });
+        let b = evidence(SlashReason::Censorship, 5, &CensorshipProof { slots: vec![2] });
+        let c = evidence(SlashReason::Equivocation, 5, &CensorshipProof { slots: vec![1] });
+        assert_eq!(a.dedup_key(), b.dedup_key());
+        assert_ne!(a.dedup_key(), c.dedup_key());
+    }
+
     #[test]
     fn test_slashing_policy_fraudulent_poi() {
         let policy = SlashingPolicy::for_reason(SlashReason::FraudulentPoI);
@@ -0,0 +1,467 @@
+//! Attestation storage and query API
+//!
+//! Accepted PoI attestations are kept in an [`AttestationStore`], indexed by
+//! digest, attester, epoch and chain ID. Two implementations are provided:
+//!
+//! - [`MemoryAttestationStore`]: in-process indexes only (tests, ephemeral nodes)
+//! - [`FileAttestationStore`]: embedded append-only JSONL log, fsync'd per
+//!   insert, indexes rebuilt in memory on open
+//!
+//! [`export_jsonl`] writes the records matching a query one per line, so
+//! auditors can replay an epoch's weight computation offline.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::poi::PoIAttestation;
+use crate::types::{current_timestamp, ValidatorId};
+
+/// Log file name inside a [`FileAttestationStore`] directory
+pub const ATTESTATION_LOG_FILE: &str = "attestations.jsonl";
+
+/// Default page size for queries
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Maximum page size for queries
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// Attestation store failures
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum StoreError {
+    #[error("Attestation {0} already stored")]
+    DuplicateDigest(String),
+
+    #[error("Corrupt attestation log at line {line}: {reason}")]
+    CorruptLog { line: usize, reason: String },
+}
+
+/// Accepted attestation with its index keys
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredAttestation {
+    /// BLAKE3 digest of the canonical payload (hex)
+    pub digest: String,
+
+    /// Epoch the attestation was accepted in
+    pub epoch: u64,
+
+    /// Validator credited with the impact, if the attester is registered (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator_id: Option<String>,
+
+    /// Acceptance time (Unix seconds)
+    pub received_at: u64,
+
+    pub attestation: PoIAttestation,
+}
+
+impl StoredAttestation {
+    /// Build a record accepted now, computing the digest from the attestation
+    pub fn new(attestation: PoIAttestation, epoch: u64, validator_id: Option<ValidatorId>) -> Result<Self> {
+        Ok(Self {
+            digest: attestation.compute_digest()?,
+            epoch,
+            validator_id: validator_id.map(hex::encode),
+            received_at: current_timestamp(),
+            attestation,
+        })
+    }
+
+    pub fn attester(&self) -> &str {
+        &self.attestation.attester.id
+    }
+
+    pub fn chain_id(&self) -> &str {
+        &self.attestation.anchor.chain_id
+    }
+}
+
+/// Query filters plus pagination (all filters are ANDed)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttestationQuery {
+    pub attester: Option<String>,
+    pub epoch: Option<u64>,
+    pub chain_id: Option<String>,
+
+    /// Records to skip (insertion order)
+    pub offset: usize,
+
+    /// Page size, clamped to `MAX_PAGE_SIZE`
+    pub limit: usize,
+}
+
+impl Default for AttestationQuery {
+    fn default() -> Self {
+        Self {
+            attester: None,
+            epoch: None,
+            chain_id: None,
+            offset: 0,
+            limit: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl AttestationQuery {
+    pub fn attester(mut self, attester: impl Into<String>) -> Self {
+        self.attester = Some(attester.into());
+        self
+    }
+
+    pub fn epoch(mut self, epoch: u64) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
+    pub fn chain_id(mut self, chain_id: impl Into<String>) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    pub fn page(mut self, offset: usize, limit: usize) -> Self {
+        self.offset = offset;
+        self.limit = limit;
+        self
+    }
+
+    fn matches(&self, record: &StoredAttestation) -> bool {
+        self.attester.as_deref().is_none_or(|a| record.attester() == a)
+            && self.epoch.is_none_or(|e| record.epoch == e)
+            && self.chain_id.as_deref().is_none_or(|c| record.chain_id() == c)
+    }
+}
+
+/// One page of query results
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttestationPage {
+    pub items: Vec<StoredAttestation>,
+
+    /// Total matching records across all pages
+    pub total: usize,
+
+    /// Offset of the next page, if any
+    pub next_offset: Option<usize>,
+}
+
+/// Attestation storage backend
+pub trait AttestationStore: Send + Sync {
+    /// Store a record; fails with [`StoreError::DuplicateDigest`] if the digest exists
+    fn insert(&mut self, record: StoredAttestation) -> Result<()>;
+
+    /// Look up by digest
+    fn get(&self, digest: &str) -> Option<StoredAttestation>;
+
+    /// Filtered, paginated listing in insertion order
+    fn query(&self, query: &AttestationQuery) -> AttestationPage;
+
+    /// Number of stored records
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Visit every stored record in insertion order, a page at a time
+pub fn for_each_record(
+    store: &dyn AttestationStore,
+    mut visit: impl FnMut(&StoredAttestation) -> Result<()>,
+) -> Result<()> {
+    let mut query = AttestationQuery { limit: MAX_PAGE_SIZE, ..Default::default() };
+    loop {
+        let page = store.query(&query);
+        for record in &page.items {
+            visit(record)?;
+        }
+        match page.next_offset {
+            Some(offset) => query.offset = offset,
+            None => return Ok(()),
+        }
+    }
+}
+
+/// In-memory indexed store
+#[derive(Debug, Default)]
+pub struct MemoryAttestationStore {
+    records: Vec<StoredAttestation>,
+    by_digest: HashMap<String, usize>,
+    by_attester: HashMap<String, Vec<usize>>,
+    by_epoch: BTreeMap<u64, Vec<usize>>,
+    by_chain: HashMap<String, Vec<usize>>,
+}
+
+impl MemoryAttestationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Smallest index list covering the query, `None` meaning "all records"
+    fn candidates(&self, query: &AttestationQuery) -> Option<&[usize]> {
+        const EMPTY: &[usize] = &[];
+
+        let lists = [
+            query.attester.as_ref().map(|a| self.by_attester.get(a).map_or(EMPTY, Vec::as_slice)),
+            query.epoch.map(|e| self.by_epoch.get(&e).map_or(EMPTY, Vec::as_slice)),
+            query.chain_id.as_ref().map(|c| self.by_chain.get(c).map_or(EMPTY, Vec::as_slice)),
+        ];
+        lists.into_iter().flatten().min_by_key(|l| l.len())
+    }
+}
+
+impl AttestationStore for MemoryAttestationStore {
+    fn insert(&mut self, record: StoredAttestation) -> Result<()> {
+        if self.by_digest.contains_key(&record.digest) {
+            return Err(StoreError::DuplicateDigest(record.digest).into());
+        }
+
+        let idx = self.records.len();
+        self.by_digest.insert(record.digest.clone(), idx);
+        self.by_attester.entry(record.attester().to_string()).or_default().push(idx);
+        self.by_epoch.entry(record.epoch).or_default().push(idx);
+        self.by_chain.entry(record.chain_id().to_string()).or_default().push(idx);
+        self.records.push(record);
+        Ok(())
+    }
+
+    fn get(&self, digest: &str) -> Option<StoredAttestation> {
+        self.by_digest.get(digest).map(|&idx| self.records[idx].clone())
+    }
+
+    fn query(&self, query: &AttestationQuery) -> AttestationPage {
+        let limit = query.limit.clamp(1, MAX_PAGE_SIZE);
+        let matching: Vec<&StoredAttestation> = match self.candidates(query) {
+            Some(indexes) => indexes.iter().map(|&i| &self.records[i]).filter(|r| query.matches(r)).collect(),
+            None => self.records.iter().collect(),
+        };
+
+        let total = matching.len();
+        let items: Vec<StoredAttestation> = matching.into_iter().skip(query.offset).take(limit).cloned().collect();
+        let end = query.offset.saturating_add(items.len());
+
+        AttestationPage {
+            items,
+            total,
+            next_offset: (end < total).then_some(end),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.records.len()
+    }
+}
+
+/// Embedded on-disk store: append-only JSONL log plus in-memory indexes
+#[derive(Debug)]
+pub struct FileAttestationStore {
+    path: PathBuf,
+    log: File,
+    index: MemoryAttestationStore,
+}
+
+impl FileAttestationStore {
+    /// Open (or create) a store in `dir`, replaying the log into the indexes
+    ///
+    /// A torn final line from an interrupted write is truncated; any other
+    /// unparsable line fails with [`StoreError::CorruptLog`].
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create attestation store {}", dir.display()))?;
+        let path = dir.join(ATTESTATION_LOG_FILE);
+
+        let mut log = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open attestation log {}", path.display()))?;
+
+        let mut contents = String::new();
+        log.read_to_string(&mut contents)?;
+
+        // Everything after the last newline is an incomplete append
+        let complete_len = contents.rfind('\n').map_or(0, |i| i + 1);
+        if complete_len < contents.len() {
+            warn!("Truncating torn record at end of {}", path.display());
+            log.set_len(complete_len as u64)?;
+        }
+
+        let mut index = MemoryAttestationStore::new();
+        for (i, line) in contents[..complete_len].lines().enumerate() {
+            let record: StoredAttestation = serde_json::from_str(line)
+                .map_err(|e| StoreError::CorruptLog { line: i + 1, reason: e.to_string() })?;
+            index.insert(record).map_err(|e| StoreError::CorruptLog { line: i + 1, reason: e.to_string() })?;
+        }
+
+        Ok(Self { path, log, index })
+    }
+
+    /// Path of the JSONL log
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AttestationStore for FileAttestationStore {
+    fn insert(&mut self, record: StoredAttestation) -> Result<()> {
+        if self.index.by_digest.contains_key(&record.digest) {
+            return Err(StoreError::DuplicateDigest(record.digest).into());
+        }
+
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        self.log.write_all(&line)?;
+        self.log.sync_data()?;
+
+        self.index.insert(record)
+    }
+
+    fn get(&self, digest: &str) -> Option<StoredAttestation> {
+        self.index.get(digest)
+    }
+
+    fn query(&self, query: &AttestationQuery) -> AttestationPage {
+        self.index.query(query)
+    }
+
+    fn len(&self) -> usize {
+        self.index.len()
+    }
+}
+
+/// Write every record matching `query` (ignoring its pagination) as JSONL
+///
+/// Returns the number of records written.
+pub fn export_jsonl<S, W>(store: &S, query: &AttestationQuery, mut out: W) -> Result<usize>
+where
+    S: AttestationStore + ?Sized,
+    W: Write,
+{
+    let mut page_query = query.clone().page(0, MAX_PAGE_SIZE);
+    let mut written = 0;
+
+    loop {
+        let page = store.query(&page_query);
+        for record in &page.items {
+            serde_json::to_writer(&mut out, record)?;
+            out.write_all(b"\n")?;
+            written += 1;
+        }
+        match page.next_offset {
+            Some(next) => page_query.offset = next,
+            None => break,
+        }
+    }
+
+    out.flush()?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poi::tests::{test_attestation, test_signing_key};
+
+    fn record(attester: &str, nonce_byte: u8, epoch: u64) -> StoredAttestation {
+        let mut attestation = test_attestation();
+        attestation.attester.id = attester.to_string();
+        attestation.nonce = format!("{:02x}", nonce_byte).repeat(16);
+        attestation.sign(&test_signing_key()).unwrap();
+        StoredAttestation::new(attestation, epoch, Some([nonce_byte; 32])).unwrap()
+    }
+
+    fn populate(store: &mut dyn AttestationStore) {
+        for i in 0..10u8 {
+            let attester = if i % 2 == 0 { "node0:bizra" } else { "node1:bizra" };
+            store.insert(record(attester, i, (i / 4) as u64)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_indexes_and_pagination() {
+        let mut store = MemoryAttestationStore::new();
+        populate(&mut store);
+        assert_eq!(store.len(), 10);
+
+        let page = store.query(&AttestationQuery::default().attester("node0:bizra").page(0, 3));
+        assert_eq!(page.total, 5);
+        assert_eq!(page.items.len(), 3);
+        assert_eq!(page.next_offset, Some(3));
+
+        let page = store.query(&AttestationQuery::default().attester("node0:bizra").page(3, 3));
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.next_offset, None);
+
+        let page = store.query(&AttestationQuery::default().epoch(1).attester("node1:bizra"));
+        let nonces: Vec<_> = page.items.iter().map(|r| &r.attestation.nonce[..2]).collect();
+        assert_eq!(nonces, ["05", "07"]);
+
+        assert_eq!(store.query(&AttestationQuery::default().chain_id("bizra-testnet-001")).total, 10);
+        assert_eq!(store.query(&AttestationQuery::default().chain_id("bizra-main-alpha")).total, 0);
+
+        let first = record("node0:bizra", 0, 0);
+        assert_eq!(store.get(&first.digest).unwrap().attestation, first.attestation);
+        assert!(store.get(&"00".repeat(32)).is_none());
+    }
+
+    #[test]
+    fn test_duplicate_digest_rejected() {
+        let mut store = MemoryAttestationStore::new();
+        store.insert(record("node0:bizra", 1, 0)).unwrap();
+
+        let err = store.insert(record("node0:bizra", 1, 3)).unwrap_err();
+        assert!(matches!(err.downcast_ref::<StoreError>(), Some(StoreError::DuplicateDigest(_))));
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn test_file_store_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut store = FileAttestationStore::open(dir.path()).unwrap();
+            populate(&mut store);
+        }
+
+        // Simulate a crash mid-append
+        let log_path = dir.path().join(ATTESTATION_LOG_FILE);
+        let mut log = OpenOptions::new().append(true).open(&log_path).unwrap();
+        log.write_all(b"{\"digest\":\"trunc").unwrap();
+        drop(log);
+
+        let mut store = FileAttestationStore::open(dir.path()).unwrap();
+        assert_eq!(store.len(), 10);
+        assert_eq!(store.query(&AttestationQuery::default().epoch(2)).total, 2);
+
+        let err = store.insert(record("node1:bizra", 3, 0)).unwrap_err();
+        assert!(matches!(err.downcast_ref::<StoreError>(), Some(StoreError::DuplicateDigest(_))));
+
+        store.insert(record("node1:bizra", 42, 3)).unwrap();
+        drop(store);
+        assert_eq!(FileAttestationStore::open(dir.path()).unwrap().len(), 11);
+    }
+
+    #[test]
+    fn test_export_jsonl() {
+        let mut store = MemoryAttestationStore::new();
+        populate(&mut store);
+
+        let mut out = Vec::new();
+        let written = export_jsonl(&store, &AttestationQuery::default().epoch(0).page(0, 1), &mut out).unwrap();
+        assert_eq!(written, 4);
+
+        let lines: Vec<StoredAttestation> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|r| r.epoch == 0));
+        assert_eq!(lines[0].validator_id.as_deref(), Some(hex::encode([0u8; 32]).as_str()));
+    }
+}
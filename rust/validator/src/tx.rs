@@ -0,0 +1,527 @@
+//! Signed validator lifecycle transactions
+//!
+//! Every lifecycle change requested over the network arrives as a signed
+//! transaction and is validated before the registry touches any state:
+//!
+//! | Transaction     | Signed by           | Proves                                     |
+//! |-----------------|---------------------|--------------------------------------------|
+//! | [`JoinTx`]      | the joining key     | possession of `pk_ed25519`                 |
+//! | [`ExitTx`]      | the registered key  | the operator requests the exit             |
+//! | [`RotateKeyTx`] | old key and new key | the operator authorizes, and holds, the new key |
+//! | [`BondTx`]      | the registered key  | the operator moves funds in its bond ledger |
+//!
+//! The signed message is a domain separator followed by the RFC 8785 canonical
+//! JSON of the transaction without its signature fields. Byte fields are hex;
+//! amounts are decimal strings because they can exceed 2^53.
+//!
+//! A transaction is valid for [`TX_VALIDITY_EPOCHS`] epochs after its `epoch`,
+//! and only on the chain named by `chain_id`.
+
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::canonical::canonicalize;
+use crate::types::{validator_id_for, Ed25519PublicKey, ValidatorId};
+
+/// Domain separator for join transactions
+pub const JOIN_TX_DOMAIN: &[u8] = b"BIZRA-JOIN-TX-v1";
+
+/// Domain separator for exit transactions
+pub const EXIT_TX_DOMAIN: &[u8] = b"BIZRA-EXIT-TX-v1";
+
+/// Domain separator for key-rotation transactions
+pub const ROTATE_KEY_TX_DOMAIN: &[u8] = b"BIZRA-ROTATE-KEY-TX-v1";
+
+/// Domain separator for bond transactions
+pub const BOND_TX_DOMAIN: &[u8] = b"BIZRA-BOND-TX-v1";
+
+/// Epochs after `tx.epoch` during which a transaction is accepted
+pub const TX_VALIDITY_EPOCHS: u64 = 2;
+
+/// Transaction rejections
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TxError {
+    #[error("Transaction for chain {found}, expected {expected}")]
+    ChainMismatch { expected: String, found: String },
+
+    #[error("validator_id {validator_id} is not blake3(pk_ed25519)")]
+    IdKeyMismatch { validator_id: String },
+
+    #[error("Invalid {field} encoding: {reason}")]
+    InvalidEncoding { field: &'static str, reason: String },
+
+    #[error("{tx} signature does not verify")]
+    BadSignature { tx: &'static str },
+
+    #[error("Transaction epoch {tx_epoch} outside validity window at epoch {current_epoch}")]
+    OutsideValidityWindow { tx_epoch: u64, current_epoch: u64 },
+
+    #[error("Invalid network address {0:?}")]
+    InvalidAddress(String),
+
+    #[error("Bond transaction nonce {found}, expected {expected}")]
+    NonceMismatch { expected: u64, found: u64 },
+}
+
+/// Request to join the validator set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JoinTx {
+    pub chain_id: String,
+
+    /// blake3(pk_ed25519) (hex)
+    pub validator_id: String,
+
+    /// Ed25519 public key (hex)
+    pub pk_ed25519: String,
+
+    /// libp2p multiaddr
+    pub network_address: String,
+
+    /// Bond the operator intends to deposit (decimal string)
+    ///
+    /// Informational: a joining validator starts with no bond, which is only
+    /// credited by deposits through the bond ledger.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "opt_u128_string")]
+    pub bond: Option<u128>,
+
+    /// Epoch the transaction was created in
+    pub epoch: u64,
+
+    /// Ed25519 signature by `pk_ed25519` (hex)
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// Request to leave the validator set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitTx {
+    pub chain_id: String,
+
+    /// Validator leaving (hex)
+    pub validator_id: String,
+
+    /// Epoch the transaction was created in
+    pub epoch: u64,
+
+    /// Ed25519 signature by the registered key (hex)
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// Request to replace a validator's signing key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotateKeyTx {
+    pub chain_id: String,
+
+    /// Validator rotating its key (hex)
+    pub validator_id: String,
+
+    /// Replacement Ed25519 public key (hex)
+    pub new_pk_ed25519: String,
+
+    /// Epoch the transaction was created in
+    pub epoch: u64,
+
+    /// Ed25519 signature by the current key (hex)
+    #[serde(default)]
+    pub signature: String,
+
+    /// Ed25519 signature by the new key: proof of possession (hex)
+    #[serde(default)]
+    pub new_key_signature: String,
+}
+
+/// Bond ledger operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BondAction {
+    /// Deposit or top up the bond by `amount`
+    Deposit,
+
+    /// Start unbonding `amount`
+    Unbond,
+
+    /// Pay out everything released (no `amount`)
+    Withdraw,
+}
+
+/// Request to move funds in a validator's bond ledger
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BondTx {
+    pub chain_id: String,
+
+    /// Validator whose bond moves (hex)
+    pub validator_id: String,
+
+    pub action: BondAction,
+
+    /// Amount for deposits and unbonds (decimal string)
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "opt_u128_string")]
+    pub amount: Option<u128>,
+
+    /// One past the validator's last applied bond transaction
+    pub nonce: u64,
+
+    /// Epoch the transaction was created in
+    pub epoch: u64,
+
+    /// Ed25519 signature by the registered key (hex)
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl JoinTx {
+    /// Unsigned join request for `key`, with `validator_id` derived from the key
+    pub fn new(chain_id: impl Into<String>, key: &SigningKey, network_address: impl Into<String>, bond: Option<u128>, epoch: u64) -> Self {
+        let pk = key.verifying_key().to_bytes();
+        Self {
+            chain_id: chain_id.into(),
+            validator_id: hex::encode(validator_id_for(&pk)),
+            pk_ed25519: hex::encode(pk),
+            network_address: network_address.into(),
+            bond,
+            epoch,
+            signature: String::new(),
+        }
+    }
+
+    /// Message covered by the signature
+    pub fn signing_payload(&self) -> Result<Vec<u8>, TxError> {
+        signing_payload(JOIN_TX_DOMAIN, self, &["signature"])
+    }
+
+    /// Sign with the joining key
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), TxError> {
+        self.signature = hex::encode(key.sign(&self.signing_payload()?).to_bytes());
+        Ok(())
+    }
+
+    /// Validate and return the joining validator's id and key
+    pub fn validate(&self, chain_id: &str, current_epoch: u64) -> Result<(ValidatorId, Ed25519PublicKey), TxError> {
+        check_chain_and_epoch(chain_id, &self.chain_id, self.epoch, current_epoch)?;
+        check_network_address(&self.network_address)?;
+
+        let validator_id = decode_32("validator_id", &self.validator_id)?;
+        let pk = decode_32("pk_ed25519", &self.pk_ed25519)?;
+        if validator_id != validator_id_for(&pk) {
+            return Err(TxError::IdKeyMismatch { validator_id: self.validator_id.clone() });
+        }
+
+        verify("JoinTx", &self.signing_payload()?, &pk, &self.signature)?;
+        Ok((validator_id, pk))
+    }
+}
+
+impl ExitTx {
+    /// Unsigned exit request
+    pub fn new(chain_id: impl Into<String>, validator_id: &ValidatorId, epoch: u64) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            validator_id: hex::encode(validator_id),
+            epoch,
+            signature: String::new(),
+        }
+    }
+
+    /// Message covered by the signature
+    pub fn signing_payload(&self) -> Result<Vec<u8>, TxError> {
+        signing_payload(EXIT_TX_DOMAIN, self, &["signature"])
+    }
+
+    /// Sign with the registered key
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), TxError> {
+        self.signature = hex::encode(key.sign(&self.signing_payload()?).to_bytes());
+        Ok(())
+    }
+
+    /// Id of the exiting validator (checked before its key is looked up)
+    pub fn validator_id(&self) -> Result<ValidatorId, TxError> {
+        decode_32("validator_id", &self.validator_id)
+    }
+
+    /// Validate against the validator's registered key
+    pub fn validate(&self, chain_id: &str, current_epoch: u64, registered_pk: &Ed25519PublicKey) -> Result<(), TxError> {
+        check_chain_and_epoch(chain_id, &self.chain_id, self.epoch, current_epoch)?;
+        verify("ExitTx", &self.signing_payload()?, registered_pk, &self.signature)
+    }
+}
+
+impl RotateKeyTx {
+    /// Unsigned rotation request
+    pub fn new(chain_id: impl Into<String>, validator_id: &ValidatorId, new_pk_ed25519: &Ed25519PublicKey, epoch: u64) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            validator_id: hex::encode(validator_id),
+            new_pk_ed25519: hex::encode(new_pk_ed25519),
+            epoch,
+            signature: String::new(),
+            new_key_signature: String::new(),
+        }
+    }
+
+    /// Message covered by both signatures
+    pub fn signing_payload(&self) -> Result<Vec<u8>, TxError> {
+        signing_payload(ROTATE_KEY_TX_DOMAIN, self, &["signature", "new_key_signature"])
+    }
+
+    /// Sign with the current key and the new key
+    pub fn sign(&mut self, current_key: &SigningKey, new_key: &SigningKey) -> Result<(), TxError> {
+        let payload = self.signing_payload()?;
+        self.signature = hex::encode(current_key.sign(&payload).to_bytes());
+        self.new_key_signature = hex::encode(new_key.sign(&payload).to_bytes());
+        Ok(())
+    }
+
+    /// Id of the rotating validator (checked before its key is looked up)
+    pub fn validator_id(&self) -> Result<ValidatorId, TxError> {
+        decode_32("validator_id", &self.validator_id)
+    }
+
+    /// Validate against the validator's current key and return the new key
+    pub fn validate(&self, chain_id: &str, current_epoch: u64, current_pk: &Ed25519PublicKey) -> Result<Ed25519PublicKey, TxError> {
+        check_chain_and_epoch(chain_id, &self.chain_id, self.epoch, current_epoch)?;
+
+        let new_pk = decode_32("new_pk_ed25519", &self.new_pk_ed25519)?;
+        let payload = self.signing_payload()?;
+        verify("RotateKeyTx", &payload, current_pk, &self.signature)?;
+        verify("RotateKeyTx new-key", &payload, &new_pk, &self.new_key_signature)?;
+        Ok(new_pk)
+    }
+}
+
+impl BondTx {
+    /// Unsigned bond request (`amount` is None for withdrawals)
+    pub fn new(
+        chain_id: impl Into<String>,
+        validator_id: &ValidatorId,
+        action: BondAction,
+        amount: Option<u128>,
+        nonce: u64,
+        epoch: u64,
+    ) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            validator_id: hex::encode(validator_id),
+            action,
+            amount,
+            nonce,
+            epoch,
+            signature: String::new(),
+        }
+    }
+
+    /// Message covered by the signature
+    pub fn signing_payload(&self) -> Result<Vec<u8>, TxError> {
+        signing_payload(BOND_TX_DOMAIN, self, &["signature"])
+    }
+
+    /// Sign with the registered key
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), TxError> {
+        self.signature = hex::encode(key.sign(&self.signing_payload()?).to_bytes());
+        Ok(())
+    }
+
+    /// Id of the validator (checked before its key is looked up)
+    pub fn validator_id(&self) -> Result<ValidatorId, TxError> {
+        decode_32("validator_id", &self.validator_id)
+    }
+
+    /// Validate against the validator's registered key and return the amount
+    /// (0 for withdrawals)
+    pub fn validate(&self, chain_id: &str, current_epoch: u64, registered_pk: &Ed25519PublicKey) -> Result<u128, TxError> {
+        check_chain_and_epoch(chain_id, &self.chain_id, self.epoch, current_epoch)?;
+
+        let amount = match (self.action, self.amount) {
+            (BondAction::Withdraw, None) => 0,
+            (BondAction::Deposit | BondAction::Unbond, Some(amount)) => amount,
+            (BondAction::Withdraw, Some(_)) => {
+                return Err(TxError::InvalidEncoding { field: "amount", reason: "withdrawals carry no amount".to_string() })
+            }
+            (_, None) => return Err(TxError::InvalidEncoding { field: "amount", reason: "missing".to_string() }),
+        };
+        verify("BondTx", &self.signing_payload()?, registered_pk, &self.signature)?;
+        Ok(amount)
+    }
+}
+
+/// Domain separator followed by the canonical JSON of `tx` minus `signature_fields`
+fn signing_payload<T: Serialize>(domain: &[u8], tx: &T, signature_fields: &[&str]) -> Result<Vec<u8>, TxError> {
+    let encoding = |e: String| TxError::InvalidEncoding { field: "transaction", reason: e };
+
+    let mut value = serde_json::to_value(tx).map_err(|e| encoding(e.to_string()))?;
+    if let Value::Object(map) = &mut value {
+        for field in signature_fields {
+            map.remove(*field);
+        }
+    }
+    let canonical = canonicalize(&value).map_err(|e| encoding(e.to_string()))?;
+    Ok([domain, canonical.as_bytes()].concat())
+}
+
+fn check_chain_and_epoch(expected_chain: &str, chain_id: &str, tx_epoch: u64, current_epoch: u64) -> Result<(), TxError> {
+    if chain_id != expected_chain {
+        return Err(TxError::ChainMismatch { expected: expected_chain.to_string(), found: chain_id.to_string() });
+    }
+    if tx_epoch > current_epoch || current_epoch - tx_epoch > TX_VALIDITY_EPOCHS {
+        return Err(TxError::OutsideValidityWindow { tx_epoch, current_epoch });
+    }
+    Ok(())
+}
+
+fn check_network_address(address: &str) -> Result<(), TxError> {
+    // Minimal multiaddr shape: "/<protocol>/<value>..." with no empty segments
+    let segments: Vec<&str> = address.split('/').skip(1).collect();
+    if !address.starts_with('/') || segments.len() < 2 || segments.iter().any(|s| s.is_empty()) {
+        return Err(TxError::InvalidAddress(address.to_string()));
+    }
+    Ok(())
+}
+
+fn decode_32(field: &'static str, value: &str) -> Result<[u8; 32], TxError> {
+    let bytes = hex::decode(value).map_err(|e| TxError::InvalidEncoding { field, reason: e.to_string() })?;
+    bytes.try_into().map_err(|b: Vec<u8>| TxError::InvalidEncoding {
+        field,
+        reason: format!("expected 32 bytes, got {}", b.len()),
+    })
+}
+
+fn verify(tx: &'static str, payload: &[u8], pk: &Ed25519PublicKey, signature: &str) -> Result<(), TxError> {
+    let signature = hex::decode(signature).map_err(|e| TxError::InvalidEncoding { field: "signature", reason: e.to_string() })?;
+    if !poi::verify_attestation(payload, pk, &signature) {
+        return Err(TxError::BadSignature { tx });
+    }
+    Ok(())
+}
+
+/// `Option<u128>` as an optional decimal string
+mod opt_u128_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<u128>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(v) => serializer.serialize_some(&v.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u128>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| s.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN: &str = "bizra-testnet-001";
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn signed_join(seed: u8) -> JoinTx {
+        let mut tx = JoinTx::new(CHAIN, &key(seed), "/ip4/127.0.0.1/tcp/9944", Some(u128::MAX), 5);
+        tx.sign(&key(seed)).unwrap();
+        tx
+    }
+
+    #[test]
+    fn test_join_tx_round_trip() {
+        let tx = signed_join(1);
+        let json = serde_json::to_string(&tx).unwrap();
+        assert!(json.contains(&format!("\"bond\":\"{}\"", u128::MAX)));
+
+        let decoded: JoinTx = serde_json::from_str(&json).unwrap();
+        let (validator_id, pk) = decoded.validate(CHAIN, 6).unwrap();
+        assert_eq!(pk, key(1).verifying_key().to_bytes());
+        assert_eq!(validator_id, validator_id_for(&pk));
+    }
+
+    #[test]
+    fn test_join_tx_rejections() {
+        // validator_id not derived from the key
+        let mut tx = signed_join(1);
+        tx.validator_id = hex::encode([1u8; 32]);
+        tx.sign(&key(1)).unwrap();
+        assert!(matches!(tx.validate(CHAIN, 5), Err(TxError::IdKeyMismatch { .. })));
+
+        // Signed by a different key than the one it registers
+        let mut tx = signed_join(1);
+        tx.sign(&key(2)).unwrap();
+        assert_eq!(tx.validate(CHAIN, 5), Err(TxError::BadSignature { tx: "JoinTx" }));
+
+        // Any field change invalidates the signature
+        let mut tx = signed_join(1);
+        tx.bond = Some(1);
+        assert_eq!(tx.validate(CHAIN, 5), Err(TxError::BadSignature { tx: "JoinTx" }));
+
+        let tx = signed_join(1);
+        assert!(matches!(tx.validate("bizra-devnet", 5), Err(TxError::ChainMismatch { .. })));
+        assert!(matches!(tx.validate(CHAIN, 4), Err(TxError::OutsideValidityWindow { .. })));
+        assert!(matches!(tx.validate(CHAIN, 5 + TX_VALIDITY_EPOCHS + 1), Err(TxError::OutsideValidityWindow { .. })));
+
+        for address in ["", "ip4/127.0.0.1", "/ip4", "/ip4//tcp/1"] {
+            let mut tx = JoinTx::new(CHAIN, &key(1), address, None, 5);
+            tx.sign(&key(1)).unwrap();
+            assert!(matches!(tx.validate(CHAIN, 5), Err(TxError::InvalidAddress(_))), "{:?}", address);
+        }
+    }
+
+    #[test]
+    fn test_exit_and_rotate_tx() {
+        let pk = key(1).verifying_key().to_bytes();
+        let validator_id = validator_id_for(&pk);
+
+        let mut exit = ExitTx::new(CHAIN, &validator_id, 5);
+        exit.sign(&key(1)).unwrap();
+        assert!(exit.validate(CHAIN, 5, &pk).is_ok());
+        assert_eq!(exit.validate(CHAIN, 5, &key(2).verifying_key().to_bytes()), Err(TxError::BadSignature { tx: "ExitTx" }));
+
+        let new_pk = key(2).verifying_key().to_bytes();
+        let mut rotate = RotateKeyTx::new(CHAIN, &validator_id, &new_pk, 5);
+        rotate.sign(&key(1), &key(2)).unwrap();
+        assert_eq!(rotate.validate(CHAIN, 5, &pk), Ok(new_pk));
+
+        // New key must prove possession
+        rotate.sign(&key(1), &key(3)).unwrap();
+        assert_eq!(rotate.validate(CHAIN, 5, &pk), Err(TxError::BadSignature { tx: "RotateKeyTx new-key" }));
+
+        // Signatures are domain separated: an exit signature is not a join signature
+        let mut join = JoinTx::new(CHAIN, &key(1), "/ip4/127.0.0.1/tcp/1", None, 5);
+        join.signature = exit.signature.clone();
+        assert!(join.validate(CHAIN, 5).is_err());
+    }
+
+    #[test]
+    fn test_bond_tx() {
+        let pk = key(1).verifying_key().to_bytes();
+        let validator_id = validator_id_for(&pk);
+
+        let mut deposit = BondTx::new(CHAIN, &validator_id, BondAction::Deposit, Some(u128::MAX), 1, 5);
+        deposit.sign(&key(1)).unwrap();
+        let json = serde_json::to_string(&deposit).unwrap();
+        assert!(json.contains(&format!("\"amount\":\"{}\"", u128::MAX)) && json.contains("\"deposit\""));
+        let decoded: BondTx = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.validate(CHAIN, 5, &pk), Ok(u128::MAX));
+
+        // Only the registered key authorizes it, and the nonce is signed
+        assert_eq!(deposit.validate(CHAIN, 5, &key(2).verifying_key().to_bytes()), Err(TxError::BadSignature { tx: "BondTx" }));
+        let mut replayed = deposit.clone();
+        replayed.nonce = 2;
+        assert_eq!(replayed.validate(CHAIN, 5, &pk), Err(TxError::BadSignature { tx: "BondTx" }));
+
+        let mut withdraw = BondTx::new(CHAIN, &validator_id, BondAction::Withdraw, None, 2, 5);
+        withdraw.sign(&key(1)).unwrap();
+        assert_eq!(withdraw.validate(CHAIN, 5, &pk), Ok(0));
+
+        for (action, amount) in [(BondAction::Withdraw, Some(1)), (BondAction::Unbond, None)] {
+            let mut tx = BondTx::new(CHAIN, &validator_id, action, amount, 3, 5);
+            tx.sign(&key(1)).unwrap();
+            assert!(matches!(tx.validate(CHAIN, 5, &pk), Err(TxError::InvalidEncoding { field: "amount", .. })));
+        }
+    }
+}
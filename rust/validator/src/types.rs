@@ -6,12 +6,20 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::bond::{BondAccount, BondError};
+use crate::poi::score_to_bps;
+
 /// Validator identifier (hash of Ed25519 public key)
 pub type ValidatorId = [u8; 32];
 
 /// Ed25519 public key (32 bytes)
 pub type Ed25519PublicKey = [u8; 32];
 
+/// Validator id bound to an Ed25519 public key: blake3(pk_ed25519)
+pub fn validator_id_for(pk_ed25519: &Ed25519PublicKey) -> ValidatorId {
+    *blake3::hash(pk_ed25519).as_bytes()
+}
+
 /// BLS12-381 public key (48 bytes, optional for aggregated refs)
 /// Note: Using Vec<u8> for serde compatibility (serde doesn't support [u8; 48] by default)
 /// Runtime validation ensures exactly 48 bytes
@@ -19,7 +27,8 @@ pub type Bls12381PublicKey = Vec<u8>;
 
 /// Validator lifecycle status
 ///
-/// States: Pending → Active → Exiting → Exited (or Slashed)
+/// States: Pending → Active → Exiting → Exited (or Slashed); Active ⇄ Offline;
+/// Offline → Exiting
 ///
 /// ## احسان Principle
 /// Explicit state machine prevents ambiguous validator states
@@ -38,6 +47,11 @@ pub enum ValidatorStatus {
     /// - Earning rewards
     Active,
 
+    /// Active validator past the offline threshold
+    /// - Weight not counted, not in active set
+    /// - Returns to Active when seen again
+    Offline,
+
     /// Voluntary exit initiated
     /// - Still participating in consensus
     /// - Unbonding period started
@@ -63,6 +77,7 @@ impl fmt::Display for ValidatorStatus {
         match self {
             ValidatorStatus::Pending => write!(f, "Pending"),
             ValidatorStatus::Active => write!(f, "Active"),
+            ValidatorStatus::Offline => write!(f, "Offline"),
             ValidatorStatus::Exiting => write!(f, "Exiting"),
             ValidatorStatus::Exited => write!(f, "Exited"),
             ValidatorStatus::Slashed => write!(f, "Slashed"),
@@ -87,6 +102,25 @@ impl ValidatorStatus {
     }
 }
 
+/// Key rotation announced by the current key, effective at `activation_epoch`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingKeyRotation {
+    pub pk_ed25519: Ed25519PublicKey,
+    pub announced_epoch: u64,
+    pub activation_epoch: u64,
+}
+
+/// Key that signed for the validator until `retired_at`
+///
+/// Any signature by this key for a slot in or after `retired_at` is key-theft evidence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetiredKey {
+    pub pk_ed25519: Ed25519PublicKey,
+
+    /// First epoch in which the key is no longer valid
+    pub retired_at: u64,
+}
+
 /// Validator record
 ///
 /// Implements ValidatorRecord from spec section 4.1:
@@ -102,7 +136,11 @@ impl ValidatorStatus {
 ///   poi_weight: u128,
 ///   rep_score: u64,
 ///   stake_bond: u128,
-///   last_seen_slot: u64
+///   bond: BondAccount,
+///   last_seen_slot: u64,
+///   quarantine_until: u64?,
+///   pending_key: PendingKeyRotation?,
+///   key_history: [RetiredKey]
 /// }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,12 +172,28 @@ pub struct ValidatorRecord {
     /// Long-term reputation score (0..100,000 scale)
     pub rep_score: u64,
 
-    /// Optional stake bond for Sybil resistance
+    /// Bonded stake for Sybil resistance (counts toward weight)
     pub stake_bond: u128,
 
+    /// Unbonding, withdrawable, withdrawn and burned bond funds
+    #[serde(default)]
+    pub bond: BondAccount,
+
     /// Last slot where validator was seen (liveness tracking)
     pub last_seen_slot: u64,
 
+    /// First epoch after a slashing quarantine (None if not quarantined)
+    #[serde(default)]
+    pub quarantine_until: Option<u64>,
+
+    /// Announced key rotation waiting for its activation epoch
+    #[serde(default)]
+    pub pending_key: Option<PendingKeyRotation>,
+
+    /// Keys rotated out, oldest first, kept for `key_history_epochs` after retiring
+    #[serde(default)]
+    pub key_history: Vec<RetiredKey>,
+
     /// Timestamp when record was created/updated (Unix timestamp in seconds)
     pub updated_at: u64,
 }
@@ -172,7 +226,11 @@ impl ValidatorRecord {
             poi_weight: 0,
             rep_score: MIN_REPUTATION_SCORE, // Start with minimum
             stake_bond: 0,
+            bond: BondAccount::default(),
             last_seen_slot: 0,
+            quarantine_until: None,
+            pending_key: None,
+            key_history: Vec::new(),
             updated_at: current_timestamp(),
         }
     }
@@ -205,17 +263,19 @@ impl ValidatorRecord {
         Ok(())
     }
 
-    /// Initiate voluntary exit (Active → Exiting)
-    pub fn initiate_exit(&mut self, current_epoch: u64) -> Result<(), String> {
-        if self.status != ValidatorStatus::Active {
-            return Err(format!(
-                "Cannot exit validator in status: {}",
-                self.status
-            ));
+    /// Initiate voluntary exit (Active or Offline → Exiting)
+    ///
+    /// The validator leaves `unbonding_delay_epochs` after `current_epoch`. An
+    /// Offline validator does not regain weight by exiting.
+    pub fn initiate_exit(&mut self, current_epoch: u64, unbonding_delay_epochs: u64) -> Result<(), String> {
+        match self.status {
+            ValidatorStatus::Active => {}
+            ValidatorStatus::Offline => self.poi_weight = 0,
+            status => return Err(format!("Cannot exit validator in status: {}", status)),
         }
 
         self.status = ValidatorStatus::Exiting;
-        self.epoch_exit = Some(current_epoch + UNBONDING_DELAY_EPOCHS);
+        self.epoch_exit = Some(current_epoch + unbonding_delay_epochs);
         self.updated_at = current_timestamp();
 
         Ok(())
@@ -244,8 +304,86 @@ impl ValidatorRecord {
         Ok(())
     }
 
+    /// Rejoin after leaving (Exited → Pending)
+    ///
+    /// Bond ledger, key history and reputation carry over; reputation below
+    /// the starting score is raised to it, as for a new validator.
+    pub fn rejoin(&mut self, network_address: String, current_epoch: u64) -> Result<(), String> {
+        if self.status != ValidatorStatus::Exited {
+            return Err(format!("Cannot rejoin validator in status: {}", self.status));
+        }
+
+        self.status = ValidatorStatus::Pending;
+        self.network_address = network_address;
+        self.epoch_join = current_epoch;
+        self.epoch_exit = None;
+        self.poi_weight = 0;
+        self.rep_score = self.rep_score.max(MIN_REPUTATION_SCORE);
+        self.updated_at = current_timestamp();
+
+        Ok(())
+    }
+
+    /// Release from slashing quarantine (Slashed → Exited)
+    ///
+    /// The validator may rejoin with a new JoinTx afterwards ([`Self::rejoin`]).
+    pub fn release_quarantine(&mut self, current_epoch: u64) -> Result<(), String> {
+        if self.status != ValidatorStatus::Slashed {
+            return Err(format!(
+                "Cannot release validator in status: {}",
+                self.status
+            ));
+        }
+
+        let until = self.quarantine_until.ok_or("No quarantine end epoch set")?;
+        if current_epoch < until {
+            return Err(format!(
+                "Quarantine not complete: current={}, until={}",
+                current_epoch, until
+            ));
+        }
+
+        self.status = ValidatorStatus::Exited;
+        self.quarantine_until = None;
+        self.updated_at = current_timestamp();
+
+        Ok(())
+    }
+
+    /// Mark validator offline (Active → Offline)
+    pub fn mark_offline(&mut self) -> Result<(), String> {
+        if self.status != ValidatorStatus::Active {
+            return Err(format!(
+                "Cannot mark validator offline in status: {}",
+                self.status
+            ));
+        }
+
+        self.status = ValidatorStatus::Offline;
+        self.updated_at = current_timestamp();
+
+        Ok(())
+    }
+
+    /// Bring an offline validator back (Offline → Active)
+    pub fn revive(&mut self) -> Result<(), String> {
+        if self.status != ValidatorStatus::Offline {
+            return Err(format!(
+                "Cannot revive validator in status: {}",
+                self.status
+            ));
+        }
+
+        self.status = ValidatorStatus::Active;
+        self.updated_at = current_timestamp();
+
+        Ok(())
+    }
+
     /// Slash validator for misbehavior
     ///
+    /// The bond burn applies to unbonding funds as well as the bonded amount.
+    ///
     /// # Arguments
     /// * `weight_slash_factor` - Factor to reduce weight (0.0-1.0)
     /// * `bond_slash_factor` - Factor to burn bond (0.0-1.0)
@@ -265,9 +403,8 @@ impl ValidatorRecord {
         let weight_reduction = (self.poi_weight as f64 * weight_slash_factor) as u128;
         self.poi_weight = self.poi_weight.saturating_sub(weight_reduction);
 
-        // Burn stake bond
-        let bond_burn = (self.stake_bond as f64 * bond_slash_factor) as u128;
-        self.stake_bond = self.stake_bond.saturating_sub(bond_burn);
+        // Burn stake bond, including funds still unbonding
+        self.bond.slash(&mut self.stake_bond, score_to_bps(bond_slash_factor));
 
         // Reduce reputation (floored at 0)
         self.rep_score = self.rep_score.saturating_sub(rep_penalty);
@@ -279,6 +416,119 @@ impl ValidatorRecord {
         Ok(())
     }
 
+    /// Announce a key rotation taking effect at `activation_epoch`
+    ///
+    /// The current key stays valid until then. One rotation may be pending at a time.
+    pub fn schedule_key_rotation(
+        &mut self,
+        new_pk: Ed25519PublicKey,
+        current_epoch: u64,
+        activation_epoch: u64,
+    ) -> Result<(), String> {
+        if matches!(self.status, ValidatorStatus::Exited | ValidatorStatus::Slashed) {
+            return Err(format!(
+                "Cannot rotate key of validator in status: {}",
+                self.status
+            ));
+        }
+        if let Some(pending) = &self.pending_key {
+            return Err(format!(
+                "Key rotation already pending until epoch {}",
+                pending.activation_epoch
+            ));
+        }
+        if new_pk == self.pk_ed25519 || self.retired_key(&new_pk).is_some() {
+            return Err("New key must not be a current or retired key".to_string());
+        }
+
+        self.pending_key = Some(PendingKeyRotation {
+            pk_ed25519: new_pk,
+            announced_epoch: current_epoch,
+            activation_epoch,
+        });
+        self.updated_at = current_timestamp();
+
+        Ok(())
+    }
+
+    /// Switch to the pending key once its activation epoch is reached
+    ///
+    /// Returns true if the key was rotated; the old key moves to `key_history`.
+    pub fn apply_key_rotation(&mut self, current_epoch: u64) -> bool {
+        match self.pending_key {
+            Some(pending) if pending.activation_epoch <= current_epoch => {
+                self.key_history.push(RetiredKey {
+                    pk_ed25519: self.pk_ed25519,
+                    retired_at: pending.activation_epoch,
+                });
+                self.pk_ed25519 = pending.pk_ed25519;
+                self.pending_key = None;
+                self.updated_at = current_timestamp();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Drop retired keys whose evidence window of `retention_epochs` has closed
+    ///
+    /// A key retired at epoch `r` is kept through epoch `r + retention_epochs - 1`.
+    /// Rotations are at least one epoch apart, so this bounds the history.
+    pub fn prune_key_history(&mut self, current_epoch: u64, retention_epochs: u64) {
+        self.key_history.retain(|k| k.retired_at.saturating_add(retention_epochs) > current_epoch);
+    }
+
+    /// History entry for a rotated-out key
+    pub fn retired_key(&self, pk: &Ed25519PublicKey) -> Option<&RetiredKey> {
+        self.key_history.iter().find(|k| &k.pk_ed25519 == pk)
+    }
+
+    /// Whether `pk` is this validator's current, pending or a retired key
+    pub fn holds_key(&self, pk: &Ed25519PublicKey) -> bool {
+        &self.pk_ed25519 == pk
+            || self.pending_key.is_some_and(|p| &p.pk_ed25519 == pk)
+            || self.retired_key(pk).is_some()
+    }
+
+    /// Deposit or top up the bond
+    pub fn deposit_bond(&mut self, amount: u128) -> Result<(), BondError> {
+        if !matches!(self.status, ValidatorStatus::Pending | ValidatorStatus::Active | ValidatorStatus::Offline) {
+            return Err(BondError::Locked(self.status.to_string()));
+        }
+        BondAccount::deposit(&mut self.stake_bond, amount)?;
+        self.updated_at = current_timestamp();
+        Ok(())
+    }
+
+    /// Start unbonding `amount`, withdrawable from `release_epoch`
+    pub fn unbond(&mut self, amount: u128, release_epoch: u64) -> Result<(), BondError> {
+        if self.status == ValidatorStatus::Slashed {
+            return Err(BondError::Locked(self.status.to_string()));
+        }
+        self.bond.unbond(&mut self.stake_bond, amount, release_epoch)?;
+        self.updated_at = current_timestamp();
+        Ok(())
+    }
+
+    /// Release matured unbonding entries (held while slashed); returns the amount released
+    pub fn release_unbonded(&mut self, current_epoch: u64) -> u128 {
+        if self.status == ValidatorStatus::Slashed {
+            return 0;
+        }
+        let released = self.bond.release(current_epoch);
+        if released > 0 {
+            self.updated_at = current_timestamp();
+        }
+        released
+    }
+
+    /// Withdraw all released bond funds; returns the amount paid out
+    pub fn withdraw_bond(&mut self) -> Result<u128, BondError> {
+        let amount = self.bond.withdraw()?;
+        self.updated_at = current_timestamp();
+        Ok(amount)
+    }
+
     /// Update liveness tracking
     pub fn mark_seen(&mut self, slot: u64) {
         if slot > self.last_seen_slot {
@@ -306,7 +556,7 @@ impl ValidatorRecord {
 }
 
 /// Get current Unix timestamp in seconds
-fn current_timestamp() -> u64 {
+pub(crate) fn current_timestamp() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
@@ -314,15 +564,13 @@ fn current_timestamp() -> u64 {
 }
 
 // Re-export constants from lib.rs for convenience
-use crate::{
-    UNBONDING_DELAY_EPOCHS, LIVENESS_MISS_THRESHOLD, OFFLINE_THRESHOLD,
-    MIN_REPUTATION_FOR_ACTIVATION,
-};
+use crate::{LIVENESS_MISS_THRESHOLD, OFFLINE_THRESHOLD, MIN_REPUTATION_FOR_ACTIVATION};
 const MIN_REPUTATION_SCORE: u64 = 500; // Minimum starting reputation
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::UNBONDING_DELAY_EPOCHS;
 
     fn test_validator_id() -> ValidatorId {
         [1u8; 32]
@@ -347,11 +595,13 @@ mod tests {
         assert!(!ValidatorStatus::Pending.weight_counts());
         assert!(!ValidatorStatus::Exited.weight_counts());
         assert!(!ValidatorStatus::Slashed.weight_counts());
+        assert!(!ValidatorStatus::Offline.weight_counts());
 
         // Active set
         assert!(ValidatorStatus::Active.is_active_set());
         assert!(ValidatorStatus::Exiting.is_active_set());
         assert!(!ValidatorStatus::Pending.is_active_set());
+        assert!(!ValidatorStatus::Offline.is_active_set());
     }
 
     #[test]
@@ -419,9 +669,30 @@ mod tests {
         assert_eq!(validator.status, ValidatorStatus::Active);
 
         // Initiate exit at epoch 5
-        assert!(validator.initiate_exit(5).is_ok());
+        assert!(validator.initiate_exit(5, UNBONDING_DELAY_EPOCHS).is_ok());
         assert_eq!(validator.status, ValidatorStatus::Exiting);
         assert_eq!(validator.epoch_exit, Some(5 + UNBONDING_DELAY_EPOCHS));
+
+        // Not from Exiting or Pending
+        assert!(validator.initiate_exit(6, UNBONDING_DELAY_EPOCHS).is_err());
+    }
+
+    #[test]
+    fn test_offline_validator_can_exit() {
+        let mut validator = ValidatorRecord::new_pending(
+            test_validator_id(),
+            test_ed25519_key(),
+            "/ip4/127.0.0.1/tcp/9944".to_string(),
+            0,
+        );
+        validator.activate(0).unwrap();
+        validator.poi_weight = 1_000;
+        validator.status = ValidatorStatus::Offline;
+
+        validator.initiate_exit(5, 7).unwrap();
+        assert_eq!(validator.status, ValidatorStatus::Exiting);
+        assert_eq!(validator.epoch_exit, Some(12));
+        assert_eq!(validator.poi_weight, 0);
     }
 
     #[test]
@@ -434,7 +705,7 @@ mod tests {
         );
 
         validator.activate(0).unwrap();
-        validator.initiate_exit(5).unwrap();
+        validator.initiate_exit(5, UNBONDING_DELAY_EPOCHS).unwrap();
 
         // Try to complete before unbonding period
         let result = validator.complete_exit(6);
@@ -446,6 +717,34 @@ mod tests {
         assert_eq!(validator.status, ValidatorStatus::Exited);
     }
 
+    #[test]
+    fn test_release_quarantine() {
+        let mut validator = ValidatorRecord::new_pending(
+            test_validator_id(),
+            test_ed25519_key(),
+            "/ip4/127.0.0.1/tcp/9944".to_string(),
+            0,
+        );
+
+        assert!(validator.release_quarantine(3).is_err()); // Not slashed
+
+        validator.activate(0).unwrap();
+        validator.slash(0.5, 0.3, 100).unwrap();
+        validator.quarantine_until = Some(4);
+
+        assert!(validator.release_quarantine(3).unwrap_err().contains("Quarantine not complete"));
+        assert!(validator.release_quarantine(4).is_ok());
+        assert_eq!(validator.status, ValidatorStatus::Exited);
+        assert_eq!(validator.quarantine_until, None);
+
+        // Rejoins with the slash still on record
+        let burned = validator.bond.burned;
+        validator.rejoin("/ip4/127.0.0.1/tcp/9945".to_string(), 5).unwrap();
+        assert_eq!((validator.status, validator.epoch_join), (ValidatorStatus::Pending, 5));
+        assert_eq!((validator.rep_score, validator.bond.burned), (MIN_REPUTATION_SCORE, burned));
+        assert!(validator.rejoin(String::new(), 5).is_err());
+    }
+
     #[test]
     fn test_slash_validator() {
         let mut validator = ValidatorRecord::new_pending(
@@ -510,4 +809,67 @@ mod tests {
         // Offline after threshold
         assert!(validator.is_offline(100 + OFFLINE_THRESHOLD + 1));
     }
+
+    #[test]
+    fn test_key_rotation() {
+        let mut validator = ValidatorRecord::new_pending(
+            test_validator_id(),
+            test_ed25519_key(),
+            "/ip4/127.0.0.1/tcp/9944".to_string(),
+            0,
+        );
+
+        validator.schedule_key_rotation([3u8; 32], 1, 3).unwrap();
+        assert!(validator.schedule_key_rotation([4u8; 32], 1, 3).is_err()); // One at a time
+
+        assert!(!validator.apply_key_rotation(2));
+        assert_eq!(validator.pk_ed25519, test_ed25519_key());
+
+        assert!(validator.apply_key_rotation(3));
+        assert_eq!(validator.pk_ed25519, [3u8; 32]);
+        assert_eq!(validator.pending_key, None);
+        assert_eq!(validator.retired_key(&test_ed25519_key()).unwrap().retired_at, 3);
+
+        // Retired keys cannot come back
+        assert!(validator.schedule_key_rotation(test_ed25519_key(), 4, 6).is_err());
+
+        // Retired keys are kept for the retention window, however many rotations follow
+        for i in 0..17 {
+            validator.schedule_key_rotation([10 + i as u8; 32], 4 + i, 4 + i).unwrap();
+            assert!(validator.apply_key_rotation(4 + i));
+            validator.prune_key_history(4 + i, 64);
+        }
+        assert_eq!(validator.key_history.len(), 18);
+        validator.prune_key_history(3 + 64, 64);
+        assert!(validator.retired_key(&test_ed25519_key()).is_none());
+        assert_eq!(validator.key_history[0].pk_ed25519, [3u8; 32]);
+    }
+
+    #[test]
+    fn test_bond_operations_follow_status() {
+        let mut validator = ValidatorRecord::new_pending(
+            test_validator_id(),
+            test_ed25519_key(),
+            "/ip4/127.0.0.1/tcp/9944".to_string(),
+            0,
+        );
+
+        validator.deposit_bond(1_000).unwrap();
+        validator.activate(0).unwrap();
+        validator.deposit_bond(1_000).unwrap();
+        validator.unbond(500, 2).unwrap();
+        assert_eq!(validator.stake_bond, 1_500);
+
+        // Slash burns 30% of the bonded and unbonding amounts; release waits for the quarantine
+        validator.slash(0.5, 0.3, 0).unwrap();
+        assert_eq!((validator.stake_bond, validator.bond.unbonding_total(), validator.bond.burned), (1_050, 350, 600));
+        assert!(validator.deposit_bond(1).is_err());
+        assert!(validator.unbond(1, 5).is_err());
+        assert_eq!(validator.release_unbonded(2), 0);
+
+        validator.quarantine_until = Some(3);
+        validator.release_quarantine(3).unwrap();
+        assert_eq!(validator.release_unbonded(3), 350);
+        assert_eq!(validator.withdraw_bond(), Ok(350));
+    }
 }
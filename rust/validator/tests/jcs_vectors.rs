@@ -0,0 +1,52 @@
+// Cross-language RFC 8785 (JCS) vectors for PoI attestation digests
+// احسان (Ihsan) principle: one digest, reproducible by every implementation
+
+use serde_json::Value;
+use validator::canonical::{canonicalize, format_f64};
+use validator::{ChainSpec, PoIAttestation};
+
+fn vectors() -> Value {
+    serde_json::from_str(include_str!("vectors/jcs_vectors.json")).expect("vectors file is valid JSON")
+}
+
+#[test]
+fn number_vectors() {
+    let vectors = vectors();
+    for case in vectors["numbers"].as_array().unwrap() {
+        let hex = case["ieee754_hex"].as_str().unwrap();
+        let bits = u64::from_str_radix(hex, 16).unwrap();
+        let expected = case["expected"].as_str().unwrap();
+
+        assert_eq!(format_f64(f64::from_bits(bits)).unwrap(), expected, "ieee754 0x{}", hex);
+    }
+}
+
+#[test]
+fn document_vectors() {
+    let vectors = vectors();
+    for case in vectors["documents"].as_array().unwrap() {
+        let name = case["name"].as_str().unwrap();
+        let input: Value = serde_json::from_str(case["input"].as_str().unwrap()).unwrap();
+
+        assert_eq!(canonicalize(&input).unwrap(), case["expected"].as_str().unwrap(), "{}", name);
+    }
+}
+
+#[test]
+fn attestation_vectors() {
+    let vectors = vectors();
+    for case in vectors["attestations"].as_array().unwrap() {
+        let name = case["name"].as_str().unwrap();
+        let attestation: PoIAttestation = serde_json::from_value(case["attestation"].clone()).unwrap();
+
+        let payload = String::from_utf8(attestation.canonical_payload().unwrap()).unwrap();
+        assert_eq!(payload, case["canonical_payload"].as_str().unwrap(), "{}", name);
+        assert_eq!(attestation.compute_digest().unwrap(), case["blake3_digest"].as_str().unwrap(), "{}", name);
+
+        // The signature in the vector is over the canonical payload
+        let spec = ChainSpec::new(&attestation.anchor.chain_id, &attestation.anchor.genesis_merkle_root);
+        attestation
+            .validate(&spec)
+            .unwrap_or_else(|e| panic!("{}: {}", name, e));
+    }
+}
@@ -0,0 +1,64 @@
+// Fixed-point PoI weight vs the f64 reference
+// احسان (Ihsan) principle: consensus weight is bit-exact on every platform
+
+use proptest::prelude::*;
+use validator::poi::{isqrt, score_to_bps, BPS_SCALE};
+use validator::{PoIWeightCalculator, WeightParams};
+
+fn params() -> impl Strategy<Value = WeightParams> {
+    (0u128..10_000, 0u128..1_000_000, 0u128..10_000, 0u128..10_000).prop_map(
+        |(base_weight, poi_lambda_bps, rep_mu_bps, stake_nu_bps)| WeightParams {
+            base_weight,
+            poi_lambda_bps,
+            rep_mu_bps,
+            stake_nu_bps,
+        },
+    )
+}
+
+proptest! {
+    /// Documented bound: reference - 1 <= fixed <= reference
+    #[test]
+    fn fixed_point_tracks_float_reference(
+        params in params(),
+        poi_carry_bps in 0u128..10_000_000,
+        rep_score in 0u64..=100_000,
+        stake_bond in 0u128..1_000_000_000_000,
+    ) {
+        let calculator = PoIWeightCalculator::with_params(params);
+        let fixed = calculator.calculate_weight(poi_carry_bps, rep_score, stake_bond);
+        let poi_carry = poi_carry_bps as f64 / BPS_SCALE as f64;
+        let reference = calculator.calculate_weight_reference(poi_carry, rep_score, stake_bond);
+
+        // Small slack for the reference's own rounding error
+        let slack = reference * 1e-12;
+        prop_assert!(fixed as f64 <= reference + slack, "fixed {} > reference {}", fixed, reference);
+        prop_assert!(fixed as f64 >= reference - 1.0 - slack, "fixed {} < reference {} - 1", fixed, reference);
+    }
+
+    #[test]
+    fn weight_is_monotonic(
+        poi_carry_bps in 0u128..10_000_000,
+        rep_score in 0u64..100_000,
+        stake_bond in 0u128..u64::MAX as u128,
+    ) {
+        let calculator = PoIWeightCalculator::new();
+        let weight = calculator.calculate_weight(poi_carry_bps, rep_score, stake_bond);
+        prop_assert!(calculator.calculate_weight(poi_carry_bps + 1, rep_score, stake_bond) >= weight);
+        prop_assert!(calculator.calculate_weight(poi_carry_bps, rep_score + 1, stake_bond) >= weight);
+        prop_assert!(calculator.calculate_weight(poi_carry_bps, rep_score, stake_bond + 1) >= weight);
+    }
+
+    #[test]
+    fn isqrt_is_floor_sqrt(n in any::<u128>()) {
+        let r = isqrt(n);
+        prop_assert!(r * r <= n);
+        prop_assert!((r + 1).checked_mul(r + 1).is_none_or(|sq| sq > n));
+    }
+
+    #[test]
+    fn score_to_bps_is_nearest(score in 0.0f64..1_000.0) {
+        let bps = score_to_bps(score) as f64;
+        prop_assert!((bps - score * BPS_SCALE as f64).abs() <= 0.5 + 1e-9);
+    }
+}
@@ -12,30 +12,56 @@
 export class ValidatorRegistryNapi {
   /**
    * Create new validator registry
+   *
+   * @param genesis_path - Genesis file with the chain spec (alpha testnet if omitted)
+   * @param data_dir - Directory for the on-disk attestation store (in-memory if omitted)
    */
-  constructor();
+  constructor(genesis_path?: string, data_dir?: string);
 
   /**
-   * Register new validator (Pending state)
+   * Submit a signed join transaction (Pending state)
    *
-   * @param validator_id - Hex-encoded 32-byte validator ID
-   * @param pk_ed25519 - Hex-encoded 32-byte Ed25519 public key
-   * @param network_address - Multiaddr format (e.g., "/ip4/127.0.0.1/tcp/9944")
-   * @param epoch - Current epoch number
-   * @returns Registration result
-   */
-  register(
-    validator_id: string,
-    pk_ed25519: string,
-    network_address: string,
-    epoch: number
-  ): Promise<{
+   * The validator starts unbonded; its bond is credited by a `deposit` BondTx.
+   * An exited validator rejoins by signing with its current key.
+   *
+   * @param tx_json - JoinTx signed by its own pk_ed25519, as JSON string
+   * @returns Registered validator id and status
+   */
+  submitJoinTx(tx_json: string): Promise<{
     success: boolean;
     validator_id: string;
     status: string;
     epoch_join: number;
-    poi_weight: number;
-    message: string;
+    stake_bond: string;
+  }>;
+
+  /**
+   * Submit a signed exit transaction (Active or Offline → Exiting)
+   *
+   * @param tx_json - ExitTx signed by the registered key, as JSON string
+   * @returns Validator id, status and the epoch the exit completes
+   */
+  submitExitTx(tx_json: string): Promise<{
+    success: boolean;
+    validator_id: string;
+    status: string;
+    epoch_exit: number;
+  }>;
+
+  /**
+   * Submit a signed key rotation transaction
+   *
+   * The new key takes over after the chain's key rotation delay.
+   *
+   * @param tx_json - RotateKeyTx signed by the current and the new key, as JSON string
+   * @returns Validator id, its current key and the scheduled key
+   */
+  submitRotateKeyTx(tx_json: string): Promise<{
+    success: boolean;
+    validator_id: string;
+    pk_ed25519: string;
+    new_pk_ed25519: string;
+    activation_epoch: number;
   }>;
 
   /**
@@ -47,13 +73,22 @@ export class ValidatorRegistryNapi {
   getValidator(validator_id: string): Promise<{
     validator_id: string;
     pk_ed25519: string;
-    status: 'Pending' | 'Active' | 'Exiting' | 'Exited' | 'Slashed';
+    status: 'Pending' | 'Active' | 'Offline' | 'Exiting' | 'Exited' | 'Slashed';
     poi_weight: number;
     rep_score: number;
     stake_bond: string;
+    bond: {
+      unbonding: Array<{ amount: string; release_epoch: number }>;
+      withdrawable: string;
+      withdrawn: string;
+      burned: string;
+      nonce: number;
+    };
     epoch_join: number;
     last_seen_slot: number;
     network_address: string;
+    pending_key: { pk_ed25519: string; activation_epoch: number } | null;
+    key_history: Array<{ pk_ed25519: string; retired_at: number }>;
   } | null>;
 
   /**
@@ -72,6 +107,26 @@ export class ValidatorRegistryNapi {
     active_count: number;
   }>;
 
+  /**
+   * Submit a signed bond transaction
+   *
+   * `action` is `deposit` or `unbond` with a decimal `amount`, or `withdraw`
+   * (pays out everything released) without one. `nonce` is one past the
+   * validator's last applied bond transaction (`bond.nonce` of getValidator).
+   *
+   * @param tx_json - BondTx signed by the validator's current key, as JSON string
+   * @returns Amount moved, new bonded amount and, for unbonds, the release epoch
+   */
+  submitBondTx(tx_json: string): Promise<{
+    success: boolean;
+    validator_id: string;
+    action: 'deposit' | 'unbond' | 'withdraw';
+    amount: string;
+    stake_bond: string;
+    release_epoch: number | null;
+    nonce: number;
+  }>;
+
   /**
    * Get registry statistics
    *
@@ -81,6 +136,7 @@ export class ValidatorRegistryNapi {
     current_epoch: number;
     active_validators: number;
     total_active_weight: number;
+    chain_id: string;
     finality_threshold: number;
     rust_enabled: boolean;
   }>;
@@ -88,6 +144,8 @@ export class ValidatorRegistryNapi {
   /**
    * Submit PoI attestation
    *
+   * The attester key must belong to a registered validator.
+   *
    * @param attestation_json - Complete PoI attestation as JSON string
    * @returns Acceptance status with computed digest
    */
@@ -97,6 +155,10 @@ export class ValidatorRegistryNapi {
     digest: string;
     status: string;
     impact_score: number;
+    epoch: number;
+    validator_id: string;
+    /** New reputation if this was the validator's first attestation of the epoch */
+    rep_score: number | null;
     message: string;
   }>;
 
@@ -118,22 +180,189 @@ export class ValidatorRegistryNapi {
   }>;
 
   /**
-   * Get epoch rewards summary
+   * Get stored attestation by digest
+   *
+   * @param digest - BLAKE3 digest (hex)
+   * @returns Stored record or null if not found
+   */
+  getAttestation(digest: string): Promise<StoredAttestation | null>;
+
+  /**
+   * Query stored attestations
+   *
+   * @param attester - Attester ID filter
+   * @param epoch - Epoch filter
+   * @param chain_id - Chain ID filter
+   * @param offset - Records to skip (default 0)
+   * @param limit - Page size (default 100, max 1000)
+   * @returns Page of stored records
+   */
+  queryAttestations(
+    attester?: string,
+    epoch?: number,
+    chain_id?: string,
+    offset?: number,
+    limit?: number
+  ): Promise<{
+    items: StoredAttestation[];
+    total: number;
+    next_offset: number | null;
+  }>;
+
+  /**
+   * Export stored attestations as JSONL
+   *
+   * @param path - Output file path
+   * @param epoch - Epoch filter (all epochs if omitted)
+   * @returns Number of records written
+   */
+  exportAttestations(path: string, epoch?: number): Promise<number>;
+
+  /**
+   * Subscribe to validator-set changes
+   *
+   * Events arrive in the order the registry applied them. A listener that
+   * falls behind receives `{ type: 'lagged', missed }` and should re-read the
+   * active set. The listener does not keep the process alive.
+   *
+   * @param callback - Called with one event per change
+   */
+  onRegistryEvent(callback: (event: RegistryEvent) => void): void;
+
+  /**
+   * Set the key reward manifests are signed with
+   *
+   * @param secretKey - Hex-encoded 32-byte Ed25519 secret key
+   * @returns Hex-encoded public key of the signer
+   */
+  setRewardSigningKey(secretKey: string): Promise<string>;
+
+  /**
+   * Count a block proposed in the open epoch (feeds the proposer reward share)
+   *
+   * @param validator_id - Hex-encoded 32-byte ID of the proposing validator
+   * @returns Blocks the validator has proposed in the open epoch
+   */
+  recordBlockProposal(validator_id: string): Promise<number>;
+
+  /**
+   * Close the open epoch, update validator PoI weights, distribute rewards and advance the registry
+   *
+   * @returns Summary of the closed epoch with the reward manifest and registry transition
+   */
+  finalizeEpoch(): Promise<EpochSummary & { validators_updated: number; rewards: EpochRewards; transition: EpochTransition }>;
+
+  /**
+   * Get epoch summary
    *
    * @param epoch - Epoch number
-   * @returns Epoch summary with attestation count, rewards, top contributors
+   * @returns Epoch summary with attestation count, total impact, top contributors
    */
-  getEpochSummary(epoch: number): Promise<{
-    epoch: number;
+  getEpochSummary(epoch: number): Promise<EpochSummary>;
+}
+
+/**
+ * Accepted attestation as persisted by the attestation store
+ */
+export interface StoredAttestation {
+  digest: string;
+  epoch: number;
+  validator_id?: string;
+  received_at: number;
+  attestation: any;
+}
+
+/**
+ * Per-epoch PoI aggregation result
+ */
+export interface EpochSummary {
+  epoch: number;
+  status: 'finalized' | 'open' | 'unknown';
+  attestation_count: number;
+  total_impact_score: number;
+  total_credited_impact: number;
+  top_contributors: Array<{
+    validator_id: string;
     attestation_count: number;
-    total_impact_score: number;
-    rewards: {
-      bloom_minted: number;
-      seed_distributed: number;
-    };
-    top_contributors: any[];
-    message?: string;
+    impact_score: number;
+    credited_impact: number;
+    poi_carry: number;
+  }>;
+  /** Present once the epoch is finalized */
+  rewards?: EpochRewards;
+}
+
+/**
+ * BLOOM/SEED distribution of one epoch (amounts are decimal strings)
+ */
+export interface EpochRewards {
+  bloom_minted: string;
+  seed_distributed: string;
+  manifest: RewardManifest;
+}
+
+export interface RewardManifest {
+  chain_id: string;
+  epoch: number;
+  bloom_issued: string;
+  seed_issued: string;
+  bloom_minted: string;
+  seed_distributed: string;
+  bloom_withheld: string;
+  seed_withheld: string;
+  rewards: Array<{
+    validator_id: string;
+    bloom: string;
+    seed: string;
+    bloom_penalty: string;
+    seed_penalty: string;
+    blocks_proposed: number;
+    liveness_misses: number;
   }>;
+  /** Empty if no reward signing key is set */
+  signer: string;
+  signature: string;
+}
+
+/**
+ * Validator-set change (ids and keys are hex)
+ */
+export type RegistryEvent =
+  | { type: 'registered' | 'activated' | 'deactivated' | 'exited' | 'released'; validator_id: string; epoch: number }
+  | { type: 'exit_initiated'; validator_id: string; exit_epoch: number }
+  | {
+      type: 'slashed';
+      validator_id: string;
+      reason: string;
+      slot: number;
+      epoch: number;
+      previous_status: string;
+      weight_slashed: number;
+      bond_burned: number;
+      rep_penalty: number;
+      quarantine_until: number;
+    }
+  | { type: 'went_offline' | 'revived'; validator_id: string }
+  | { type: 'key_rotation_scheduled'; validator_id: string; pk_ed25519: string; activation_epoch: number }
+  | { type: 'key_rotated'; validator_id: string; pk_ed25519: string }
+  | { type: 'weight_updated'; validator_id: string; poi_weight: number }
+  | { type: 'reputation_updated'; validator_id: string; reason: string; rep_score: number }
+  | { type: 'epoch_advanced'; from_epoch: number; to_epoch: number; active_count: number }
+  | { type: 'lagged'; missed: number };
+
+export interface EpochTransition {
+  from_epoch: number;
+  to_epoch: number;
+  churn_limit: number;
+  activated: string[];
+  exited: string[];
+  released: string[];
+  deactivated: string[];
+  keys_rotated: string[];
+  bonds_released: Array<{ validator_id: string; amount: string }>;
+  reputation_decayed: number;
+  pending_remaining: number;
+  active_count: number;
 }
 
 /**
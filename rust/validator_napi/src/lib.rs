@@ -17,25 +17,35 @@
 //! ```javascript
 //! const { ValidatorRegistryNapi } = require('@bizra/validator-napi');
 //!
-//! const registry = new ValidatorRegistryNapi();
-//! await registry.register({
-//!   validator_id: "hex32",
-//!   pk_ed25519: "hex32",
-//!   network_address: "/ip4/127.0.0.1/tcp/9944",
-//!   epoch: 0
-//! });
+//! const registry = new ValidatorRegistryNapi('./genesis/testnet.json');
+//! // JoinTx signed by the joining key (validator_id, pk_ed25519, network_address, ...)
+//! await registry.submitJoinTx(JSON.stringify(joinTx));
 //!
 //! const stats = await registry.getStats();
 //! console.log(stats); // { active_validators: 1, total_weight: 100, ... }
 //! ```
 
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction};
 use napi_derive::napi;
 use validator::{
-    ValidatorRegistry, ValidatorRecord, ValidatorStatus,
-    PoIAttestation, PoIWeightCalculator,
+    ChainSpec, ValidatorRegistry, ValidatorStatus,
+    PoIAttestation, PoIWeightCalculator, RegistryEvent, ReplayGuard,
 };
-use serde_json;
+use validator::epoch::{EpochAggregator, EpochSummary};
+use validator::poi::BPS_SCALE;
+use validator::rewards::RewardManifest;
+use validator::registry_store::{FileRegistryStore, RegistryStore, REGISTRY_SNAPSHOT_DIR};
+use validator::reputation::ReputationReason;
+use validator::tx::{BondTx, ExitTx, JoinTx, RotateKeyTx};
+use validator::store::{
+    export_jsonl, AttestationQuery, AttestationStore, FileAttestationStore,
+    MemoryAttestationStore, StoredAttestation,
+};
+use ed25519_dalek::SigningKey;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Mutex;
 
 /// NAPI wrapper for ValidatorRegistry
 ///
@@ -44,79 +54,140 @@ use serde_json;
 pub struct ValidatorRegistryNapi {
     registry: ValidatorRegistry,
     calculator: PoIWeightCalculator,
+    replay_guard: Mutex<ReplayGuard>,
+    aggregator: Mutex<EpochAggregator>,
+    store: Mutex<Box<dyn AttestationStore>>,
+    manifest_key: Mutex<Option<SigningKey>>,
 }
 
 #[napi]
 impl ValidatorRegistryNapi {
     /// Create new validator registry
+    ///
+    /// # Arguments
+    /// * `genesis_path` - Genesis file with the chain spec (alpha testnet if omitted)
+    /// * `data_dir` - Directory for the on-disk attestation store and registry
+    ///   snapshots (in-memory if omitted)
     #[napi(constructor)]
-    pub fn new() -> Result<Self> {
+    pub fn new(genesis_path: Option<String>, data_dir: Option<String>) -> Result<Self> {
+        let chain_spec = match genesis_path {
+            Some(path) => ChainSpec::load(&path)
+                .map_err(|e| Error::from_reason(format!("Failed to load chain spec: {:#}", e)))?,
+            None => ChainSpec::testnet(),
+        };
+
+        let store: Box<dyn AttestationStore> = match &data_dir {
+            Some(dir) => Box::new(FileAttestationStore::open(dir)
+                .map_err(|e| Error::from_reason(format!("Failed to open attestation store: {}", e)))?),
+            None => Box::new(MemoryAttestationStore::new()),
+        };
+
+        let calculator = PoIWeightCalculator::from_chain_spec(&chain_spec);
+        let aggregation = chain_spec.aggregation;
+        let (registry, epoch) = match &data_dir {
+            Some(dir) => {
+                let registry_store = FileRegistryStore::open(std::path::Path::new(dir).join(REGISTRY_SNAPSHOT_DIR))
+                    .map_err(|e| Error::from_reason(format!("Failed to open registry store: {}", e)))?;
+                let epoch = registry_store.epochs().last().copied().unwrap_or(0);
+                let registry = ValidatorRegistry::with_store(chain_spec, Box::new(registry_store))
+                    .map_err(|e| Error::from_reason(format!("Failed to restore registry: {:#}", e)))?;
+                (registry, epoch)
+            }
+            None => (ValidatorRegistry::with_chain_spec(chain_spec), 0),
+        };
+
+        // Credit of the open epoch and the nonces already used come back from the store
+        let aggregator = EpochAggregator::from_store(epoch, aggregation, store.as_ref())
+            .map_err(|e| Error::from_reason(format!("Failed to rebuild epoch aggregation: {:#}", e)))?;
+        let replay_guard = ReplayGuard::from_store(store.as_ref())
+            .map_err(|e| Error::from_reason(format!("Failed to rebuild replay protection: {:#}", e)))?;
+
         Ok(Self {
-            registry: ValidatorRegistry::new(),
-            calculator: PoIWeightCalculator::new(),
+            calculator,
+            replay_guard: Mutex::new(replay_guard),
+            aggregator: Mutex::new(aggregator),
+            registry,
+            store: Mutex::new(store),
+            manifest_key: Mutex::new(None),
         })
     }
 
-    /// Register new validator (Pending state)
+    /// Submit a signed join transaction (Pending state)
+    ///
+    /// The validator starts unbonded; its bond is credited by a deposit `BondTx`.
+    /// An exited validator rejoins by signing with its current key.
     ///
     /// # Arguments
-    /// * `validator_id` - Hex-encoded 32-byte validator ID
-    /// * `pk_ed25519` - Hex-encoded 32-byte Ed25519 public key
-    /// * `network_address` - Multiaddr format (e.g., "/ip4/127.0.0.1/tcp/9944")
-    /// * `epoch` - Current epoch number
+    /// * `tx_json` - JoinTx signed by its own `pk_ed25519`, as JSON string
     ///
     /// # Returns
-    /// Object with success status and validator details
+    /// Registered validator id and status
     #[napi]
-    pub async fn register(
-        &self,
-        validator_id: String,
-        pk_ed25519: String,
-        network_address: String,
-        epoch: u32,
-    ) -> Result<serde_json::Value> {
-        // Parse hex validator_id
-        let id_bytes = hex::decode(&validator_id)
-            .map_err(|e| Error::from_reason(format!("Invalid validator_id hex: {}", e)))?;
+    pub async fn submit_join_tx(&self, tx_json: String) -> Result<serde_json::Value> {
+        let tx: JoinTx = serde_json::from_str(&tx_json)
+            .map_err(|e| Error::from_reason(format!("Invalid JoinTx JSON: {}", e)))?;
 
-        if id_bytes.len() != 32 {
-            return Err(Error::from_reason("validator_id must be 32 bytes"));
-        }
+        let validator = self.registry.submit_join_tx(&tx).await
+            .map_err(|e| Error::from_reason(format!("JoinTx rejected: {}", e)))?;
 
-        let mut id = [0u8; 32];
-        id.copy_from_slice(&id_bytes);
+        Ok(serde_json::json!({
+            "success": true,
+            "validator_id": hex::encode(validator.validator_id),
+            "status": validator.status.to_string(),
+            "epoch_join": validator.epoch_join,
+            "stake_bond": validator.stake_bond.to_string(),
+        }))
+    }
 
-        // Parse hex pubkey
-        let pk_bytes = hex::decode(&pk_ed25519)
-            .map_err(|e| Error::from_reason(format!("Invalid pk_ed25519 hex: {}", e)))?;
+    /// Submit a signed exit transaction (Active or Offline → Exiting)
+    ///
+    /// # Arguments
+    /// * `tx_json` - ExitTx signed by the registered key, as JSON string
+    ///
+    /// # Returns
+    /// Validator id, status and the epoch the exit completes
+    #[napi]
+    pub async fn submit_exit_tx(&self, tx_json: String) -> Result<serde_json::Value> {
+        let tx: ExitTx = serde_json::from_str(&tx_json)
+            .map_err(|e| Error::from_reason(format!("Invalid ExitTx JSON: {}", e)))?;
 
-        if pk_bytes.len() != 32 {
-            return Err(Error::from_reason("pk_ed25519 must be 32 bytes"));
-        }
+        let validator = self.registry.submit_exit_tx(&tx).await
+            .map_err(|e| Error::from_reason(format!("ExitTx rejected: {}", e)))?;
+
+        Ok(serde_json::json!({
+            "success": true,
+            "validator_id": hex::encode(validator.validator_id),
+            "status": validator.status.to_string(),
+            "epoch_exit": validator.epoch_exit,
+        }))
+    }
 
-        let mut pk = [0u8; 32];
-        pk.copy_from_slice(&pk_bytes);
+    /// Submit a signed key rotation transaction
+    ///
+    /// The new key takes over after the chain's key rotation delay.
+    ///
+    /// # Arguments
+    /// * `tx_json` - RotateKeyTx signed by the current and the new key, as JSON string
+    ///
+    /// # Returns
+    /// Validator id, its current key and the scheduled key
+    #[napi]
+    pub async fn submit_rotate_key_tx(&self, tx_json: String) -> Result<serde_json::Value> {
+        let tx: RotateKeyTx = serde_json::from_str(&tx_json)
+            .map_err(|e| Error::from_reason(format!("Invalid RotateKeyTx JSON: {}", e)))?;
 
-        // Create validator record
-        let validator = ValidatorRecord::new_pending(
-            id,
-            pk,
-            network_address,
-            epoch as u64,
-        );
+        let validator = self.registry.submit_rotate_key_tx(&tx).await
+            .map_err(|e| Error::from_reason(format!("RotateKeyTx rejected: {}", e)))?;
 
-        // Register
-        self.registry.register(validator.clone()).await
-            .map_err(|e| Error::from_reason(format!("Registration failed: {}", e)))?;
+        let pending = validator.pending_key
+            .ok_or_else(|| Error::from_reason("Key rotation was not scheduled"))?;
 
-        // Return response
         Ok(serde_json::json!({
             "success": true,
-            "validator_id": validator_id,
-            "status": "Pending",
-            "epoch_join": epoch,
-            "poi_weight": validator.poi_weight,
-            "message": "Validator registered successfully"
+            "validator_id": hex::encode(validator.validator_id),
+            "pk_ed25519": hex::encode(validator.pk_ed25519),
+            "new_pk_ed25519": hex::encode(pending.pk_ed25519),
+            "activation_epoch": pending.activation_epoch,
         }))
     }
 
@@ -144,9 +215,10 @@ impl ValidatorRegistryNapi {
                 let status_str = match validator.status {
                     ValidatorStatus::Pending => "Pending",
                     ValidatorStatus::Active => "Active",
-                    ValidatorStatus::Exiting { .. } => "Exiting",
+                    ValidatorStatus::Offline => "Offline",
+                    ValidatorStatus::Exiting => "Exiting",
                     ValidatorStatus::Exited => "Exited",
-                    ValidatorStatus::Slashed { .. } => "Slashed",
+                    ValidatorStatus::Slashed => "Slashed",
                 };
 
                 Ok(Some(serde_json::json!({
@@ -156,9 +228,27 @@ impl ValidatorRegistryNapi {
                     "poi_weight": validator.poi_weight,
                     "rep_score": validator.rep_score,
                     "stake_bond": validator.stake_bond.to_string(),
+                    "bond": {
+                        "unbonding": validator.bond.unbonding.iter().map(|e| serde_json::json!({
+                            "amount": e.amount.to_string(),
+                            "release_epoch": e.release_epoch,
+                        })).collect::<Vec<_>>(),
+                        "withdrawable": validator.bond.withdrawable.to_string(),
+                        "withdrawn": validator.bond.withdrawn.to_string(),
+                        "burned": validator.bond.burned.to_string(),
+                        "nonce": validator.bond.nonce,
+                    },
                     "epoch_join": validator.epoch_join,
                     "last_seen_slot": validator.last_seen_slot,
                     "network_address": validator.network_address,
+                    "pending_key": validator.pending_key.map(|p| serde_json::json!({
+                        "pk_ed25519": hex::encode(p.pk_ed25519),
+                        "activation_epoch": p.activation_epoch,
+                    })),
+                    "key_history": validator.key_history.iter().map(|k| serde_json::json!({
+                        "pk_ed25519": hex::encode(k.pk_ed25519),
+                        "retired_at": k.retired_at,
+                    })).collect::<Vec<_>>(),
                 })))
             }
             None => Ok(None)
@@ -177,9 +267,10 @@ impl ValidatorRegistryNapi {
             let status_str = match v.status {
                 ValidatorStatus::Pending => "Pending",
                 ValidatorStatus::Active => "Active",
-                ValidatorStatus::Exiting { .. } => "Exiting",
+                ValidatorStatus::Offline => "Offline",
+                ValidatorStatus::Exiting => "Exiting",
                 ValidatorStatus::Exited => "Exited",
-                ValidatorStatus::Slashed { .. } => "Slashed",
+                ValidatorStatus::Slashed => "Slashed",
             };
 
             serde_json::json!({
@@ -197,6 +288,32 @@ impl ValidatorRegistryNapi {
         }))
     }
 
+    /// Submit a signed bond transaction (deposit, unbond or withdraw)
+    ///
+    /// # Arguments
+    /// * `tx_json` - BondTx signed by the validator's current key, as JSON string
+    ///
+    /// # Returns
+    /// Amount moved, new bonded amount and, for unbonds, the release epoch
+    #[napi]
+    pub async fn submit_bond_tx(&self, tx_json: String) -> Result<serde_json::Value> {
+        let tx: BondTx = serde_json::from_str(&tx_json)
+            .map_err(|e| Error::from_reason(format!("Invalid BondTx JSON: {}", e)))?;
+
+        let receipt = self.registry.submit_bond_tx(&tx).await
+            .map_err(|e| Error::from_reason(format!("BondTx rejected: {}", e)))?;
+
+        Ok(serde_json::json!({
+            "success": true,
+            "validator_id": tx.validator_id,
+            "action": receipt.action,
+            "amount": receipt.amount.to_string(),
+            "stake_bond": receipt.stake_bond.to_string(),
+            "release_epoch": receipt.release_epoch,
+            "nonce": tx.nonce,
+        }))
+    }
+
     /// Get registry statistics
     ///
     /// # Returns
@@ -211,13 +328,16 @@ impl ValidatorRegistryNapi {
             "current_epoch": current_epoch,
             "active_validators": active_count,
             "total_active_weight": total_weight,
-            "finality_threshold": validator::DEFAULT_FINALITY_THRESHOLD,
+            "chain_id": self.registry.chain_spec().chain_id,
+            "finality_threshold": self.registry.chain_spec().thresholds.finality_threshold,
             "rust_enabled": true,
         }))
     }
 
     /// Submit PoI attestation
     ///
+    /// The attester key must belong to a registered validator.
+    ///
     /// # Arguments
     /// * `attestation_json` - Complete PoI attestation as JSON string
     ///
@@ -230,15 +350,50 @@ impl ValidatorRegistryNapi {
             .map_err(|e| Error::from_reason(format!("Invalid attestation JSON: {}", e)))?;
 
         // Validate attestation
-        attestation.validate("bizra-testnet-001", "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f")
+        attestation.validate(self.registry.chain_spec())
             .map_err(|e| Error::from_reason(format!("Attestation validation failed: {}", e)))?;
 
         // Compute digest
         let digest = attestation.compute_digest()
             .map_err(|e| Error::from_reason(format!("Digest computation failed: {}", e)))?;
 
-        // TODO: Store attestation in database (Day 13+)
-        // For now, just accept it
+        // Only registered validators may attest; unknown keys never reach the replay guard
+        let pk = attestation.attester_ed25519_key()
+            .map_err(|e| Error::from_reason(format!("Invalid attester key: {}", e)))?;
+        let validator = self.registry.find_by_pubkey(&pk).await
+            .ok_or_else(|| Error::from_reason(format!("Attestation rejected: attester key {} is not registered", hex::encode(pk))))?;
+        let mut aggregator = self.aggregator.lock().await;
+        let epoch = aggregator.current_epoch();
+
+        // Reject replayed nonces/digests and overlapping windows from the same attester,
+        // recording the attestation before it is stored and forgetting it if the write fails
+        let mut replay_guard = self.replay_guard.lock().await;
+        replay_guard.check_and_record(&attestation)
+            .map_err(|e| Error::from_reason(format!("Attestation rejected: {}", e)))?;
+
+        // Persist before crediting so the store can replay every counted attestation
+        let record = StoredAttestation::new(attestation.clone(), epoch, Some(validator.validator_id));
+        let stored = match record {
+            Ok(record) => self.store.lock().await.insert(record),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = stored {
+            replay_guard.forget(&attestation)
+                .map_err(|e| Error::from_reason(format!("Attestation rejected: {}", e)))?;
+            return Err(Error::from_reason(format!("Attestation rejected: {}", e)));
+        }
+        drop(replay_guard);
+
+        // The first attestation credited in an epoch earns an active validator reputation
+        aggregator.record(validator.validator_id, &attestation);
+        let mut rep_score = None;
+        if validator.status.is_active_set() && aggregator.attestation_count(&validator.validator_id) == 1 {
+            let score = self.registry
+                .record_reputation(&validator.validator_id, ReputationReason::TimelyAttestation)
+                .await
+                .map_err(|e| Error::from_reason(format!("Failed to record reputation: {}", e)))?;
+            rep_score = Some(score);
+        }
 
         Ok(serde_json::json!({
             "success": true,
@@ -246,6 +401,9 @@ impl ValidatorRegistryNapi {
             "digest": digest,
             "status": "accepted",
             "impact_score": attestation.measurement.impact_score,
+            "epoch": epoch,
+            "validator_id": hex::encode(validator.validator_id),
+            "rep_score": rep_score,
             "message": "Attestation accepted and verified"
         }))
     }
@@ -264,10 +422,7 @@ impl ValidatorRegistryNapi {
             .map_err(|e| Error::from_reason(format!("Invalid attestation JSON: {}", e)))?;
 
         // Validate attestation
-        let validation_result = attestation.validate(
-            "bizra-testnet-001",
-            "d9c9fa504add65a1be737f3fe3447bc056fd1aad2850f491184208354f41926f"
-        );
+        let validation_result = attestation.validate(self.registry.chain_spec());
 
         let (valid, reasons) = match validation_result {
             Ok(_) => (true, Vec::new()),
@@ -293,33 +448,298 @@ impl ValidatorRegistryNapi {
         }))
     }
 
-    /// Get epoch rewards summary
+    /// Get stored attestation by digest
     ///
     /// # Arguments
-    /// * `epoch` - Epoch number
+    /// * `digest` - BLAKE3 digest (hex)
     ///
     /// # Returns
-    /// Epoch summary with attestation count, rewards, top contributors
+    /// Stored record or null if not found
     #[napi]
-    pub async fn get_epoch_summary(&self, epoch: u32) -> Result<serde_json::Value> {
-        // TODO: Implement epoch tracking in Day 13+
-        // For now, return placeholder
+    pub async fn get_attestation(&self, digest: String) -> Result<Option<serde_json::Value>> {
+        let record = self.store.lock().await.get(&digest);
+        record
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| Error::from_reason(format!("Serialization failed: {}", e)))
+    }
+
+    /// Query stored attestations
+    ///
+    /// # Arguments
+    /// * `attester` - Attester ID filter (optional)
+    /// * `epoch` - Epoch filter (optional)
+    /// * `chain_id` - Chain ID filter (optional)
+    /// * `offset` - Records to skip (default 0)
+    /// * `limit` - Page size (default 100, max 1000)
+    ///
+    /// # Returns
+    /// Page of stored records with total count and next offset
+    #[napi]
+    pub async fn query_attestations(
+        &self,
+        attester: Option<String>,
+        epoch: Option<u32>,
+        chain_id: Option<String>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<serde_json::Value> {
+        let query = attestation_query(attester, epoch, chain_id, offset, limit);
+        let page = self.store.lock().await.query(&query);
 
         Ok(serde_json::json!({
-            "epoch": epoch,
-            "attestation_count": 0,
-            "total_impact_score": 0.0,
-            "rewards": {
-                "bloom_minted": 0,
-                "seed_distributed": 0
-            },
-            "top_contributors": [],
-            "message": "Epoch tracking coming in Day 13+"
+            "items": page.items,
+            "total": page.total,
+            "next_offset": page.next_offset,
         }))
     }
+
+    /// Export stored attestations as JSONL (one record per line)
+    ///
+    /// # Arguments
+    /// * `path` - Output file path
+    /// * `epoch` - Epoch filter (optional; all epochs if omitted)
+    ///
+    /// # Returns
+    /// Number of records written
+    #[napi]
+    pub async fn export_attestations(&self, path: String, epoch: Option<u32>) -> Result<u32> {
+        let file = std::fs::File::create(&path)
+            .map_err(|e| Error::from_reason(format!("Failed to create {}: {}", path, e)))?;
+        let query = attestation_query(None, epoch, None, None, None);
+
+        let store = self.store.lock().await;
+        let written = export_jsonl(store.as_ref(), &query, std::io::BufWriter::new(file))
+            .map_err(|e| Error::from_reason(format!("Export failed: {}", e)))?;
+        Ok(written as u32)
+    }
+
+    /// Subscribe to validator-set changes
+    ///
+    /// The callback receives every registry event (registration, activation,
+    /// exit, slashing, weight updates, ...) in the order it was applied. If the
+    /// listener falls behind, it receives `{ type: "lagged", missed }` and
+    /// should re-read the active set. The listener does not keep Node alive.
+    ///
+    /// # Arguments
+    /// * `callback` - Called with one event object per change
+    #[napi(ts_args_type = "callback: (event: RegistryEvent) => void")]
+    pub fn on_registry_event(&self, env: Env, callback: JsFunction) -> Result<()> {
+        let mut listener: ThreadsafeFunction<serde_json::Value, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<serde_json::Value>| Ok(vec![ctx.value]))?;
+        listener.unref(&env)?;
+
+        let mut events = self.registry.subscribe();
+        spawn(async move {
+            loop {
+                let json = match events.recv().await {
+                    Ok(event) => registry_event_json(&event),
+                    Err(RecvError::Lagged(missed)) => serde_json::json!({ "type": "lagged", "missed": missed }),
+                    Err(RecvError::Closed) => break,
+                };
+                if listener.call(json, ThreadsafeFunctionCallMode::NonBlocking) == Status::Closing {
+                    break;
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Set the key reward manifests are signed with
+    ///
+    /// # Arguments
+    /// * `secret_key` - Hex-encoded 32-byte Ed25519 secret key
+    ///
+    /// # Returns
+    /// Hex-encoded public key of the signer
+    #[napi]
+    pub async fn set_reward_signing_key(&self, secret_key: String) -> Result<String> {
+        let bytes: [u8; 32] = hex::decode(&secret_key)
+            .map_err(|e| Error::from_reason(format!("Invalid secret_key hex: {}", e)))?
+            .try_into()
+            .map_err(|_| Error::from_reason("secret_key must be 32 bytes"))?;
+
+        let key = SigningKey::from_bytes(&bytes);
+        let public = hex::encode(key.verifying_key().to_bytes());
+        *self.manifest_key.lock().await = Some(key);
+        Ok(public)
+    }
+
+    /// Count a block proposed in the open epoch
+    ///
+    /// Proposal counts feed the proposer share of the epoch's rewards.
+    ///
+    /// # Arguments
+    /// * `validator_id` - Hex-encoded 32-byte ID of the proposing validator
+    ///
+    /// # Returns
+    /// Blocks the validator has proposed in the open epoch
+    #[napi]
+    pub async fn record_block_proposal(&self, validator_id: String) -> Result<u32> {
+        let id: [u8; 32] = hex::decode(&validator_id)
+            .map_err(|e| Error::from_reason(format!("Invalid validator_id hex: {}", e)))?
+            .try_into()
+            .map_err(|_| Error::from_reason("validator_id must be 32 bytes"))?;
+        if self.registry.get(&id).await.is_none() {
+            return Err(Error::from_reason(format!("Unknown validator: {}", validator_id)));
+        }
+
+        let mut aggregator = self.aggregator.lock().await;
+        aggregator.record_proposal(id);
+        Ok(aggregator.blocks_proposed()[&id] as u32)
+    }
+
+    /// Close the open epoch
+    ///
+    /// Folds the epoch's attestations into each validator's PoI carry, updates
+    /// `poi_weight` of weight-counting validators, distributes the epoch's
+    /// BLOOM/SEED rewards (signed if a reward signing key is set) and advances
+    /// the registry epoch (activations, exits, quarantine releases, reputation decay).
+    /// The registry steps are one stored change, kept with the reward manifest;
+    /// if it fails, both the registry and the aggregator stay in the open epoch.
+    ///
+    /// # Returns
+    /// Summary of the closed epoch with the reward manifest and registry transition
+    #[napi]
+    pub async fn finalize_epoch(&self) -> Result<serde_json::Value> {
+        let mut aggregator = self.aggregator.lock().await;
+        let epoch = aggregator.current_epoch();
+        let closed = aggregator.close(epoch)
+            .map_err(|e| Error::from_reason(format!("Epoch finalization failed: {}", e)))?;
+
+        // One registry change; the aggregator moves on only once it is stored
+        let key = self.manifest_key.lock().await.clone();
+        let close = self.registry
+            .close_epoch(&closed.carry, aggregator.blocks_proposed(), &self.calculator, key.as_ref())
+            .await
+            .map_err(|e| Error::from_reason(format!("Epoch finalization failed: {:#}", e)))?;
+        let summary = aggregator.advance(closed)
+            .map_err(|e| Error::from_reason(format!("Epoch finalization failed: {}", e)))?;
+        let transition = close.transition;
+
+        let mut json = epoch_summary_json(&summary);
+        json["validators_updated"] = serde_json::json!(close.weights_updated);
+        json["rewards"] = rewards_json(&close.rewards)?;
+        json["transition"] = serde_json::json!({
+            "from_epoch": transition.from_epoch,
+            "to_epoch": transition.to_epoch,
+            "churn_limit": transition.churn_limit,
+            "activated": transition.activated.iter().map(hex::encode).collect::<Vec<_>>(),
+            "exited": transition.exited.iter().map(hex::encode).collect::<Vec<_>>(),
+            "released": transition.released.iter().map(hex::encode).collect::<Vec<_>>(),
+            "deactivated": transition.deactivated.iter().map(hex::encode).collect::<Vec<_>>(),
+            "keys_rotated": transition.keys_rotated.iter().map(hex::encode).collect::<Vec<_>>(),
+            "bonds_released": transition.bonds_released.iter()
+                .map(|(id, amount)| serde_json::json!({ "validator_id": hex::encode(id), "amount": amount.to_string() }))
+                .collect::<Vec<_>>(),
+            "reputation_decayed": transition.reputation_decayed,
+            "pending_remaining": transition.pending_remaining,
+            "active_count": transition.active_count,
+        });
+        Ok(json)
+    }
+
+    /// Get epoch summary
+    ///
+    /// # Arguments
+    /// * `epoch` - Epoch number
+    ///
+    /// # Returns
+    /// Epoch summary with attestation count, total impact, top contributors
+    /// and, once finalized, the reward manifest
+    #[napi]
+    pub async fn get_epoch_summary(&self, epoch: u32) -> Result<serde_json::Value> {
+        let aggregator = self.aggregator.lock().await;
+
+        match aggregator.summary(epoch as u64) {
+            Some(summary) => {
+                let mut json = epoch_summary_json(summary);
+                let manifest = self.registry.reward_manifest(epoch as u64).await
+                    .map_err(|e| Error::from_reason(format!("Failed to read reward manifest: {}", e)))?;
+                if let Some(manifest) = manifest {
+                    json["rewards"] = rewards_json(&manifest)?;
+                }
+                Ok(json)
+            }
+            None => Ok(serde_json::json!({
+                "epoch": epoch,
+                "status": if epoch as u64 >= aggregator.current_epoch() { "open" } else { "unknown" },
+                "attestation_count": 0,
+                "total_impact_score": 0.0,
+                "total_credited_impact": 0.0,
+                "top_contributors": [],
+            })),
+        }
+    }
+}
+
+/// Build a store query from optional NAPI arguments
+fn attestation_query(
+    attester: Option<String>,
+    epoch: Option<u32>,
+    chain_id: Option<String>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> AttestationQuery {
+    let defaults = AttestationQuery::default();
+    AttestationQuery {
+        attester,
+        epoch: epoch.map(u64::from),
+        chain_id,
+        offset: offset.map_or(defaults.offset, |o| o as usize),
+        limit: limit.map_or(defaults.limit, |l| l as usize),
+    }
+}
+
+/// Render a registry event with hex-encoded ids and keys
+fn registry_event_json(event: &RegistryEvent) -> serde_json::Value {
+    let mut json = serde_json::to_value(event).unwrap_or_else(|_| serde_json::json!({ "type": event.kind() }));
+    if let Some(fields) = json.as_object_mut() {
+        for key in ["validator_id", "pk_ed25519"] {
+            let bytes = fields.get(key).and_then(|v| serde_json::from_value::<[u8; 32]>(v.clone()).ok());
+            if let Some(bytes) = bytes {
+                fields.insert(key.to_string(), serde_json::json!(hex::encode(bytes)));
+            }
+        }
+    }
+    json
+}
+
+/// Render a reward manifest with its totals
+fn rewards_json(manifest: &RewardManifest) -> Result<serde_json::Value> {
+    let manifest_json = serde_json::to_value(manifest)
+        .map_err(|e| Error::from_reason(format!("Failed to encode reward manifest: {}", e)))?;
+    Ok(serde_json::json!({
+        "bloom_minted": manifest.bloom_minted.to_string(),
+        "seed_distributed": manifest.seed_distributed.to_string(),
+        "manifest": manifest_json,
+    }))
+}
+
+/// Render an epoch summary, converting bps back to impact units
+fn epoch_summary_json(summary: &EpochSummary) -> serde_json::Value {
+    let units = |bps: u128| bps as f64 / BPS_SCALE as f64;
+
+    let top_contributors: Vec<serde_json::Value> = summary.top_contributors.iter().map(|c| {
+        serde_json::json!({
+            "validator_id": hex::encode(c.validator_id),
+            "attestation_count": c.attestation_count,
+            "impact_score": units(c.impact_bps),
+            "credited_impact": units(c.credited_impact_bps),
+            "poi_carry": units(c.poi_carry_bps),
+        })
+    }).collect();
+
+    serde_json::json!({
+        "epoch": summary.epoch,
+        "status": "finalized",
+        "attestation_count": summary.attestation_count,
+        "total_impact_score": units(summary.total_impact_bps),
+        "total_credited_impact": units(summary.total_credited_impact_bps),
+        "top_contributors": top_contributors,
+    })
 }
 
-/// Module initialization for NAPI
 #[napi]
 pub fn get_version() -> String {
     format!("validator-napi v{}", env!("CARGO_PKG_VERSION"))